@@ -1,14 +1,21 @@
+use std::path::PathBuf;
+
 use anyhow::Error;
 use clap::Parser;
 use uuid::Uuid;
 
 use fedimovies::activitypub::{
     actors::helpers::update_remote_profile, builders::delete_note::prepare_delete_note,
-    builders::delete_person::prepare_delete_person, fetcher::fetchers::fetch_actor,
-    fetcher::helpers::import_from_outbox,
+    builders::delete_person::prepare_delete_person, builders::update_person::prepare_update_person,
+    fetcher::fetchers::fetch_actor, fetcher::helpers::import_from_outbox,
 };
 use fedimovies::admin::roles::{role_from_str, ALLOWED_ROLES};
 use fedimovies::media::{remove_files, remove_media, MediaStorage};
+use fedimovies::tmdb::{
+    get_movie_metadata_provider, get_or_create_movie_user_by_id, import_movie_id_export,
+    import_movie_metadata_dump, lookup_and_create_movie_user, open_export_file, refresh_media_user,
+    MovieMetadataProvider, MovieUsername,
+};
 use fedimovies::validators::{emojis::EMOJI_LOCAL_MAX_SIZE, users::validate_local_username};
 use fedimovies_config::Config;
 use fedimovies_models::{
@@ -25,10 +32,11 @@ use fedimovies_models::{
         delete_profile, find_empty_profiles, find_unreachable, get_profile_by_id,
         get_profile_by_remote_actor_id,
     },
+    profiles::types::AccountKind,
     subscriptions::queries::reset_subscriptions,
     users::queries::{
-        create_invite_code, create_user, get_invite_codes, get_user_by_id, set_user_password,
-        set_user_role,
+        create_invite_code, create_user, get_invite_codes, get_user_by_id, get_user_by_name,
+        get_users_by_account_kind, set_user_password, set_user_role,
     },
     users::types::UserCreateData,
 };
@@ -67,6 +75,11 @@ pub enum SubCommand {
     PruneRemoteEmojis(PruneRemoteEmojis),
     ListUnreachableActors(ListUnreachableActors),
     ImportEmoji(ImportEmoji),
+    ImportMovie(ImportMovie),
+    ImportMovies(ImportMovies),
+    RefreshMovie(RefreshMovie),
+    ListMovies(ListMovies),
+    ImportMovieCatalog(ImportMovieCatalog),
     UpdateCurrentBlock(UpdateCurrentBlock),
     ResetSubscriptions(ResetSubscriptions),
     CreateMoneroWallet(CreateMoneroWallet),
@@ -154,6 +167,8 @@ impl CreateUser {
             wallet_address: None,
             invite_code: None,
             role,
+            account_kind: AccountKind::User,
+            tmdb_id: None,
         };
         create_user(db_client, user_data).await?;
         println!("user created");
@@ -501,6 +516,203 @@ impl ImportEmoji {
     }
 }
 
+fn get_movie_provider(config: &Config) -> Result<Box<dyn MovieMetadataProvider>, Error> {
+    get_movie_metadata_provider(config).ok_or_else(|| Error::msg("TMDB API key is not configured"))
+}
+
+/// Create movie account from TMDB by TMDB ID or by title and release year
+#[derive(Parser)]
+pub struct ImportMovie {
+    /// TMDB ID or movie title
+    movie: String,
+    /// Release year (required when movie is imported by title)
+    year: Option<u32>,
+}
+
+impl ImportMovie {
+    pub async fn execute(
+        &self,
+        config: &Config,
+        db_client: &mut impl DatabaseClient,
+    ) -> Result<(), Error> {
+        let movie_provider = get_movie_provider(config)?;
+        let user = match self.year {
+            Some(year) => {
+                let username = MovieUsername::new(&self.movie, year).short_form();
+                lookup_and_create_movie_user(
+                    &config.instance(),
+                    db_client,
+                    Some(movie_provider.as_ref()),
+                    &config.media_dir(),
+                    &username,
+                )
+                .await?
+            }
+            None => {
+                let tmdb_id: u32 = self
+                    .movie
+                    .parse()
+                    .map_err(|_| Error::msg("release year is required to import by title"))?;
+                get_or_create_movie_user_by_id(
+                    &config.instance(),
+                    db_client,
+                    movie_provider.as_ref(),
+                    &config.media_dir(),
+                    tmdb_id,
+                )
+                .await?
+            }
+        };
+        println!("movie account {} imported", user.profile.username);
+        Ok(())
+    }
+}
+
+/// Create movie accounts from a file with TMDB IDs (one per line)
+#[derive(Parser)]
+pub struct ImportMovies {
+    path: String,
+}
+
+impl ImportMovies {
+    pub async fn execute(
+        &self,
+        config: &Config,
+        db_client: &mut impl DatabaseClient,
+    ) -> Result<(), Error> {
+        let movie_provider = get_movie_provider(config)?;
+        let contents = std::fs::read_to_string(&self.path)?;
+        let mut imported_count = 0;
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            };
+            let tmdb_id: u32 = match line.parse() {
+                Ok(tmdb_id) => tmdb_id,
+                Err(_) => {
+                    println!("invalid TMDB ID: {}", line);
+                    continue;
+                }
+            };
+            match get_or_create_movie_user_by_id(
+                &config.instance(),
+                db_client,
+                movie_provider.as_ref(),
+                &config.media_dir(),
+                tmdb_id,
+            )
+            .await
+            {
+                Ok(user) => {
+                    println!("movie account {} imported", user.profile.username);
+                    imported_count += 1;
+                }
+                Err(error) => println!("failed to import movie {}: {}", tmdb_id, error),
+            };
+        }
+        println!("{} movies imported", imported_count);
+        Ok(())
+    }
+}
+
+/// Re-fetch metadata of movie or TV show account from TMDB
+#[derive(Parser)]
+pub struct RefreshMovie {
+    username: String,
+}
+
+impl RefreshMovie {
+    pub async fn execute(
+        &self,
+        config: &Config,
+        db_client: &mut impl DatabaseClient,
+    ) -> Result<(), Error> {
+        let movie_provider = get_movie_provider(config)?;
+        let user = get_user_by_name(db_client, &self.username).await?;
+        if !user.profile.is_media() {
+            return Err(Error::msg("not a movie account"));
+        };
+        let instance = config.instance();
+        let (user, deletion_queue, _) = refresh_media_user(
+            &instance,
+            db_client,
+            movie_provider.as_ref(),
+            &config.media_dir(),
+            &user,
+        )
+        .await?;
+        remove_media(config, deletion_queue).await;
+        prepare_update_person(db_client, &instance, &user, None)
+            .await?
+            .enqueue(db_client)
+            .await?;
+        println!("profile updated");
+        Ok(())
+    }
+}
+
+/// List movie accounts
+#[derive(Parser)]
+pub struct ListMovies;
+
+impl ListMovies {
+    pub async fn execute(
+        &self,
+        _config: &Config,
+        db_client: &impl DatabaseClient,
+    ) -> Result<(), Error> {
+        let users = get_users_by_account_kind(db_client, &AccountKind::Movie).await?;
+        println!(
+            "{0: <60} | {1: <10} | {2: <35}",
+            "username", "TMDB ID", "updated at",
+        );
+        for user in users {
+            println!(
+                "{0: <60} | {1: <10} | {2: <35}",
+                user.profile.username,
+                user.profile.tmdb_id.unwrap_or_default(),
+                user.profile.updated_at.to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Load TMDB daily ID export and cached movie details into local movie catalog
+#[derive(Parser)]
+pub struct ImportMovieCatalog {
+    /// Path to TMDB daily ID export (e.g. movie_ids_05_15_2023.json.gz)
+    #[clap(long)]
+    ids: Option<PathBuf>,
+    /// Path to JSON lines file with TMDB movie details (can be gzip-compressed)
+    #[clap(long)]
+    metadata: Option<PathBuf>,
+}
+
+impl ImportMovieCatalog {
+    pub async fn execute(
+        &self,
+        _config: &Config,
+        db_client: &impl DatabaseClient,
+    ) -> Result<(), Error> {
+        if self.ids.is_none() && self.metadata.is_none() {
+            return Err(Error::msg("nothing to import"));
+        };
+        if let Some(ref ids_path) = self.ids {
+            let reader = open_export_file(ids_path)?;
+            let imported_count = import_movie_id_export(db_client, reader).await?;
+            println!("{} movie IDs imported", imported_count);
+        };
+        if let Some(ref metadata_path) = self.metadata {
+            let reader = open_export_file(metadata_path)?;
+            let imported_count = import_movie_metadata_dump(db_client, reader).await?;
+            println!("{} movie details imported", imported_count);
+        };
+        Ok(())
+    }
+}
+
 /// Update blockchain synchronization starting block
 #[derive(Parser)]
 pub struct UpdateCurrentBlock {
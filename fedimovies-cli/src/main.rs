@@ -59,6 +59,13 @@ async fn main() {
                     cmd.execute(&config, db_client).await.unwrap()
                 }
                 SubCommand::ImportEmoji(cmd) => cmd.execute(&config, db_client).await.unwrap(),
+                SubCommand::ImportMovie(cmd) => cmd.execute(&config, db_client).await.unwrap(),
+                SubCommand::ImportMovies(cmd) => cmd.execute(&config, db_client).await.unwrap(),
+                SubCommand::RefreshMovie(cmd) => cmd.execute(&config, db_client).await.unwrap(),
+                SubCommand::ListMovies(cmd) => cmd.execute(&config, db_client).await.unwrap(),
+                SubCommand::ImportMovieCatalog(cmd) => {
+                    cmd.execute(&config, db_client).await.unwrap()
+                }
                 SubCommand::UpdateCurrentBlock(cmd) => {
                     cmd.execute(&config, db_client).await.unwrap()
                 }
@@ -0,0 +1,42 @@
+use serde::Deserialize;
+
+fn default_only_top_level() -> bool {
+    true
+}
+
+fn default_one_per_author() -> bool {
+    true
+}
+
+/// Rules for reposting posts that mention movie accounts
+#[derive(Clone, Deserialize)]
+pub struct BoostPolicyConfig {
+    // Don't repost replies
+    #[serde(default = "default_only_top_level")]
+    pub only_top_level: bool,
+    // Repost only posts with star rating
+    #[serde(default)]
+    pub require_rating: bool,
+    // Repost posts made by automated accounts
+    #[serde(default)]
+    pub allow_bots: bool,
+    // Repost at most one post of each author
+    #[serde(default = "default_one_per_author")]
+    pub one_per_author: bool,
+    // Posts from these instances are not reposted
+    // (blocked instances are always excluded)
+    #[serde(default)]
+    pub muted_instances: Vec<String>,
+}
+
+impl Default for BoostPolicyConfig {
+    fn default() -> Self {
+        Self {
+            only_top_level: default_only_top_level(),
+            require_rating: false,
+            allow_bots: false,
+            one_per_author: default_one_per_author(),
+            muted_instances: vec![],
+        }
+    }
+}
@@ -7,6 +7,7 @@ use url::Url;
 
 use fedimovies_utils::urls::normalize_url;
 
+use super::boost_policy::BoostPolicyConfig;
 use super::environment::Environment;
 use super::federation::FederationConfig;
 use super::limits::Limits;
@@ -22,6 +23,14 @@ fn default_login_message() -> String {
     "What?!".to_string()
 }
 
+fn default_tmdb_api_url() -> String {
+    "https://api.themoviedb.org/3".to_string()
+}
+
+fn default_tmdb_refresh_days() -> u32 {
+    30
+}
+
 #[derive(Clone, Deserialize)]
 pub struct Config {
     // Properties auto-populated from the environment
@@ -57,8 +66,11 @@ pub struct Config {
 
     #[serde(default)]
     pub tmdb_api_key: Option<String>,
-    #[serde(default)]
-    pub movie_user_password: Option<String>,
+    #[serde(default = "default_tmdb_api_url")]
+    pub tmdb_api_url: String,
+    // Metadata of movie accounts is re-fetched after this number of days
+    #[serde(default = "default_tmdb_refresh_days")]
+    pub tmdb_refresh_days: u32,
 
     #[serde(skip)]
     pub(super) instance_rsa_key: Option<RsaPrivateKey>,
@@ -88,6 +100,9 @@ pub struct Config {
     #[serde(default)]
     pub blocked_instances: Vec<String>,
 
+    #[serde(default)]
+    pub boost_policy: BoostPolicyConfig,
+
     // IPFS
     pub ipfs_api_url: Option<String>,
     pub ipfs_gateway_url: Option<String>,
@@ -1,3 +1,4 @@
+mod boost_policy;
 mod config;
 mod environment;
 mod federation;
@@ -6,6 +7,7 @@ mod loader;
 mod registration;
 mod retention;
 
+pub use boost_policy::BoostPolicyConfig;
 pub use config::{Config, Instance};
 pub use environment::Environment;
 pub use loader::parse_config;
@@ -0,0 +1 @@
+ALTER TABLE post ADD COLUMN rating REAL CHECK (rating >= 0.5 AND rating <= 5);
@@ -0,0 +1,16 @@
+ALTER TABLE actor_profile ADD COLUMN rating_stats JSONB NOT NULL DEFAULT '{}';
+
+UPDATE actor_profile
+SET rating_stats = jsonb_build_object('review_count', reviews.count)
+FROM (
+    SELECT mention.profile_id, count(post)
+    FROM post
+    JOIN mention ON mention.post_id = post.id
+    WHERE
+        post.author_id != mention.profile_id
+        AND post.in_reply_to_id IS NULL
+        AND post.repost_of_id IS NULL
+        AND post.visibility = 1
+    GROUP BY mention.profile_id
+) AS reviews
+WHERE actor_profile.id = reviews.profile_id AND actor_profile.actor_json IS NULL;
@@ -0,0 +1,25 @@
+ALTER TABLE actor_profile ADD COLUMN account_kind SMALLINT NOT NULL DEFAULT 0;
+ALTER TABLE actor_profile ADD COLUMN tmdb_id INTEGER;
+ALTER TABLE actor_profile ADD CHECK ((account_kind = 0) = (tmdb_id IS NULL));
+
+-- Existing movie accounts are recognized by their TMDB profile link
+UPDATE actor_profile
+SET account_kind = 1, tmdb_id = movie.tmdb_id
+FROM (
+    SELECT DISTINCT ON (actor_profile.id)
+        actor_profile.id AS profile_id,
+        substring(field ->> 'value_source' FROM '^https://www\.themoviedb\.org/movie/(\d+)$')::INTEGER AS tmdb_id
+    FROM actor_profile
+    CROSS JOIN jsonb_array_elements(actor_profile.extra_fields) AS field
+    WHERE
+        actor_profile.actor_json IS NULL
+        AND field ->> 'name' = 'TMDB Profile'
+        AND field ->> 'value_source' ~ '^https://www\.themoviedb\.org/movie/\d+$'
+) AS movie
+WHERE actor_profile.id = movie.profile_id;
+
+-- Movie accounts can't log in and have guest role
+UPDATE user_account
+SET password_hash = NULL, user_role = 0
+FROM actor_profile
+WHERE user_account.id = actor_profile.id AND actor_profile.account_kind = 1;
@@ -0,0 +1,12 @@
+CREATE TABLE movie_catalog (
+    tmdb_id INTEGER PRIMARY KEY,
+    original_title VARCHAR(1000) NOT NULL,
+    adult BOOLEAN NOT NULL,
+    popularity REAL NOT NULL,
+    movie_username VARCHAR(200),
+    metadata JSONB,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((movie_username IS NULL) = (metadata IS NULL))
+);
+
+CREATE INDEX movie_catalog_movie_username_idx ON movie_catalog (movie_username);
@@ -0,0 +1,11 @@
+CREATE TABLE watch_log_entry (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES user_account (id) ON DELETE CASCADE,
+    movie_id UUID NOT NULL REFERENCES actor_profile (id) ON DELETE CASCADE,
+    watched_on DATE NOT NULL,
+    is_rewatch BOOLEAN NOT NULL,
+    rating REAL CHECK (rating >= 0.5 AND rating <= 5),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX watch_log_entry_movie_id_idx ON watch_log_entry (movie_id);
@@ -0,0 +1,9 @@
+CREATE TABLE watchlist_entry (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES user_account (id) ON DELETE CASCADE,
+    movie_id UUID NOT NULL REFERENCES actor_profile (id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (owner_id, movie_id)
+);
+
+ALTER TABLE user_account ADD COLUMN show_watchlist BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,17 @@
+CREATE TABLE movie_list (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES user_account (id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE movie_list_item (
+    list_id UUID NOT NULL REFERENCES movie_list (id) ON DELETE CASCADE,
+    movie_id UUID NOT NULL REFERENCES actor_profile (id) ON DELETE CASCADE,
+    position SMALLINT NOT NULL,
+    note TEXT,
+    PRIMARY KEY (list_id, movie_id),
+    UNIQUE (list_id, position)
+);
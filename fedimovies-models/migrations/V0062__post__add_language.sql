@@ -0,0 +1,2 @@
+ALTER TABLE post ADD COLUMN language VARCHAR(10);
+CREATE INDEX mention_profile_id_idx ON mention (profile_id);
@@ -0,0 +1,13 @@
+ALTER TABLE post ADD COLUMN content_source TEXT;
+
+CREATE TABLE post_edit (
+    id UUID PRIMARY KEY,
+    post_id UUID NOT NULL REFERENCES post (id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    content_source TEXT,
+    summary TEXT,
+    is_sensitive BOOLEAN NOT NULL,
+    rating REAL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX post_edit_post_id_idx ON post_edit (post_id);
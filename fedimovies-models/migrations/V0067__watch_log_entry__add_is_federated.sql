@@ -0,0 +1 @@
+ALTER TABLE watch_log_entry ADD COLUMN is_federated BOOLEAN NOT NULL DEFAULT FALSE;
@@ -41,7 +41,11 @@ CREATE TABLE actor_profile (
     created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
     updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
     unreachable_since TIMESTAMP WITH TIME ZONE,
-    CHECK ((hostname IS NULL) = (actor_json IS NULL))
+    rating_stats JSONB NOT NULL DEFAULT '{}',
+    account_kind SMALLINT NOT NULL DEFAULT 0,
+    tmdb_id INTEGER,
+    CHECK ((hostname IS NULL) = (actor_json IS NULL)),
+    CHECK ((account_kind = 0) = (tmdb_id IS NULL))
 );
 
 CREATE TABLE user_invite_code (
@@ -59,7 +63,8 @@ CREATE TABLE user_account (
     invite_code VARCHAR(100) UNIQUE REFERENCES user_invite_code (code) ON DELETE SET NULL,
     user_role SMALLINT NOT NULL,
     client_config JSONB NOT NULL DEFAULT '{}',
-    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    show_watchlist BOOLEAN NOT NULL DEFAULT FALSE
 );
 
 CREATE TABLE oauth_application (
@@ -127,9 +132,26 @@ CREATE TABLE post (
     token_tx_id VARCHAR(200),
     created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
     updated_at TIMESTAMP WITH TIME ZONE,
+    rating REAL CHECK (rating >= 0.5 AND rating <= 5),
+    language VARCHAR(10),
+    summary TEXT,
+    content_source TEXT,
     UNIQUE (author_id, repost_of_id)
 );
 
+CREATE TABLE post_edit (
+    id UUID PRIMARY KEY,
+    post_id UUID NOT NULL REFERENCES post (id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    content_source TEXT,
+    summary TEXT,
+    is_sensitive BOOLEAN NOT NULL,
+    rating REAL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX post_edit_post_id_idx ON post_edit (post_id);
+
 CREATE TABLE post_reaction (
     id UUID PRIMARY KEY,
     author_id UUID NOT NULL REFERENCES actor_profile (id) ON DELETE CASCADE,
@@ -139,6 +161,14 @@ CREATE TABLE post_reaction (
     UNIQUE (author_id, post_id)
 );
 
+CREATE TABLE post_bookmark (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES user_account (id) ON DELETE CASCADE,
+    post_id UUID NOT NULL REFERENCES post (id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (owner_id, post_id)
+);
+
 CREATE TABLE media_attachment (
     id UUID PRIMARY KEY,
     owner_id UUID NOT NULL REFERENCES actor_profile (id) ON DELETE CASCADE,
@@ -156,6 +186,8 @@ CREATE TABLE mention (
     PRIMARY KEY (post_id, profile_id)
 );
 
+CREATE INDEX mention_profile_id_idx ON mention (profile_id);
+
 CREATE TABLE tag (
     id SERIAL PRIMARY KEY,
     tag_name VARCHAR(100) UNIQUE NOT NULL
@@ -239,3 +271,68 @@ CREATE TABLE subscription (
     UNIQUE (sender_id, recipient_id),
     CHECK (sender_id != recipient_id)
 );
+
+CREATE TABLE movie_catalog (
+    tmdb_id INTEGER PRIMARY KEY,
+    original_title VARCHAR(1000) NOT NULL,
+    adult BOOLEAN NOT NULL,
+    popularity REAL NOT NULL,
+    movie_username VARCHAR(200),
+    metadata JSONB,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((movie_username IS NULL) = (metadata IS NULL))
+);
+
+CREATE INDEX movie_catalog_movie_username_idx ON movie_catalog (movie_username);
+
+CREATE TABLE watch_log_entry (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES user_account (id) ON DELETE CASCADE,
+    movie_id UUID NOT NULL REFERENCES actor_profile (id) ON DELETE CASCADE,
+    watched_on DATE NOT NULL,
+    is_rewatch BOOLEAN NOT NULL,
+    rating REAL CHECK (rating >= 0.5 AND rating <= 5),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    is_federated BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX watch_log_entry_movie_id_idx ON watch_log_entry (movie_id);
+
+CREATE TABLE watchlist_entry (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES user_account (id) ON DELETE CASCADE,
+    movie_id UUID NOT NULL REFERENCES actor_profile (id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (owner_id, movie_id)
+);
+
+CREATE TABLE list (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES user_account (id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE list_member (
+    list_id UUID NOT NULL REFERENCES list (id) ON DELETE CASCADE,
+    profile_id UUID NOT NULL REFERENCES actor_profile (id) ON DELETE CASCADE,
+    PRIMARY KEY (list_id, profile_id)
+);
+
+CREATE TABLE movie_list (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES user_account (id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE movie_list_item (
+    list_id UUID NOT NULL REFERENCES movie_list (id) ON DELETE CASCADE,
+    movie_id UUID NOT NULL REFERENCES actor_profile (id) ON DELETE CASCADE,
+    position SMALLINT NOT NULL,
+    note TEXT,
+    PRIMARY KEY (list_id, movie_id),
+    UNIQUE (list_id, position)
+);
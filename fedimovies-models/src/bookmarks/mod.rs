@@ -0,0 +1,2 @@
+pub mod queries;
+mod types;
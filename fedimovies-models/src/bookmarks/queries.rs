@@ -0,0 +1,234 @@
+use uuid::Uuid;
+
+use fedimovies_utils::id::generate_ulid;
+
+use crate::database::{catch_unique_violation, query_macro::query, DatabaseClient, DatabaseError};
+use crate::posts::queries::{
+    build_block_filter, build_visibility_filter, RELATED_ATTACHMENTS, RELATED_EMOJIS,
+    RELATED_LINKS, RELATED_MENTIONS, RELATED_TAGS,
+};
+
+use super::types::{Bookmark, DbBookmark};
+
+pub async fn create_bookmark(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    post_id: &Uuid,
+) -> Result<DbBookmark, DatabaseError> {
+    let bookmark_id = generate_ulid();
+    // Bookmarks of reposts are not allowed
+    let maybe_row = db_client
+        .query_opt(
+            "
+        INSERT INTO post_bookmark (id, owner_id, post_id)
+        SELECT $1, $2, $3
+        WHERE NOT EXISTS (
+            SELECT 1 FROM post
+            WHERE post.id = $3 AND post.repost_of_id IS NOT NULL
+        )
+        RETURNING post_bookmark
+        ",
+            &[&bookmark_id, &owner_id, &post_id],
+        )
+        .await
+        .map_err(catch_unique_violation("bookmark"))?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("post"))?;
+    let bookmark = row.try_get("post_bookmark")?;
+    Ok(bookmark)
+}
+
+pub async fn delete_bookmark(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    post_id: &Uuid,
+) -> Result<(), DatabaseError> {
+    let deleted_count = db_client
+        .execute(
+            "
+        DELETE FROM post_bookmark
+        WHERE owner_id = $1 AND post_id = $2
+        ",
+            &[&owner_id, &post_id],
+        )
+        .await?;
+    if deleted_count == 0 {
+        return Err(DatabaseError::NotFound("bookmark"));
+    };
+    Ok(())
+}
+
+/// Finds bookmarks among given posts and returns their IDs
+pub async fn find_bookmarked_by_user(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    posts_ids: &[Uuid],
+) -> Result<Vec<Uuid>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT post_id
+        FROM post_bookmark
+        WHERE owner_id = $1 AND post_id = ANY($2)
+        ",
+            &[&owner_id, &posts_ids],
+        )
+        .await?;
+    let bookmarks: Vec<Uuid> = rows
+        .iter()
+        .map(|row| row.try_get("post_id"))
+        .collect::<Result<_, _>>()?;
+    Ok(bookmarks)
+}
+
+/// Returns bookmarks of visible posts, most recently bookmarked first
+pub async fn get_bookmarks(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    max_bookmark_id: Option<Uuid>,
+    limit: u16,
+) -> Result<Vec<Bookmark>, DatabaseError> {
+    let statement = format!(
+        "
+        SELECT
+            post_bookmark, post, actor_profile,
+            {related_attachments},
+            {related_mentions},
+            {related_tags},
+            {related_links},
+            {related_emojis}
+        FROM post_bookmark
+        JOIN post ON post_bookmark.post_id = post.id
+        JOIN actor_profile ON post.author_id = actor_profile.id
+        WHERE
+            post_bookmark.owner_id = $current_user_id
+            AND {block_filter}
+            AND {visibility_filter}
+            AND ($max_bookmark_id::uuid IS NULL OR post_bookmark.id < $max_bookmark_id)
+        ORDER BY post_bookmark.id DESC
+        LIMIT $limit
+        ",
+        related_attachments = RELATED_ATTACHMENTS,
+        related_mentions = RELATED_MENTIONS,
+        related_tags = RELATED_TAGS,
+        related_links = RELATED_LINKS,
+        related_emojis = RELATED_EMOJIS,
+        block_filter = build_block_filter(),
+        visibility_filter = build_visibility_filter(),
+    );
+    let limit: i64 = limit.into();
+    let query = query!(
+        &statement,
+        current_user_id = owner_id,
+        max_bookmark_id = max_bookmark_id,
+        limit = limit,
+    )?;
+    let rows = db_client.query(query.sql(), query.parameters()).await?;
+    let bookmarks = rows
+        .iter()
+        .map(Bookmark::try_from)
+        .collect::<Result<_, _>>()?;
+    Ok(bookmarks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::create_test_database;
+    use crate::posts::{queries::create_post, types::PostCreateData};
+    use crate::relationships::queries::block;
+    use crate::users::{queries::create_user, types::UserCreateData};
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_and_delete_bookmark() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let post_data_1 = PostCreateData {
+            content: "review 1".to_string(),
+            ..Default::default()
+        };
+        let post_1 = create_post(db_client, &user.id, post_data_1).await.unwrap();
+        let post_data_2 = PostCreateData {
+            content: "review 2".to_string(),
+            ..Default::default()
+        };
+        let post_2 = create_post(db_client, &user.id, post_data_2).await.unwrap();
+
+        let bookmark = create_bookmark(db_client, &user.id, &post_2.id)
+            .await
+            .unwrap();
+        assert_eq!(bookmark.owner_id, user.id);
+        assert_eq!(bookmark.post_id, post_2.id);
+        create_bookmark(db_client, &user.id, &post_1.id)
+            .await
+            .unwrap();
+        let error = create_bookmark(db_client, &user.id, &post_1.id)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, DatabaseError::AlreadyExists("bookmark")));
+
+        let bookmarked = find_bookmarked_by_user(db_client, &user.id, &[post_1.id, post_2.id])
+            .await
+            .unwrap();
+        assert_eq!(bookmarked.len(), 2);
+        // Most recently bookmarked first
+        let bookmarks = get_bookmarks(db_client, &user.id, None, 10).await.unwrap();
+        assert_eq!(bookmarks.len(), 2);
+        assert_eq!(bookmarks[0].post.id, post_1.id);
+        assert_eq!(bookmarks[1].post.id, post_2.id);
+        assert_eq!(bookmarks[1].id, bookmark.id);
+        let bookmarks = get_bookmarks(db_client, &user.id, Some(bookmarks[0].id), 10)
+            .await
+            .unwrap();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].post.id, post_2.id);
+
+        delete_bookmark(db_client, &user.id, &post_1.id)
+            .await
+            .unwrap();
+        let error = delete_bookmark(db_client, &user.id, &post_1.id)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, DatabaseError::NotFound("bookmark")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_bookmarks_blocked_author() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let author_data = UserCreateData {
+            username: "author".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let author = create_user(db_client, author_data).await.unwrap();
+        let post_data = PostCreateData {
+            content: "review".to_string(),
+            ..Default::default()
+        };
+        let post = create_post(db_client, &author.id, post_data).await.unwrap();
+        create_bookmark(db_client, &user.id, &post.id)
+            .await
+            .unwrap();
+        let bookmarks = get_bookmarks(db_client, &user.id, None, 10).await.unwrap();
+        assert_eq!(bookmarks.len(), 1);
+
+        block(db_client, &user.id, &author.id).await.unwrap();
+        let bookmarks = get_bookmarks(db_client, &user.id, None, 10).await.unwrap();
+        assert_eq!(bookmarks.len(), 0);
+    }
+}
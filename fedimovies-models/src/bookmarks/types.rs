@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use postgres_types::FromSql;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use crate::database::DatabaseError;
+use crate::posts::types::Post;
+
+#[derive(FromSql)]
+#[postgres(name = "post_bookmark")]
+pub struct DbBookmark {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub post_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct Bookmark {
+    pub id: Uuid,
+    pub post: Post,
+}
+
+impl TryFrom<&Row> for Bookmark {
+    type Error = DatabaseError;
+
+    fn try_from(row: &Row) -> Result<Self, Self::Error> {
+        let db_bookmark: DbBookmark = row.try_get("post_bookmark")?;
+        let post = Post::try_from(row)?;
+        let bookmark = Self {
+            id: db_bookmark.id,
+            post,
+        };
+        Ok(bookmark)
+    }
+}
@@ -1,11 +1,15 @@
 pub mod attachments;
 pub mod background_jobs;
+pub mod bookmarks;
 pub mod cleanup;
 pub mod database;
 pub mod emojis;
 pub mod instances;
 pub mod invoices;
+pub mod lists;
 pub mod markers;
+pub mod movie_catalog;
+pub mod movie_lists;
 pub mod notifications;
 pub mod oauth;
 pub mod posts;
@@ -15,4 +19,7 @@ pub mod reactions;
 pub mod relationships;
 pub mod subscriptions;
 pub mod tags;
+pub mod trends;
 pub mod users;
+pub mod watch_log;
+pub mod watchlist;
@@ -0,0 +1,254 @@
+use uuid::Uuid;
+
+use fedimovies_utils::id::generate_ulid;
+
+use crate::database::{DatabaseClient, DatabaseError};
+use crate::profiles::types::DbActorProfile;
+
+use super::types::DbList;
+
+pub async fn create_list(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    title: &str,
+) -> Result<DbList, DatabaseError> {
+    let list_id = generate_ulid();
+    let row = db_client
+        .query_one(
+            "
+        INSERT INTO list (id, owner_id, title)
+        VALUES ($1, $2, $3)
+        RETURNING list
+        ",
+            &[&list_id, &owner_id, &title],
+        )
+        .await?;
+    let list = row.try_get("list")?;
+    Ok(list)
+}
+
+pub async fn update_list(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+    owner_id: &Uuid,
+    title: &str,
+) -> Result<DbList, DatabaseError> {
+    let maybe_row = db_client
+        .query_opt(
+            "
+        UPDATE list SET title = $3
+        WHERE id = $1 AND owner_id = $2
+        RETURNING list
+        ",
+            &[&list_id, &owner_id, &title],
+        )
+        .await?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("list"))?;
+    let list = row.try_get("list")?;
+    Ok(list)
+}
+
+pub async fn get_list(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+    owner_id: &Uuid,
+) -> Result<DbList, DatabaseError> {
+    let maybe_row = db_client
+        .query_opt(
+            "
+        SELECT list
+        FROM list
+        WHERE id = $1 AND owner_id = $2
+        ",
+            &[&list_id, &owner_id],
+        )
+        .await?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("list"))?;
+    let list = row.try_get("list")?;
+    Ok(list)
+}
+
+/// Returns lists created by the user, oldest first
+pub async fn get_lists(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+) -> Result<Vec<DbList>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT list
+        FROM list
+        WHERE owner_id = $1
+        ORDER BY list.created_at, list.id
+        ",
+            &[&owner_id],
+        )
+        .await?;
+    let lists = rows
+        .iter()
+        .map(|row| row.try_get("list"))
+        .collect::<Result<_, _>>()?;
+    Ok(lists)
+}
+
+pub async fn delete_list(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+    owner_id: &Uuid,
+) -> Result<(), DatabaseError> {
+    let deleted_count = db_client
+        .execute(
+            "
+        DELETE FROM list
+        WHERE id = $1 AND owner_id = $2
+        ",
+            &[&list_id, &owner_id],
+        )
+        .await?;
+    if deleted_count == 0 {
+        return Err(DatabaseError::NotFound("list"));
+    };
+    Ok(())
+}
+
+pub async fn add_list_members(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+    profile_ids: &[Uuid],
+) -> Result<(), DatabaseError> {
+    db_client
+        .execute(
+            "
+        INSERT INTO list_member (list_id, profile_id)
+        SELECT $1, profile_id FROM unnest($2::uuid[]) AS profile_id
+        ON CONFLICT (list_id, profile_id) DO NOTHING
+        ",
+            &[&list_id, &profile_ids],
+        )
+        .await?;
+    Ok(())
+}
+
+pub async fn remove_list_members(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+    profile_ids: &[Uuid],
+) -> Result<(), DatabaseError> {
+    // Does not return NotFound error
+    db_client
+        .execute(
+            "
+        DELETE FROM list_member
+        WHERE list_id = $1 AND profile_id = ANY($2)
+        ",
+            &[&list_id, &profile_ids],
+        )
+        .await?;
+    Ok(())
+}
+
+/// Removes profile from all lists owned by the user
+pub async fn remove_from_owner_lists(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    profile_id: &Uuid,
+) -> Result<(), DatabaseError> {
+    db_client
+        .execute(
+            "
+        DELETE FROM list_member
+        USING list
+        WHERE
+            list_member.list_id = list.id
+            AND list.owner_id = $1
+            AND list_member.profile_id = $2
+        ",
+            &[&owner_id, &profile_id],
+        )
+        .await?;
+    Ok(())
+}
+
+pub async fn get_list_members(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+) -> Result<Vec<DbActorProfile>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT actor_profile
+        FROM list_member
+        JOIN actor_profile ON list_member.profile_id = actor_profile.id
+        WHERE list_member.list_id = $1
+        ORDER BY actor_profile.username
+        ",
+            &[&list_id],
+        )
+        .await?;
+    let profiles = rows
+        .iter()
+        .map(|row| row.try_get("actor_profile"))
+        .collect::<Result<_, _>>()?;
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::create_test_database;
+    use crate::users::{queries::create_user, types::UserCreateData};
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_update_and_delete_list() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let critic_data = UserCreateData {
+            username: "critic".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let critic = create_user(db_client, critic_data).await.unwrap();
+
+        let list = create_list(db_client, &user.id, "Critics").await.unwrap();
+        assert_eq!(list.owner_id, user.id);
+        assert_eq!(list.title, "Critics");
+        let list = update_list(db_client, &list.id, &user.id, "Film critics")
+            .await
+            .unwrap();
+        assert_eq!(list.title, "Film critics");
+        let error = update_list(db_client, &list.id, &critic.id, "Test")
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, DatabaseError::NotFound("list")));
+
+        add_list_members(db_client, &list.id, &[critic.id])
+            .await
+            .unwrap();
+        // Repeat
+        add_list_members(db_client, &list.id, &[critic.id])
+            .await
+            .unwrap();
+        let members = get_list_members(db_client, &list.id).await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].id, critic.id);
+        remove_list_members(db_client, &list.id, &[critic.id])
+            .await
+            .unwrap();
+        let members = get_list_members(db_client, &list.id).await.unwrap();
+        assert!(members.is_empty());
+
+        let lists = get_lists(db_client, &user.id).await.unwrap();
+        assert_eq!(lists.len(), 1);
+        delete_list(db_client, &list.id, &user.id).await.unwrap();
+        let error = get_list(db_client, &list.id, &user.id).await.err().unwrap();
+        assert!(matches!(error, DatabaseError::NotFound("list")));
+    }
+}
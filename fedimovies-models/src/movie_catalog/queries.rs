@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+
+use serde_json::Value as JsonValue;
+
+use crate::database::{DatabaseClient, DatabaseError};
+
+use super::types::{DbMovieCatalogEntry, MovieCatalogData};
+
+/// Adds entries to movie catalog or updates existing ones.
+/// Previously loaded metadata is kept if the entry doesn't have it.
+pub async fn upsert_catalog_entries(
+    db_client: &impl DatabaseClient,
+    entries: Vec<MovieCatalogData>,
+) -> Result<u64, DatabaseError> {
+    let mut tmdb_ids = vec![];
+    let mut original_titles = vec![];
+    let mut adult_flags = vec![];
+    let mut popularities = vec![];
+    let mut movie_usernames = vec![];
+    let mut metadata_values: Vec<Option<JsonValue>> = vec![];
+    // Row can't be updated twice by the same statement,
+    // so only the last entry with a given TMDB ID is kept
+    let mut seen_ids = HashSet::new();
+    let mut entries: Vec<MovieCatalogData> = entries
+        .into_iter()
+        .rev()
+        .filter(|entry| seen_ids.insert(entry.tmdb_id))
+        .collect();
+    entries.reverse();
+    for entry in entries {
+        tmdb_ids.push(entry.tmdb_id);
+        original_titles.push(entry.original_title);
+        adult_flags.push(entry.adult);
+        popularities.push(entry.popularity);
+        movie_usernames.push(entry.movie_username);
+        metadata_values.push(entry.metadata);
+    }
+    let updated_count = db_client
+        .execute(
+            "
+        INSERT INTO movie_catalog (
+            tmdb_id,
+            original_title,
+            adult,
+            popularity,
+            movie_username,
+            metadata
+        )
+        SELECT * FROM unnest(
+            $1::integer[],
+            $2::text[],
+            $3::boolean[],
+            $4::real[],
+            $5::text[],
+            $6::jsonb[]
+        )
+        ON CONFLICT (tmdb_id) DO UPDATE
+        SET
+            original_title = EXCLUDED.original_title,
+            adult = EXCLUDED.adult,
+            popularity = EXCLUDED.popularity,
+            movie_username = COALESCE(EXCLUDED.movie_username, movie_catalog.movie_username),
+            metadata = COALESCE(EXCLUDED.metadata, movie_catalog.metadata),
+            updated_at = CURRENT_TIMESTAMP
+        ",
+            &[
+                &tmdb_ids,
+                &original_titles,
+                &adult_flags,
+                &popularities,
+                &movie_usernames,
+                &metadata_values,
+            ],
+        )
+        .await?;
+    Ok(updated_count)
+}
+
+/// Returns catalog entries with metadata that match the movie username
+/// (short form, without TMDB ID)
+pub async fn get_catalog_entries_by_username(
+    db_client: &impl DatabaseClient,
+    movie_username: &str,
+) -> Result<Vec<DbMovieCatalogEntry>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT movie_catalog
+        FROM movie_catalog
+        WHERE movie_username = $1 AND metadata IS NOT NULL
+        ORDER BY tmdb_id
+        ",
+            &[&movie_username],
+        )
+        .await?;
+    let entries = rows
+        .iter()
+        .map(|row| row.try_get("movie_catalog"))
+        .collect::<Result<_, _>>()?;
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::create_test_database;
+    use serde_json::json;
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_upsert_catalog_entries() {
+        let db_client = &create_test_database().await;
+        let entry = MovieCatalogData {
+            tmdb_id: 19995,
+            original_title: "Avatar".to_string(),
+            adult: false,
+            popularity: 102.3,
+            movie_username: Some("Avatar_2009".to_string()),
+            metadata: Some(json!({"id": 19995, "title": "Avatar"})),
+        };
+        let count = upsert_catalog_entries(db_client, vec![entry])
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        // Entry from ID export doesn't have metadata
+        let entry = MovieCatalogData {
+            tmdb_id: 19995,
+            original_title: "Avatar".to_string(),
+            adult: false,
+            popularity: 110.0,
+            movie_username: None,
+            metadata: None,
+        };
+        upsert_catalog_entries(db_client, vec![entry])
+            .await
+            .unwrap();
+
+        let entries = get_catalog_entries_by_username(db_client, "Avatar_2009")
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tmdb_id, 19995);
+        assert_eq!(entries[0].popularity, 110.0);
+        assert!(entries[0].metadata.is_some());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_upsert_catalog_entries_duplicate_ids() {
+        let db_client = &create_test_database().await;
+        let entries = [90.0, 102.3]
+            .into_iter()
+            .map(|popularity| MovieCatalogData {
+                tmdb_id: 19995,
+                original_title: "Avatar".to_string(),
+                adult: false,
+                popularity,
+                movie_username: Some("Avatar_2009".to_string()),
+                metadata: Some(json!({"id": 19995, "title": "Avatar"})),
+            })
+            .collect();
+        let count = upsert_catalog_entries(db_client, entries).await.unwrap();
+        assert_eq!(count, 1);
+
+        let entries = get_catalog_entries_by_username(db_client, "Avatar_2009")
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].popularity, 102.3);
+    }
+}
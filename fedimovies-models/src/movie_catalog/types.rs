@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use postgres_types::FromSql;
+use serde_json::Value as JsonValue;
+
+#[derive(FromSql)]
+#[postgres(name = "movie_catalog")]
+pub struct DbMovieCatalogEntry {
+    pub tmdb_id: i32,
+    pub original_title: String,
+    pub adult: bool,
+    pub popularity: f32,
+    pub movie_username: Option<String>,
+    pub metadata: Option<JsonValue>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Catalog entry from TMDB export.
+/// Username and metadata are only known if movie details were loaded.
+pub struct MovieCatalogData {
+    pub tmdb_id: i32,
+    pub original_title: String,
+    pub adult: bool,
+    pub popularity: f32,
+    pub movie_username: Option<String>,
+    pub metadata: Option<JsonValue>,
+}
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use fedimovies_utils::id::generate_ulid;
+
+use crate::database::{catch_unique_violation, DatabaseClient, DatabaseError};
+use crate::profiles::types::{AccountKind, DbActorProfile};
+
+use super::types::{MovieList, MovieListData, MovieListItem, MovieListItemData};
+
+async fn insert_movie_list_items(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+    items: Vec<MovieListItemData>,
+) -> Result<(), DatabaseError> {
+    let items_count = items.len();
+    let mut movie_ids = vec![];
+    let mut notes = vec![];
+    for item in items {
+        movie_ids.push(item.movie_id);
+        notes.push(item.note);
+    }
+    // Only movies and TV shows can be added to list
+    let inserted_count = db_client
+        .execute(
+            "
+        INSERT INTO movie_list_item (list_id, movie_id, position, note)
+        SELECT $1, item.movie_id, item.position, item.note
+        FROM unnest($2::uuid[], $3::text[])
+            WITH ORDINALITY AS item (movie_id, note, position)
+        JOIN actor_profile ON actor_profile.id = item.movie_id
+        WHERE actor_profile.account_kind NOT IN ($4, $5)
+        ",
+            &[
+                &list_id,
+                &movie_ids,
+                &notes,
+                &AccountKind::User,
+                &AccountKind::MoviePerson,
+            ],
+        )
+        .await
+        .map_err(catch_unique_violation("movie list item"))?;
+    if inserted_count != items_count as u64 {
+        return Err(DatabaseError::NotFound("movie"));
+    };
+    Ok(())
+}
+
+async fn add_movie_list_items(
+    db_client: &impl DatabaseClient,
+    lists: &mut [MovieList],
+) -> Result<(), DatabaseError> {
+    let list_ids: Vec<Uuid> = lists.iter().map(|list| list.id).collect();
+    let rows = db_client
+        .query(
+            "
+        SELECT movie_list_item.list_id, movie_list_item.note, actor_profile
+        FROM movie_list_item
+        JOIN actor_profile ON movie_list_item.movie_id = actor_profile.id
+        WHERE movie_list_item.list_id = ANY($1)
+        ORDER BY movie_list_item.position
+        ",
+            &[&list_ids],
+        )
+        .await?;
+    let mut items_map: HashMap<Uuid, Vec<MovieListItem>> = HashMap::new();
+    for row in rows {
+        let list_id: Uuid = row.try_get("list_id")?;
+        let item = MovieListItem {
+            movie: row.try_get::<_, DbActorProfile>("actor_profile")?,
+            note: row.try_get("note")?,
+        };
+        items_map.entry(list_id).or_default().push(item);
+    }
+    for list in lists {
+        list.items = items_map.remove(&list.id).unwrap_or_default();
+    }
+    Ok(())
+}
+
+pub async fn create_movie_list(
+    db_client: &mut impl DatabaseClient,
+    owner_id: &Uuid,
+    list_data: MovieListData,
+) -> Result<MovieList, DatabaseError> {
+    let transaction = db_client.transaction().await?;
+    let list_id = generate_ulid();
+    transaction
+        .execute(
+            "
+        INSERT INTO movie_list (id, owner_id, title, description)
+        VALUES ($1, $2, $3, $4)
+        ",
+            &[
+                &list_id,
+                &owner_id,
+                &list_data.title,
+                &list_data.description,
+            ],
+        )
+        .await?;
+    insert_movie_list_items(&transaction, &list_id, list_data.items).await?;
+    let list = get_movie_list(&transaction, &list_id).await?;
+    transaction.commit().await?;
+    Ok(list)
+}
+
+/// Replaces title, description and items of the list
+pub async fn update_movie_list(
+    db_client: &mut impl DatabaseClient,
+    list_id: &Uuid,
+    owner_id: &Uuid,
+    list_data: MovieListData,
+) -> Result<MovieList, DatabaseError> {
+    let transaction = db_client.transaction().await?;
+    let updated_count = transaction
+        .execute(
+            "
+        UPDATE movie_list
+        SET
+            title = $3,
+            description = $4,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND owner_id = $2
+        ",
+            &[
+                &list_id,
+                &owner_id,
+                &list_data.title,
+                &list_data.description,
+            ],
+        )
+        .await?;
+    if updated_count == 0 {
+        return Err(DatabaseError::NotFound("movie list"));
+    };
+    transaction
+        .execute(
+            "DELETE FROM movie_list_item WHERE list_id = $1",
+            &[&list_id],
+        )
+        .await?;
+    insert_movie_list_items(&transaction, list_id, list_data.items).await?;
+    let list = get_movie_list(&transaction, list_id).await?;
+    transaction.commit().await?;
+    Ok(list)
+}
+
+pub async fn get_movie_list(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+) -> Result<MovieList, DatabaseError> {
+    let maybe_row = db_client
+        .query_opt(
+            "
+        SELECT movie_list, actor_profile
+        FROM movie_list
+        JOIN actor_profile ON movie_list.owner_id = actor_profile.id
+        WHERE movie_list.id = $1
+        ",
+            &[&list_id],
+        )
+        .await?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("movie list"))?;
+    let mut lists = vec![MovieList::try_from(&row)?];
+    add_movie_list_items(db_client, &mut lists).await?;
+    Ok(lists.remove(0))
+}
+
+/// Returns lists created by the user, latest first
+pub async fn get_movie_lists_by_owner(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+) -> Result<Vec<MovieList>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT movie_list, actor_profile
+        FROM movie_list
+        JOIN actor_profile ON movie_list.owner_id = actor_profile.id
+        WHERE movie_list.owner_id = $1
+        ORDER BY movie_list.id DESC
+        ",
+            &[&owner_id],
+        )
+        .await?;
+    let mut lists: Vec<MovieList> = rows
+        .iter()
+        .map(MovieList::try_from)
+        .collect::<Result<_, _>>()?;
+    add_movie_list_items(db_client, &mut lists).await?;
+    Ok(lists)
+}
+
+pub async fn delete_movie_list(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+    owner_id: &Uuid,
+) -> Result<(), DatabaseError> {
+    let deleted_count = db_client
+        .execute(
+            "
+        DELETE FROM movie_list
+        WHERE id = $1 AND owner_id = $2
+        ",
+            &[&list_id, &owner_id],
+        )
+        .await?;
+    if deleted_count == 0 {
+        return Err(DatabaseError::NotFound("movie list"));
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::create_test_database;
+    use crate::users::{queries::create_user, types::UserCreateData};
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_update_and_delete_movie_list() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let movie_data_1 = UserCreateData {
+            username: "Ran_1985_11645".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(11645),
+            ..Default::default()
+        };
+        let movie_1 = create_user(db_client, movie_data_1).await.unwrap();
+        let movie_data_2 = UserCreateData {
+            username: "Ikiru_1952_3782".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(3782),
+            ..Default::default()
+        };
+        let movie_2 = create_user(db_client, movie_data_2).await.unwrap();
+
+        let list_data = MovieListData {
+            title: "Kurosawa ranked".to_string(),
+            description: None,
+            items: vec![
+                MovieListItemData {
+                    movie_id: movie_1.id,
+                    note: Some("King Lear".to_string()),
+                },
+                MovieListItemData {
+                    movie_id: movie_2.id,
+                    note: None,
+                },
+            ],
+        };
+        let list = create_movie_list(db_client, &user.id, list_data)
+            .await
+            .unwrap();
+        assert_eq!(list.owner.id, user.id);
+        assert_eq!(list.title, "Kurosawa ranked");
+        assert_eq!(list.items.len(), 2);
+        assert_eq!(list.items[0].movie.id, movie_1.id);
+        assert_eq!(list.items[0].note.as_deref(), Some("King Lear"));
+        assert_eq!(list.items[1].movie.id, movie_2.id);
+
+        let list_data = MovieListData {
+            title: "Kurosawa ranked".to_string(),
+            description: Some("Updated".to_string()),
+            items: vec![MovieListItemData {
+                movie_id: movie_2.id,
+                note: None,
+            }],
+        };
+        let list = update_movie_list(db_client, &list.id, &user.id, list_data)
+            .await
+            .unwrap();
+        assert_eq!(list.description.as_deref(), Some("Updated"));
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].movie.id, movie_2.id);
+        let lists = get_movie_lists_by_owner(db_client, &user.id).await.unwrap();
+        assert_eq!(lists.len(), 1);
+        assert_eq!(lists[0].items.len(), 1);
+
+        delete_movie_list(db_client, &list.id, &user.id)
+            .await
+            .unwrap();
+        let error = get_movie_list(db_client, &list.id).await.err().unwrap();
+        assert!(matches!(error, DatabaseError::NotFound("movie list")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_movie_list_not_movie() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let list_data = MovieListData {
+            title: "People".to_string(),
+            description: None,
+            items: vec![MovieListItemData {
+                movie_id: user.id,
+                note: None,
+            }],
+        };
+        let error = create_movie_list(db_client, &user.id, list_data)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, DatabaseError::NotFound("movie")));
+        let lists = get_movie_lists_by_owner(db_client, &user.id).await.unwrap();
+        assert!(lists.is_empty());
+    }
+}
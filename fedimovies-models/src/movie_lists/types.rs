@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use postgres_types::FromSql;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use crate::database::DatabaseError;
+use crate::profiles::types::DbActorProfile;
+
+#[derive(FromSql)]
+#[postgres(name = "movie_list")]
+pub struct DbMovieList {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub struct MovieListItemData {
+    pub movie_id: Uuid,
+    pub note: Option<String>,
+}
+
+pub struct MovieListData {
+    pub title: String,
+    pub description: Option<String>,
+    pub items: Vec<MovieListItemData>,
+}
+
+pub struct MovieListItem {
+    pub movie: DbActorProfile,
+    pub note: Option<String>,
+}
+
+pub struct MovieList {
+    pub id: Uuid,
+    pub owner: DbActorProfile,
+    pub title: String,
+    pub description: Option<String>,
+    pub items: Vec<MovieListItem>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<&Row> for MovieList {
+    type Error = DatabaseError;
+
+    fn try_from(row: &Row) -> Result<Self, Self::Error> {
+        let db_list: DbMovieList = row.try_get("movie_list")?;
+        let db_owner: DbActorProfile = row.try_get("actor_profile")?;
+        let list = Self {
+            id: db_list.id,
+            owner: db_owner,
+            title: db_list.title,
+            description: db_list.description,
+            // Items are loaded separately
+            items: vec![],
+            created_at: db_list.created_at,
+            updated_at: db_list.updated_at,
+        };
+        Ok(list)
+    }
+}
@@ -5,6 +5,8 @@ use crate::posts::{
     helpers::{add_related_posts, add_user_actions},
     queries::{RELATED_ATTACHMENTS, RELATED_EMOJIS, RELATED_LINKS, RELATED_MENTIONS, RELATED_TAGS},
 };
+use crate::profiles::types::AccountKind;
+use crate::relationships::types::RelationshipType;
 
 use super::types::{EventType, Notification};
 
@@ -48,6 +50,23 @@ pub async fn delete_notification(
     Ok(())
 }
 
+/// Deletes mention notifications created for the post
+pub async fn delete_mention_notifications(
+    db_client: &impl DatabaseClient,
+    post_id: &Uuid,
+) -> Result<(), DatabaseError> {
+    db_client
+        .execute(
+            "
+        DELETE FROM notification
+        WHERE post_id = $1 AND event_type = $2
+        ",
+            &[&post_id, &EventType::Mention],
+        )
+        .await?;
+    Ok(())
+}
+
 pub async fn create_follow_notification(
     db_client: &impl DatabaseClient,
     sender_id: &Uuid,
@@ -56,6 +75,21 @@ pub async fn create_follow_notification(
     create_notification(db_client, sender_id, recipient_id, None, EventType::Follow).await
 }
 
+pub async fn create_follow_request_notification(
+    db_client: &impl DatabaseClient,
+    sender_id: &Uuid,
+    recipient_id: &Uuid,
+) -> Result<(), DatabaseError> {
+    create_notification(
+        db_client,
+        sender_id,
+        recipient_id,
+        None,
+        EventType::FollowRequest,
+    )
+    .await
+}
+
 pub async fn create_reply_notification(
     db_client: &impl DatabaseClient,
     sender_id: &Uuid,
@@ -185,6 +219,14 @@ pub async fn get_notifications(
         WHERE
             recipient_id = $1
             AND ($2::integer IS NULL OR notification.id < $2)
+            -- sender is not blocked
+            AND NOT EXISTS (
+                SELECT 1 FROM relationship
+                WHERE
+                    source_id = $1
+                    AND target_id = notification.sender_id
+                    AND relationship_type = $4
+            )
         ORDER BY notification.id DESC
         LIMIT $3
         ",
@@ -195,7 +237,15 @@ pub async fn get_notifications(
         related_emojis = RELATED_EMOJIS,
     );
     let rows = db_client
-        .query(&statement, &[&recipient_id, &max_id, &i64::from(limit)])
+        .query(
+            &statement,
+            &[
+                &recipient_id,
+                &max_id,
+                &i64::from(limit),
+                &RelationshipType::Block,
+            ],
+        )
         .await?;
     let mut notifications: Vec<Notification> = rows
         .iter()
@@ -221,6 +271,7 @@ pub async fn get_notifications(
     Ok(notifications)
 }
 
+/// Returns mentions of movie and TV show accounts
 pub async fn get_mention_notifications(
     db_client: &impl DatabaseClient,
     limit: u16,
@@ -245,6 +296,7 @@ pub async fn get_mention_notifications(
         ON notification.recipient_id = recipient.id
         WHERE
             event_type = $1
+            AND recipient.account_kind != $3
         ORDER BY notification.id DESC
         LIMIT $2
         ",
@@ -255,7 +307,10 @@ pub async fn get_mention_notifications(
         related_emojis = RELATED_EMOJIS,
     );
     let rows = db_client
-        .query(&statement, &[&EventType::Mention, &i64::from(limit)])
+        .query(
+            &statement,
+            &[&EventType::Mention, &i64::from(limit), &AccountKind::User],
+        )
         .await?;
     let notifications: Vec<Notification> = rows
         .iter()
@@ -1,8 +1,12 @@
 use uuid::Uuid;
 
+use crate::bookmarks::queries::find_bookmarked_by_user;
 use crate::database::{DatabaseClient, DatabaseError};
 use crate::reactions::queries::find_favourited_by_user;
-use crate::relationships::{queries::has_relationship, types::RelationshipType};
+use crate::relationships::{
+    queries::{has_block, has_relationship},
+    types::RelationshipType,
+};
 use crate::users::types::{Permission, User};
 
 use super::queries::{find_reposted_by_user, get_post_by_id, get_related_posts};
@@ -60,17 +64,20 @@ pub async fn add_user_actions(
         .collect();
     let favourites = find_favourited_by_user(db_client, user_id, &posts_ids).await?;
     let reposted = find_reposted_by_user(db_client, user_id, &posts_ids).await?;
+    let bookmarked = find_bookmarked_by_user(db_client, user_id, &posts_ids).await?;
     for post in posts {
         if let Some(ref mut repost_of) = post.repost_of {
             let actions = PostActions {
                 favourited: favourites.contains(&repost_of.id),
                 reposted: reposted.contains(&repost_of.id),
+                bookmarked: bookmarked.contains(&repost_of.id),
             };
             repost_of.actions = Some(actions);
         };
         let actions = PostActions {
             favourited: favourites.contains(&post.id),
             reposted: reposted.contains(&post.id),
+            bookmarked: bookmarked.contains(&post.id),
         };
         post.actions = Some(actions);
     }
@@ -82,6 +89,11 @@ pub async fn can_view_post(
     user: Option<&User>,
     post: &Post,
 ) -> Result<bool, DatabaseError> {
+    if let Some(user) = user {
+        if user.id != post.author.id && has_block(db_client, &user.id, &post.author.id).await? {
+            return Ok(false);
+        };
+    };
     let is_mentioned = |user: &User| {
         post.mentions
             .iter()
@@ -143,7 +155,7 @@ mod tests {
     use super::*;
     use crate::database::test_utils::create_test_database;
     use crate::posts::{queries::create_post, types::PostCreateData};
-    use crate::relationships::queries::{follow, subscribe};
+    use crate::relationships::queries::{block, follow, subscribe};
     use crate::users::{
         queries::create_user,
         types::{Role, User, UserCreateData},
@@ -283,6 +295,24 @@ mod tests {
             .unwrap(),);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_can_view_post_blocked() {
+        let db_client = &mut create_test_database().await;
+        let author = create_test_user(db_client, "author").await;
+        let blocked = create_test_user(db_client, "blocked").await;
+        block(db_client, &author.id, &blocked.id).await.unwrap();
+        let post = Post {
+            author: author.profile,
+            visibility: Visibility::Public,
+            ..Default::default()
+        };
+        assert!(can_view_post(db_client, None, &post).await.unwrap());
+        assert!(!can_view_post(db_client, Some(&blocked), &post)
+            .await
+            .unwrap());
+    }
+
     #[test]
     fn test_can_create_post() {
         let mut user = User {
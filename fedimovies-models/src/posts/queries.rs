@@ -10,11 +10,16 @@ use crate::emojis::types::DbEmoji;
 use crate::notifications::queries::{
     create_mention_notification, create_reply_notification, create_repost_notification,
 };
-use crate::profiles::{queries::update_post_count, types::DbActorProfile};
+use crate::profiles::{
+    queries::{update_post_count, update_rating_stats},
+    types::{AccountKind, DbActorProfile},
+};
 use crate::relationships::queries::is_muted;
 use crate::relationships::types::RelationshipType;
 
-use super::types::{DbPost, Post, PostCreateData, PostUpdateData, Visibility};
+use super::types::{
+    DbPost, DbPostEdit, Post, PostCreateData, PostUpdateData, ReviewFilter, Visibility,
+};
 
 async fn create_post_attachments(
     db_client: &impl DatabaseClient,
@@ -162,6 +167,34 @@ async fn create_post_emojis(
     Ok(emojis)
 }
 
+/// Returns IDs of local profiles mentioned in given posts
+/// Returns local media accounts mentioned in the posts.
+/// Only these accounts have rating stats.
+async fn get_local_media_mentions(
+    db_client: &impl DatabaseClient,
+    posts_ids: &[Uuid],
+) -> Result<Vec<Uuid>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT DISTINCT mention.profile_id
+        FROM mention
+        JOIN actor_profile ON mention.profile_id = actor_profile.id
+        WHERE
+            mention.post_id = ANY($1)
+            AND actor_profile.actor_json IS NULL
+            AND actor_profile.account_kind != $2
+        ",
+            &[&posts_ids, &AccountKind::User],
+        )
+        .await?;
+    let profiles_ids = rows
+        .iter()
+        .map(|row| row.try_get("profile_id"))
+        .collect::<Result<_, _>>()?;
+    Ok(profiles_ids)
+}
+
 pub async fn create_post(
     db_client: &mut impl DatabaseClient,
     author_id: &Uuid,
@@ -180,9 +213,13 @@ pub async fn create_post(
             visibility,
             is_sensitive,
             object_id,
-            created_at
+            created_at,
+            rating,
+            language,
+            summary,
+            content_source
         )
-        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9
+        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13
         WHERE
         NOT EXISTS (
             SELECT 1 FROM post
@@ -212,6 +249,10 @@ pub async fn create_post(
                 &post_data.is_sensitive,
                 &post_data.object_id,
                 &post_data.created_at,
+                &post_data.rating,
+                &post_data.language,
+                &post_data.summary,
+                &post_data.content_source,
             ],
         )
         .await
@@ -306,6 +347,12 @@ pub async fn create_post(
             }
         };
     }
+    // Update ratings of mentioned local profiles
+    if db_post.in_reply_to_id.is_none() {
+        for profile_id in get_local_media_mentions(&transaction, &[db_post.id]).await? {
+            update_rating_stats(&transaction, &profile_id).await?;
+        }
+    };
     // Construct post object
     let post = Post::new(
         db_post,
@@ -324,8 +371,37 @@ pub async fn update_post(
     db_client: &mut impl DatabaseClient,
     post_id: &Uuid,
     post_data: PostUpdateData,
-) -> Result<(), DatabaseError> {
+) -> Result<DeletionQueue, DatabaseError> {
     let transaction = db_client.transaction().await?;
+    // Save current revision
+    transaction
+        .execute(
+            "
+        INSERT INTO post_edit (
+            id,
+            post_id,
+            content,
+            content_source,
+            summary,
+            is_sensitive,
+            rating,
+            created_at
+        )
+        SELECT
+            $1,
+            post.id,
+            post.content,
+            post.content_source,
+            post.summary,
+            post.is_sensitive,
+            post.rating,
+            coalesce(post.updated_at, post.created_at)
+        FROM post
+        WHERE post.id = $2
+        ",
+            &[&generate_ulid(), &post_id],
+        )
+        .await?;
     // Reposts and immutable posts can't be updated
     let maybe_row = transaction
         .query_opt(
@@ -333,16 +409,24 @@ pub async fn update_post(
         UPDATE post
         SET
             content = $1,
-            is_sensitive = $2,
-            updated_at = $3
-        WHERE id = $4
+            content_source = $2,
+            is_sensitive = $3,
+            rating = $4,
+            language = $5,
+            summary = $6,
+            updated_at = $7
+        WHERE id = $8
             AND repost_of_id IS NULL
             AND ipfs_cid IS NULL
         RETURNING post
         ",
             &[
                 &post_data.content,
+                &post_data.content_source,
                 &post_data.is_sensitive,
+                &post_data.rating,
+                &post_data.language,
+                &post_data.summary,
                 &post_data.updated_at,
                 &post_id,
             ],
@@ -350,14 +434,30 @@ pub async fn update_post(
         .await?;
     let row = maybe_row.ok_or(DatabaseError::NotFound("post"))?;
     let db_post: DbPost = row.try_get("post")?;
+    let mut rated_profiles = get_local_media_mentions(&transaction, &[db_post.id]).await?;
 
     // Delete and re-create related objects
-    transaction
-        .execute(
-            "DELETE FROM media_attachment WHERE post_id = $1",
-            &[&db_post.id],
+    // Attachments that are not in the new list are removed
+    let removed_attachments_rows = transaction
+        .query(
+            "
+        DELETE FROM media_attachment
+        WHERE post_id = $1 AND id <> ALL($2)
+        RETURNING file_name, ipfs_cid
+        ",
+            &[&db_post.id, &post_data.attachments],
         )
         .await?;
+    let mut files = vec![];
+    let mut ipfs_objects = vec![];
+    for row in removed_attachments_rows {
+        let file_name: String = row.try_get("file_name")?;
+        files.push(file_name);
+        let maybe_ipfs_cid: Option<String> = row.try_get("ipfs_cid")?;
+        if let Some(ipfs_cid) = maybe_ipfs_cid {
+            ipfs_objects.push(ipfs_cid);
+        };
+    }
     transaction
         .execute("DELETE FROM mention WHERE post_id = $1", &[&db_post.id])
         .await?;
@@ -382,8 +482,46 @@ pub async fn update_post(
     create_post_links(&transaction, &db_post.id, post_data.links).await?;
     create_post_emojis(&transaction, &db_post.id, post_data.emojis).await?;
 
+    // Update ratings of previously and currently mentioned local profiles
+    if db_post.in_reply_to_id.is_none() {
+        rated_profiles.extend(get_local_media_mentions(&transaction, &[db_post.id]).await?);
+        rated_profiles.sort();
+        rated_profiles.dedup();
+        for profile_id in rated_profiles {
+            update_rating_stats(&transaction, &profile_id).await?;
+        }
+    };
+
+    let orphaned_files = find_orphaned_files(&transaction, files).await?;
+    let orphaned_ipfs_objects = find_orphaned_ipfs_objects(&transaction, ipfs_objects).await?;
     transaction.commit().await?;
-    Ok(())
+    Ok(DeletionQueue {
+        files: orphaned_files,
+        ipfs_objects: orphaned_ipfs_objects,
+    })
+}
+
+/// Returns previous revisions of the post, oldest first
+pub async fn get_post_edits(
+    db_client: &impl DatabaseClient,
+    post_id: &Uuid,
+) -> Result<Vec<DbPostEdit>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT post_edit
+        FROM post_edit
+        WHERE post_id = $1
+        ORDER BY post_edit.created_at, post_edit.id
+        ",
+            &[&post_id],
+        )
+        .await?;
+    let edits = rows
+        .iter()
+        .map(|row| row.try_get("post_edit"))
+        .collect::<Result<_, _>>()?;
+    Ok(edits)
 }
 
 pub const RELATED_ATTACHMENTS: &str = "ARRAY(
@@ -417,7 +555,7 @@ pub const RELATED_EMOJIS: &str = "ARRAY(
         WHERE post_emoji.post_id = post.id
     ) AS emojis";
 
-fn build_visibility_filter() -> String {
+pub(crate) fn build_visibility_filter() -> String {
     format!(
         "(
             post.author_id = $current_user_id
@@ -441,6 +579,31 @@ fn build_visibility_filter() -> String {
     )
 }
 
+/// Excludes posts by authors who block or are blocked by the current user,
+/// and reposts of posts by blocked authors
+pub(crate) fn build_block_filter() -> String {
+    format!(
+        "NOT EXISTS (
+            SELECT 1 FROM relationship
+            WHERE
+                relationship_type = {relationship_block}
+                AND (
+                    source_id = $current_user_id AND target_id = post.author_id
+                    OR source_id = post.author_id AND target_id = $current_user_id
+                )
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM post AS repost_of
+            JOIN relationship ON relationship.target_id = repost_of.author_id
+            WHERE
+                repost_of.id = post.repost_of_id
+                AND relationship.source_id = $current_user_id
+                AND relationship.relationship_type = {relationship_block}
+        )",
+        relationship_block = i16::from(&RelationshipType::Block),
+    )
+}
+
 pub async fn get_home_timeline(
     db_client: &impl DatabaseClient,
     current_user_id: &Uuid,
@@ -524,6 +687,7 @@ pub async fn get_home_timeline(
                     AND target_id = post.author_id
                     AND relationship_type = {relationship_mute}
             )
+            AND {block_filter}
             AND {visibility_filter}
             AND ($max_post_id::uuid IS NULL OR post.id < $max_post_id)
         ORDER BY post.id DESC
@@ -539,6 +703,7 @@ pub async fn get_home_timeline(
         relationship_hide_reposts=i16::from(&RelationshipType::HideReposts),
         relationship_hide_replies=i16::from(&RelationshipType::HideReplies),
         relationship_mute=i16::from(&RelationshipType::Mute),
+        block_filter=build_block_filter(),
         visibility_filter=build_visibility_filter(),
     );
     let limit: i64 = limit.into();
@@ -553,6 +718,93 @@ pub async fn get_home_timeline(
     Ok(posts)
 }
 
+/// Returns posts made by members of the list.
+/// Repost, reply and mute settings of the list owner are respected.
+pub async fn get_list_timeline(
+    db_client: &impl DatabaseClient,
+    list_id: &Uuid,
+    current_user_id: &Uuid,
+    max_post_id: Option<Uuid>,
+    limit: u16,
+) -> Result<Vec<Post>, DatabaseError> {
+    let statement = format!(
+        "
+        SELECT
+            post, actor_profile,
+            {related_attachments},
+            {related_mentions},
+            {related_tags},
+            {related_links},
+            {related_emojis}
+        FROM post
+        JOIN actor_profile ON post.author_id = actor_profile.id
+        WHERE
+            EXISTS (
+                SELECT 1 FROM list_member
+                WHERE list_id = $list_id AND profile_id = post.author_id
+            )
+            AND (
+                -- show posts
+                post.repost_of_id IS NULL
+                -- show reposts if they are not hidden
+                OR NOT EXISTS (
+                    SELECT 1 FROM relationship
+                    WHERE
+                        source_id = $current_user_id
+                        AND target_id = post.author_id
+                        AND relationship_type = {relationship_hide_reposts}
+                )
+            )
+            AND (
+                -- show posts (top-level)
+                post.in_reply_to_id IS NULL
+                -- show replies if they are not hidden
+                OR NOT EXISTS (
+                    SELECT 1 FROM relationship
+                    WHERE
+                        source_id = $current_user_id
+                        AND target_id = post.author_id
+                        AND relationship_type = {relationship_hide_replies}
+                )
+            )
+            -- author is not muted
+            AND NOT EXISTS (
+                SELECT 1 FROM relationship
+                WHERE
+                    source_id = $current_user_id
+                    AND target_id = post.author_id
+                    AND relationship_type = {relationship_mute}
+            )
+            AND {block_filter}
+            AND {visibility_filter}
+            AND ($max_post_id::uuid IS NULL OR post.id < $max_post_id)
+        ORDER BY post.id DESC
+        LIMIT $limit
+        ",
+        related_attachments = RELATED_ATTACHMENTS,
+        related_mentions = RELATED_MENTIONS,
+        related_tags = RELATED_TAGS,
+        related_links = RELATED_LINKS,
+        related_emojis = RELATED_EMOJIS,
+        relationship_hide_reposts = i16::from(&RelationshipType::HideReposts),
+        relationship_hide_replies = i16::from(&RelationshipType::HideReplies),
+        relationship_mute = i16::from(&RelationshipType::Mute),
+        block_filter = build_block_filter(),
+        visibility_filter = build_visibility_filter(),
+    );
+    let limit: i64 = limit.into();
+    let query = query!(
+        &statement,
+        list_id = list_id,
+        current_user_id = current_user_id,
+        max_post_id = max_post_id,
+        limit = limit,
+    )?;
+    let rows = db_client.query(query.sql(), query.parameters()).await?;
+    let posts: Vec<Post> = rows.iter().map(Post::try_from).collect::<Result<_, _>>()?;
+    Ok(posts)
+}
+
 pub async fn get_local_timeline(
     db_client: &impl DatabaseClient,
     current_user_id: &Uuid,
@@ -573,6 +825,7 @@ pub async fn get_local_timeline(
         WHERE
             actor_profile.actor_json IS NULL
             AND post.visibility = {visibility_public}
+            AND {block_filter}
             AND ($max_post_id::uuid IS NULL OR post.id < $max_post_id)
         ORDER BY post.id DESC
         LIMIT $limit
@@ -583,6 +836,7 @@ pub async fn get_local_timeline(
         related_links = RELATED_LINKS,
         related_emojis = RELATED_EMOJIS,
         visibility_public = i16::from(&Visibility::Public),
+        block_filter = build_block_filter(),
     );
     let limit: i64 = limit.into();
     let query = query!(
@@ -649,8 +903,10 @@ pub async fn get_posts_by_author(
     let mut condition = format!(
         "post.author_id = $profile_id
         AND {visibility_filter}
+        AND {block_filter}
         AND ($max_post_id::uuid IS NULL OR post.id < $max_post_id)",
         visibility_filter = build_visibility_filter(),
+        block_filter = build_block_filter(),
     );
     if !include_replies {
         condition.push_str(" AND post.in_reply_to_id IS NULL");
@@ -693,6 +949,76 @@ pub async fn get_posts_by_author(
     Ok(posts)
 }
 
+/// Returns posts mentioning the given profile (movie reviews), latest first
+pub async fn get_posts_by_mentioned_profile(
+    db_client: &impl DatabaseClient,
+    profile_id: &Uuid,
+    current_user_id: Option<&Uuid>,
+    filter: &ReviewFilter,
+    max_post_id: Option<Uuid>,
+    limit: u16,
+) -> Result<Vec<Post>, DatabaseError> {
+    let mut condition = format!(
+        "EXISTS (
+            SELECT 1 FROM mention
+            WHERE mention.post_id = post.id AND mention.profile_id = $profile_id
+        )
+        AND {visibility_filter}
+        AND {block_filter}
+        AND ($max_post_id::uuid IS NULL OR post.id < $max_post_id)
+        AND (
+            $language::text IS NULL
+            OR post.language = $language
+            OR post.language LIKE $language || '-%'
+        )",
+        visibility_filter = build_visibility_filter(),
+        block_filter = build_block_filter(),
+    );
+    if filter.only_rated {
+        condition.push_str(" AND post.rating IS NOT NULL");
+    };
+    if filter.only_local {
+        condition.push_str(" AND actor_profile.actor_json IS NULL");
+    };
+    if filter.only_remote {
+        condition.push_str(" AND actor_profile.actor_json IS NOT NULL");
+    };
+    let statement = format!(
+        "
+        SELECT
+            post, actor_profile,
+            {related_attachments},
+            {related_mentions},
+            {related_tags},
+            {related_links},
+            {related_emojis}
+        FROM post
+        JOIN actor_profile ON post.author_id = actor_profile.id
+        WHERE {condition}
+        ORDER BY post.id DESC
+        LIMIT $limit
+        ",
+        related_attachments = RELATED_ATTACHMENTS,
+        related_mentions = RELATED_MENTIONS,
+        related_tags = RELATED_TAGS,
+        related_links = RELATED_LINKS,
+        related_emojis = RELATED_EMOJIS,
+        condition = condition,
+    );
+    let limit: i64 = limit.into();
+    let query = query!(
+        &statement,
+        profile_id = profile_id,
+        current_user_id = current_user_id,
+        language = filter.language,
+        max_post_id = max_post_id,
+        limit = limit,
+    )?;
+    let rows = db_client.query(query.sql(), query.parameters()).await?;
+    let posts: Vec<Post> = rows.iter().map(Post::try_from).collect::<Result<_, _>>()?;
+    Ok(posts)
+}
+
 pub async fn get_posts_by_tag(
     db_client: &impl DatabaseClient,
     tag_name: &str,
@@ -718,6 +1044,7 @@ pub async fn get_posts_by_tag(
                 WHERE post_tag.post_id = post.id AND tag.tag_name = $tag_name
             )
             AND {visibility_filter}
+            AND {block_filter}
             AND ($max_post_id::uuid IS NULL OR post.id < $max_post_id)
         ORDER BY post.id DESC
         LIMIT $limit
@@ -728,6 +1055,7 @@ pub async fn get_posts_by_tag(
         related_links = RELATED_LINKS,
         related_emojis = RELATED_EMOJIS,
         visibility_filter = build_visibility_filter(),
+        block_filter = build_block_filter(),
     );
     let limit: i64 = limit.into();
     let query = query!(
@@ -773,6 +1101,35 @@ pub async fn get_post_by_id(
     Ok(post)
 }
 
+/// Returns posts with the given IDs (in arbitrary order)
+pub async fn get_posts_by_ids(
+    db_client: &impl DatabaseClient,
+    posts_ids: &[Uuid],
+) -> Result<Vec<Post>, DatabaseError> {
+    let statement = format!(
+        "
+        SELECT
+            post, actor_profile,
+            {related_attachments},
+            {related_mentions},
+            {related_tags},
+            {related_links},
+            {related_emojis}
+        FROM post
+        JOIN actor_profile ON post.author_id = actor_profile.id
+        WHERE post.id = ANY($1)
+        ",
+        related_attachments = RELATED_ATTACHMENTS,
+        related_mentions = RELATED_MENTIONS,
+        related_tags = RELATED_TAGS,
+        related_links = RELATED_LINKS,
+        related_emojis = RELATED_EMOJIS,
+    );
+    let rows = db_client.query(&statement, &[&posts_ids]).await?;
+    let posts: Vec<Post> = rows.iter().map(Post::try_from).collect::<Result<_, _>>()?;
+    Ok(posts)
+}
+
 /// Given a post ID, finds all items in thread.
 /// Results are sorted by tree path.
 pub async fn get_thread(
@@ -810,7 +1167,7 @@ pub async fn get_thread(
         FROM post
         JOIN thread ON post.id = thread.id
         JOIN actor_profile ON post.author_id = actor_profile.id
-        WHERE {visibility_filter}
+        WHERE {visibility_filter} AND {block_filter}
         ORDER BY thread.path
         ",
         related_attachments = RELATED_ATTACHMENTS,
@@ -819,6 +1176,7 @@ pub async fn get_thread(
         related_links = RELATED_LINKS,
         related_emojis = RELATED_EMOJIS,
         visibility_filter = build_visibility_filter(),
+        block_filter = build_block_filter(),
     );
     let query = query!(
         &statement,
@@ -1099,6 +1457,83 @@ pub async fn find_reposted_by_user(
     Ok(reposted)
 }
 
+/// Returns true if user has reposted any post of the given author
+pub async fn has_reposted_author(
+    db_client: &impl DatabaseClient,
+    user_id: &Uuid,
+    author_id: &Uuid,
+) -> Result<bool, DatabaseError> {
+    let row = db_client
+        .query_one(
+            "
+        SELECT EXISTS (
+            SELECT 1
+            FROM post AS repost
+            JOIN post ON repost.repost_of_id = post.id
+            WHERE repost.author_id = $1 AND post.author_id = $2
+        ) AS is_reposted
+        ",
+            &[&user_id, &author_id],
+        )
+        .await?;
+    let is_reposted = row.try_get("is_reposted")?;
+    Ok(is_reposted)
+}
+
+/// Returns true if the author has a post mentioning the profile
+/// that was created at the given time
+pub async fn has_post_with_mention(
+    db_client: &impl DatabaseClient,
+    author_id: &Uuid,
+    profile_id: &Uuid,
+    created_at: &DateTime<Utc>,
+) -> Result<bool, DatabaseError> {
+    let row = db_client
+        .query_one(
+            "
+        SELECT EXISTS (
+            SELECT 1
+            FROM post
+            JOIN mention ON mention.post_id = post.id
+            WHERE
+                post.author_id = $1
+                AND mention.profile_id = $2
+                AND post.created_at = $3
+        ) AS has_post
+        ",
+            &[&author_id, &profile_id, &created_at],
+        )
+        .await?;
+    let has_post = row.try_get("has_post")?;
+    Ok(has_post)
+}
+
+/// Finds reposts of the given post made by local movie accounts
+pub async fn find_media_reposts(
+    db_client: &impl DatabaseClient,
+    post_id: &Uuid,
+) -> Result<Vec<DbPost>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT post
+        FROM post
+        JOIN actor_profile ON post.author_id = actor_profile.id
+        WHERE
+            post.repost_of_id = $1
+            AND actor_profile.account_kind != $2
+            AND actor_profile.actor_json IS NULL
+        ",
+            &[&post_id, &AccountKind::User],
+        )
+        .await?;
+    let reposts = rows
+        .iter()
+        .map(|row| row.try_get("post"))
+        .collect::<Result<_, _>>()?;
+    Ok(reposts)
+}
+
 pub async fn get_token_waitlist(
     db_client: &impl DatabaseClient,
 ) -> Result<Vec<Uuid>, DatabaseError> {
@@ -1262,6 +1697,7 @@ pub async fn delete_post(
         .iter()
         .map(|row| row.try_get("ipfs_cid"))
         .collect::<Result<_, _>>()?;
+    let rated_profiles = get_local_media_mentions(&transaction, &posts).await?;
     // Update post counters
     transaction
         .execute(
@@ -1297,6 +1733,9 @@ pub async fn delete_post(
     if let Some(repost_of_id) = &db_post.repost_of_id {
         update_repost_count(&transaction, repost_of_id, -1).await?;
     };
+    for profile_id in rated_profiles {
+        update_rating_stats(&transaction, &profile_id).await?;
+    }
     let orphaned_files = find_orphaned_files(&transaction, files).await?;
     let orphaned_ipfs_objects = find_orphaned_ipfs_objects(&transaction, ipfs_objects).await?;
     transaction.commit().await?;
@@ -1325,11 +1764,16 @@ pub async fn get_local_post_count(db_client: &impl DatabaseClient) -> Result<i64
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::attachments::queries::create_attachment;
     use crate::database::test_utils::create_test_database;
-    use crate::profiles::{queries::create_profile, types::ProfileCreateData};
-    use crate::relationships::queries::{follow, hide_reposts, subscribe};
+    use crate::lists::queries::{add_list_members, create_list};
+    use crate::profiles::{
+        queries::{create_profile, get_profile_by_id},
+        types::{DbActor, ProfileCreateData},
+    };
+    use crate::relationships::queries::{block, follow, hide_reposts, subscribe};
     use crate::users::{queries::create_user, types::UserCreateData};
-    use chrono::Duration;
+    use chrono::{Duration, TimeZone};
     use serial_test::serial;
 
     #[tokio::test]
@@ -1355,6 +1799,105 @@ mod tests {
         assert_eq!(post.updated_at, None);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_create_post_with_rating() {
+        let db_client = &mut create_test_database().await;
+        let author_data = ProfileCreateData {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let author = create_profile(db_client, author_data).await.unwrap();
+        let post_data = PostCreateData {
+            content: "review".to_string(),
+            rating: Some(4.5),
+            ..Default::default()
+        };
+        let post = create_post(db_client, &author.id, post_data).await.unwrap();
+        assert_eq!(post.rating, Some(4.5));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_post_with_summary() {
+        let db_client = &mut create_test_database().await;
+        let author_data = ProfileCreateData {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let author = create_profile(db_client, author_data).await.unwrap();
+        let post_data = PostCreateData {
+            content: "review".to_string(),
+            is_sensitive: true,
+            summary: Some("spoilers".to_string()),
+            ..Default::default()
+        };
+        let post = create_post(db_client, &author.id, post_data).await.unwrap();
+        assert_eq!(post.summary.as_deref(), Some("spoilers"));
+
+        let post_data = PostUpdateData {
+            content: "review".to_string(),
+            updated_at: Utc::now(),
+            ..Default::default()
+        };
+        update_post(db_client, &post.id, post_data).await.unwrap();
+        let post = get_post_by_id(db_client, &post.id).await.unwrap();
+        assert_eq!(post.summary, None);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_rating_stats() {
+        let db_client = &mut create_test_database().await;
+        let author_data = ProfileCreateData {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let author = create_profile(db_client, author_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Movie_2022_101".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(101),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap().profile;
+        let user_data = UserCreateData {
+            username: "user".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let post_data_1 = PostCreateData {
+            content: "review".to_string(),
+            mentions: vec![movie.id, user.id],
+            rating: Some(4.5),
+            ..Default::default()
+        };
+        create_post(db_client, &author.id, post_data_1)
+            .await
+            .unwrap();
+        let post_data_2 = PostCreateData {
+            content: "review without rating".to_string(),
+            mentions: vec![movie.id],
+            ..Default::default()
+        };
+        let post_2 = create_post(db_client, &author.id, post_data_2)
+            .await
+            .unwrap();
+        let movie = get_profile_by_id(db_client, &movie.id).await.unwrap();
+        assert_eq!(movie.rating_stats.review_count, 2);
+        assert_eq!(movie.rating_stats.rating_count(), 1);
+        assert_eq!(movie.rating_stats.histogram[8], 1);
+        assert_eq!(movie.rating_stats.average_rating(), Some(4.5));
+        // Regular accounts are not rated
+        let user = get_profile_by_id(db_client, &user.id).await.unwrap();
+        assert_eq!(user.rating_stats.review_count, 0);
+
+        delete_post(db_client, &post_2.id).await.unwrap();
+        let movie = get_profile_by_id(db_client, &movie.id).await.unwrap();
+        assert_eq!(movie.rating_stats.review_count, 1);
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_create_post_with_link() {
@@ -1402,6 +1945,49 @@ mod tests {
         let post = get_post_by_id(db_client, &post.id).await.unwrap();
         assert_eq!(post.content, "test update");
         assert!(post.updated_at.is_some());
+        let edits = get_post_edits(db_client, &post.id).await.unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].content, "test post");
+        assert_eq!(edits[0].created_at, post.created_at);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_update_post_remove_attachment() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let attachment = create_attachment(
+            db_client,
+            &user.id,
+            "test.png".to_string(),
+            1000,
+            Some("image/png".to_string()),
+        )
+        .await
+        .unwrap();
+        let post_data = PostCreateData {
+            content: "test post".to_string(),
+            attachments: vec![attachment.id],
+            ..Default::default()
+        };
+        let post = create_post(db_client, &user.id, post_data).await.unwrap();
+        assert_eq!(post.attachments.len(), 1);
+
+        let post_data = PostUpdateData {
+            content: "test update".to_string(),
+            updated_at: Utc::now(),
+            ..Default::default()
+        };
+        let deletion_queue = update_post(db_client, &post.id, post_data).await.unwrap();
+        assert_eq!(deletion_queue.files, vec!["test.png"]);
+        assert_eq!(deletion_queue.ipfs_objects.len(), 0);
+        let post = get_post_by_id(db_client, &post.id).await.unwrap();
+        assert_eq!(post.attachments.len(), 0);
     }
 
     #[tokio::test]
@@ -1605,6 +2191,111 @@ mod tests {
         assert!(!timeline.iter().any(|post| post.id == post_13.id));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_list_timeline() {
+        let db_client = &mut create_test_database().await;
+        let current_user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let current_user = create_user(db_client, current_user_data).await.unwrap();
+        let member_data = UserCreateData {
+            username: "critic".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let member = create_user(db_client, member_data).await.unwrap();
+        let other_user_data = UserCreateData {
+            username: "other".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let other_user = create_user(db_client, other_user_data).await.unwrap();
+        follow(db_client, &current_user.id, &member.id)
+            .await
+            .unwrap();
+        follow(db_client, &current_user.id, &other_user.id)
+            .await
+            .unwrap();
+        let list = create_list(db_client, &current_user.id, "Critics")
+            .await
+            .unwrap();
+        add_list_members(db_client, &list.id, &[member.id])
+            .await
+            .unwrap();
+        // Member's post
+        let post_data_1 = PostCreateData {
+            content: "review".to_string(),
+            ..Default::default()
+        };
+        let post_1 = create_post(db_client, &member.id, post_data_1)
+            .await
+            .unwrap();
+        // Post by user who is not a member
+        let post_data_2 = PostCreateData {
+            content: "review".to_string(),
+            ..Default::default()
+        };
+        let post_2 = create_post(db_client, &other_user.id, post_data_2)
+            .await
+            .unwrap();
+        // Member's repost (hidden)
+        hide_reposts(db_client, &current_user.id, &member.id)
+            .await
+            .unwrap();
+        let repost_data = PostCreateData::repost(post_2.id, None);
+        let repost = create_post(db_client, &member.id, repost_data)
+            .await
+            .unwrap();
+
+        let timeline = get_list_timeline(db_client, &list.id, &current_user.id, None, 20)
+            .await
+            .unwrap();
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].id, post_1.id);
+        assert!(!timeline.iter().any(|post| post.id == repost.id));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_home_timeline_blocked() {
+        let db_client = &mut create_test_database().await;
+        let current_user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let current_user = create_user(db_client, current_user_data).await.unwrap();
+        let user_data = UserCreateData {
+            username: "another-user".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let post_data = PostCreateData {
+            content: "mention".to_string(),
+            mentions: vec![current_user.id],
+            ..Default::default()
+        };
+        let post = create_post(db_client, &user.id, post_data).await.unwrap();
+        let timeline = get_home_timeline(db_client, &current_user.id, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].id, post.id);
+
+        // Blocked by the author
+        block(db_client, &user.id, &current_user.id).await.unwrap();
+        let timeline = get_home_timeline(db_client, &current_user.id, None, 10)
+            .await
+            .unwrap();
+        assert!(timeline.is_empty());
+        let thread = get_thread(db_client, &post.id, Some(&current_user.id)).await;
+        assert!(matches!(thread, Err(DatabaseError::NotFound("post"))));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_profile_timeline_public() {
@@ -1697,6 +2388,183 @@ mod tests {
         assert_eq!(thread[1].id, post_2.id);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_get_posts_by_mentioned_profile() {
+        let db_client = &mut create_test_database().await;
+        let author_data = ProfileCreateData {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let author = create_profile(db_client, author_data).await.unwrap();
+        let remote_author_data = ProfileCreateData {
+            username: "remote".to_string(),
+            hostname: Some("example.org".to_string()),
+            actor_json: Some(DbActor::default()),
+            ..Default::default()
+        };
+        let remote_author = create_profile(db_client, remote_author_data).await.unwrap();
+        let movie_data = ProfileCreateData {
+            username: "Movie_2022".to_string(),
+            ..Default::default()
+        };
+        let movie = create_profile(db_client, movie_data).await.unwrap();
+        let post_data_1 = PostCreateData {
+            content: "review".to_string(),
+            mentions: vec![movie.id],
+            rating: Some(4.5),
+            language: Some("en".to_string()),
+            ..Default::default()
+        };
+        let post_1 = create_post(db_client, &author.id, post_data_1)
+            .await
+            .unwrap();
+        let post_data_2 = PostCreateData {
+            content: "remote review".to_string(),
+            mentions: vec![movie.id],
+            language: Some("pt-BR".to_string()),
+            object_id: Some("https://example.org/objects/1".to_string()),
+            ..Default::default()
+        };
+        let post_2 = create_post(db_client, &remote_author.id, post_data_2)
+            .await
+            .unwrap();
+        let post_data_3 = PostCreateData {
+            content: "not a review".to_string(),
+            ..Default::default()
+        };
+        create_post(db_client, &author.id, post_data_3)
+            .await
+            .unwrap();
+
+        let filter = ReviewFilter::default();
+        let posts = get_posts_by_mentioned_profile(db_client, &movie.id, None, &filter, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].id, post_2.id);
+        assert_eq!(posts[1].id, post_1.id);
+        let posts = get_posts_by_mentioned_profile(
+            db_client,
+            &movie.id,
+            None,
+            &filter,
+            Some(post_2.id),
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, post_1.id);
+
+        let filter = ReviewFilter {
+            only_rated: true,
+            ..Default::default()
+        };
+        let posts = get_posts_by_mentioned_profile(db_client, &movie.id, None, &filter, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, post_1.id);
+
+        let filter = ReviewFilter {
+            only_remote: true,
+            ..Default::default()
+        };
+        let posts = get_posts_by_mentioned_profile(db_client, &movie.id, None, &filter, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, post_2.id);
+
+        let filter = ReviewFilter {
+            language: Some("pt".to_string()),
+            ..Default::default()
+        };
+        let posts = get_posts_by_mentioned_profile(db_client, &movie.id, None, &filter, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, post_2.id);
+        assert_eq!(posts[0].language.as_deref(), Some("pt-BR"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_find_media_reposts() {
+        let db_client = &mut create_test_database().await;
+        let author_data = ProfileCreateData {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let author = create_profile(db_client, author_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Movie_2022_101".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(101),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+        let post_data = PostCreateData {
+            content: "review".to_string(),
+            mentions: vec![movie.id],
+            ..Default::default()
+        };
+        let post = create_post(db_client, &author.id, post_data).await.unwrap();
+        assert!(!has_reposted_author(db_client, &movie.id, &author.id)
+            .await
+            .unwrap());
+
+        let repost_data = PostCreateData::repost(post.id, None);
+        let repost = create_post(db_client, &movie.id, repost_data)
+            .await
+            .unwrap();
+        assert!(has_reposted_author(db_client, &movie.id, &author.id)
+            .await
+            .unwrap());
+        let reposts = find_media_reposts(db_client, &post.id).await.unwrap();
+        assert_eq!(reposts.len(), 1);
+        assert_eq!(reposts[0].id, repost.id);
+        assert_eq!(reposts[0].author_id, movie.id);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_has_post_with_mention() {
+        let db_client = &mut create_test_database().await;
+        let author_data = ProfileCreateData {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let author = create_profile(db_client, author_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Movie_2022_101".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(101),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+        let created_at = Utc.with_ymd_and_hms(2021, 10, 22, 0, 0, 0).unwrap();
+        assert!(
+            !has_post_with_mention(db_client, &author.id, &movie.id, &created_at)
+                .await
+                .unwrap()
+        );
+
+        let post_data = PostCreateData {
+            content: "review".to_string(),
+            mentions: vec![movie.id],
+            created_at,
+            ..Default::default()
+        };
+        create_post(db_client, &author.id, post_data).await.unwrap();
+        assert!(
+            has_post_with_mention(db_client, &author.id, &movie.id, &created_at)
+                .await
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_find_extraneous_posts() {
@@ -73,6 +73,10 @@ pub struct DbPost {
     pub token_tx_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>, // edited at
+    pub rating: Option<f32>,
+    pub language: Option<String>,
+    pub summary: Option<String>,
+    pub content_source: Option<String>,
 }
 
 // List of user's actions
@@ -80,6 +84,7 @@ pub struct DbPost {
 pub struct PostActions {
     pub favourited: bool,
     pub reposted: bool,
+    pub bookmarked: bool,
 }
 
 #[derive(Clone)]
@@ -105,6 +110,10 @@ pub struct Post {
     pub token_tx_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub rating: Option<f32>,
+    pub language: Option<String>,
+    pub summary: Option<String>,
+    pub content_source: Option<String>,
 
     // These fields are not populated automatically
     // by functions in posts::queries module
@@ -134,6 +143,10 @@ impl Post {
         if db_post.repost_of_id.is_some()
             && (db_post.content.len() != 0
                 || db_post.is_sensitive
+                || db_post.rating.is_some()
+                || db_post.language.is_some()
+                || db_post.summary.is_some()
+                || db_post.content_source.is_some()
                 || db_post.in_reply_to_id.is_some()
                 || db_post.ipfs_cid.is_some()
                 || db_post.token_id.is_some()
@@ -168,6 +181,10 @@ impl Post {
             token_tx_id: db_post.token_tx_id,
             created_at: db_post.created_at,
             updated_at: db_post.updated_at,
+            rating: db_post.rating,
+            language: db_post.language,
+            summary: db_post.summary,
+            content_source: db_post.content_source,
             actions: None,
             in_reply_to: None,
             repost_of: None,
@@ -210,6 +227,10 @@ impl Default for Post {
             token_tx_id: None,
             created_at: Utc::now(),
             updated_at: None,
+            rating: None,
+            language: None,
+            summary: None,
+            content_source: None,
             actions: None,
             in_reply_to: None,
             repost_of: None,
@@ -255,6 +276,10 @@ pub struct PostCreateData {
     pub links: Vec<Uuid>,
     pub emojis: Vec<Uuid>,
     pub object_id: Option<String>,
+    pub rating: Option<f32>,
+    pub language: Option<String>,
+    pub summary: Option<String>,
+    pub content_source: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -269,10 +294,24 @@ impl PostCreateData {
     }
 }
 
+/// Filters for posts mentioning a movie
+#[derive(Default)]
+pub struct ReviewFilter {
+    pub only_rated: bool,
+    pub only_local: bool,
+    pub only_remote: bool,
+    /// Language code, matches posts with region subtags too
+    pub language: Option<String>,
+}
+
 #[cfg_attr(test, derive(Default))]
 pub struct PostUpdateData {
     pub content: String,
+    pub content_source: Option<String>,
     pub is_sensitive: bool,
+    pub rating: Option<f32>,
+    pub language: Option<String>,
+    pub summary: Option<String>,
     pub attachments: Vec<Uuid>,
     pub mentions: Vec<Uuid>,
     pub tags: Vec<String>,
@@ -280,3 +319,17 @@ pub struct PostUpdateData {
     pub emojis: Vec<Uuid>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// Previous revision of the post
+#[derive(FromSql)]
+#[postgres(name = "post_edit")]
+pub struct DbPostEdit {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub content: String,
+    pub content_source: Option<String>,
+    pub summary: Option<String>,
+    pub is_sensitive: bool,
+    pub rating: Option<f32>,
+    pub created_at: DateTime<Utc>,
+}
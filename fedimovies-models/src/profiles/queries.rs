@@ -7,11 +7,12 @@ use crate::cleanup::{find_orphaned_files, find_orphaned_ipfs_objects, DeletionQu
 use crate::database::{catch_unique_violation, query_macro::query, DatabaseClient, DatabaseError};
 use crate::emojis::types::DbEmoji;
 use crate::instances::queries::create_instance;
+use crate::posts::types::Visibility;
 use crate::relationships::types::RelationshipType;
 
 use super::types::{
-    Aliases, DbActorProfile, ExtraFields, IdentityProofs, PaymentOptions, ProfileCreateData,
-    ProfileUpdateData,
+    AccountKind, Aliases, DbActorProfile, ExtraFields, IdentityProofs, PaymentOptions,
+    ProfileCreateData, ProfileUpdateData, RatingStats, RATING_STEPS,
 };
 
 async fn create_profile_emojis(
@@ -339,6 +340,27 @@ pub async fn get_profiles_by_accts(
     Ok(profiles)
 }
 
+pub async fn get_profiles_by_ids(
+    db_client: &impl DatabaseClient,
+    profiles_ids: &[Uuid],
+) -> Result<Vec<DbActorProfile>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT actor_profile
+        FROM actor_profile
+        WHERE id = ANY($1)
+        ",
+            &[&profiles_ids],
+        )
+        .await?;
+    let profiles = rows
+        .iter()
+        .map(|row| row.try_get("actor_profile"))
+        .collect::<Result<_, _>>()?;
+    Ok(profiles)
+}
+
 /// Deletes profile from database and returns collection of orphaned objects.
 pub async fn delete_profile(
     db_client: &mut impl DatabaseClient,
@@ -567,6 +589,56 @@ pub async fn search_profiles(
     Ok(profiles)
 }
 
+/// Finds local movie and TV show accounts by title,
+/// most followed first
+pub async fn search_media_profiles(
+    db_client: &impl DatabaseClient,
+    title: &str,
+    maybe_year: Option<i32>,
+    limit: u16,
+) -> Result<Vec<DbActorProfile>, DatabaseError> {
+    // Wildcards in the title are matched literally
+    let title = title
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    // Usernames are camel-cased titles followed by year and TMDB ID
+    let rows = db_client
+        .query(
+            r"
+        SELECT actor_profile
+        FROM actor_profile
+        WHERE
+            actor_profile.actor_json IS NULL
+            AND actor_profile.account_kind NOT IN ($1, $2)
+            AND (
+                actor_profile.display_name ILIKE '%' || $3::text || '%' ESCAPE '\'
+                OR actor_profile.username
+                    ILIKE '%' || replace($3::text, ' ', '') || '%' ESCAPE '\'
+            )
+            AND (
+                $4::integer IS NULL
+                OR actor_profile.username LIKE '%\_' || $4::integer || '\_%' ESCAPE '\'
+            )
+        ORDER BY actor_profile.follower_count DESC, actor_profile.username
+        LIMIT $5
+        ",
+            &[
+                &AccountKind::User,
+                &AccountKind::MoviePerson,
+                &title,
+                &maybe_year,
+                &i64::from(limit),
+            ],
+        )
+        .await?;
+    let profiles: Vec<DbActorProfile> = rows
+        .iter()
+        .map(|row| row.try_get("actor_profile"))
+        .collect::<Result<_, _>>()?;
+    Ok(profiles)
+}
+
 pub async fn search_profiles_by_did_only(
     db_client: &impl DatabaseClient,
     did: &Did,
@@ -747,6 +819,60 @@ pub async fn update_post_count(
     Ok(profile)
 }
 
+/// Re-calculates rating stats from public top-level posts
+/// that mention the profile
+pub async fn update_rating_stats(
+    db_client: &impl DatabaseClient,
+    profile_id: &Uuid,
+) -> Result<DbActorProfile, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT
+            (post.rating * 2)::SMALLINT AS rating_step,
+            count(post) AS review_count
+        FROM post
+        JOIN mention ON mention.post_id = post.id
+        WHERE
+            mention.profile_id = $1
+            AND post.author_id != $1
+            AND post.in_reply_to_id IS NULL
+            AND post.repost_of_id IS NULL
+            AND post.visibility = $2
+        GROUP BY rating_step
+        ",
+            &[&profile_id, &Visibility::Public],
+        )
+        .await?;
+    let mut rating_stats = RatingStats::default();
+    for row in rows {
+        let maybe_rating_step: Option<i16> = row.try_get("rating_step")?;
+        let review_count: i64 = row.try_get("review_count")?;
+        let review_count = i32::try_from(review_count).unwrap_or(i32::MAX);
+        rating_stats.review_count += review_count;
+        if let Some(rating_step) = maybe_rating_step {
+            let index = usize::try_from(rating_step - 1).unwrap_or(0);
+            if index < RATING_STEPS {
+                rating_stats.histogram[index] += review_count;
+            };
+        };
+    }
+    let maybe_row = db_client
+        .query_opt(
+            "
+        UPDATE actor_profile
+        SET rating_stats = $1
+        WHERE id = $2
+        RETURNING actor_profile
+        ",
+            &[&rating_stats, &profile_id],
+        )
+        .await?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("profile"))?;
+    let profile = row.try_get("actor_profile")?;
+    Ok(profile)
+}
+
 // Doesn't return error if profile doesn't exist
 pub async fn set_reachability_status(
     db_client: &impl DatabaseClient,
@@ -997,6 +1123,60 @@ mod tests {
         assert_eq!(deletion_queue.ipfs_objects.len(), 0);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_search_media_profiles() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        create_user(db_client, user_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Dune_2021_438631".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(438631),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+        let mut profile_data = ProfileUpdateData::from(&movie.profile);
+        profile_data.display_name = Some("Dune".to_string());
+        update_profile(db_client, &movie.id, profile_data)
+            .await
+            .unwrap();
+        let movie_data = UserCreateData {
+            username: "Dune_1984_841".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(841),
+            ..Default::default()
+        };
+        create_user(db_client, movie_data).await.unwrap();
+
+        let profiles = search_media_profiles(db_client, "dune", None, 10)
+            .await
+            .unwrap();
+        assert_eq!(profiles.len(), 2);
+        let profiles = search_media_profiles(db_client, "Dune", Some(2021), 10)
+            .await
+            .unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].id, movie.id);
+        let profiles = search_media_profiles(db_client, "test", None, 10)
+            .await
+            .unwrap();
+        assert!(profiles.is_empty());
+        // Wildcards are matched literally
+        let profiles = search_media_profiles(db_client, "%", None, 10)
+            .await
+            .unwrap();
+        assert!(profiles.is_empty());
+        let profiles = search_media_profiles(db_client, "D_ne", None, 10)
+            .await
+            .unwrap();
+        assert!(profiles.is_empty());
+    }
+
     const ETHEREUM: Currency = Currency::Ethereum;
 
     #[tokio::test]
@@ -9,6 +9,7 @@ use uuid::Uuid;
 use fedimovies_utils::{caip2::ChainId, did::Did};
 
 use crate::database::{
+    int_enum::{int_enum_from_sql, int_enum_to_sql},
     json_macro::{json_from_sql, json_to_sql},
     DatabaseTypeError,
 };
@@ -319,6 +320,40 @@ impl Aliases {
 json_from_sql!(Aliases);
 json_to_sql!(Aliases);
 
+pub const RATING_STEPS: usize = 10;
+
+/// Ratings of public top-level posts that mention the profile
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RatingStats {
+    pub review_count: i32,
+    /// Number of ratings for each half-star step (0.5 - 5.0)
+    pub histogram: [i32; RATING_STEPS],
+}
+
+impl RatingStats {
+    pub fn rating_count(&self) -> i32 {
+        self.histogram.iter().sum()
+    }
+
+    pub fn average_rating(&self) -> Option<f32> {
+        let rating_count = self.rating_count();
+        if rating_count == 0 {
+            return None;
+        };
+        let rating_sum: f32 = self
+            .histogram
+            .iter()
+            .enumerate()
+            .map(|(index, count)| (index + 1) as f32 * 0.5 * *count as f32)
+            .sum();
+        Some(rating_sum / rating_count as f32)
+    }
+}
+
+json_from_sql!(RatingStats);
+json_to_sql!(RatingStats);
+
 #[derive(Clone, Deserialize)]
 pub struct ProfileEmojis(Vec<DbEmoji>);
 
@@ -360,6 +395,60 @@ pub struct DbActor {
 json_from_sql!(DbActor);
 json_to_sql!(DbActor);
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccountKind {
+    User,
+    Movie,
+    TvSeries,
+    TvSeason,
+    MoviePerson,
+}
+
+impl Default for AccountKind {
+    fn default() -> Self {
+        Self::User
+    }
+}
+
+impl AccountKind {
+    /// Returns true if account represents a movie, a TV show
+    /// or a person from their credits
+    pub fn is_media(&self) -> bool {
+        *self != Self::User
+    }
+}
+
+impl From<&AccountKind> for i16 {
+    fn from(value: &AccountKind) -> i16 {
+        match value {
+            AccountKind::User => 0,
+            AccountKind::Movie => 1,
+            AccountKind::TvSeries => 2,
+            AccountKind::TvSeason => 3,
+            AccountKind::MoviePerson => 4,
+        }
+    }
+}
+
+impl TryFrom<i16> for AccountKind {
+    type Error = DatabaseTypeError;
+
+    fn try_from(value: i16) -> Result<Self, Self::Error> {
+        let account_kind = match value {
+            0 => Self::User,
+            1 => Self::Movie,
+            2 => Self::TvSeries,
+            3 => Self::TvSeason,
+            4 => Self::MoviePerson,
+            _ => return Err(DatabaseTypeError),
+        };
+        Ok(account_kind)
+    }
+}
+
+int_enum_from_sql!(AccountKind);
+int_enum_to_sql!(AccountKind);
+
 #[derive(Clone, FromSql)]
 #[postgres(name = "actor_profile")]
 pub struct DbActorProfile {
@@ -385,6 +474,9 @@ pub struct DbActorProfile {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub unreachable_since: Option<DateTime<Utc>>,
+    pub rating_stats: RatingStats,
+    pub account_kind: AccountKind,
+    pub tmdb_id: Option<i32>,
 
     // auto-generated database fields
     pub acct: String,
@@ -411,6 +503,10 @@ impl DbActorProfile {
         self.actor_json.is_none()
     }
 
+    pub fn is_media(&self) -> bool {
+        self.account_kind.is_media()
+    }
+
     pub fn possibly_outdated(&self) -> bool {
         if self.is_local() {
             false
@@ -449,6 +545,9 @@ impl Default for DbActorProfile {
             created_at: now,
             updated_at: now,
             unreachable_since: None,
+            rating_stats: RatingStats::default(),
+            account_kind: AccountKind::User,
+            tmdb_id: None,
         }
     }
 }
@@ -583,4 +682,15 @@ mod tests {
         assert_eq!(actor_ids.len(), 1);
         assert_eq!(actor_ids[0], actor_id);
     }
+
+    #[test]
+    fn test_rating_stats() {
+        let mut rating_stats: RatingStats = serde_json::from_str("{}").unwrap();
+        assert_eq!(rating_stats.review_count, 0);
+        assert_eq!(rating_stats.average_rating(), None);
+        rating_stats.histogram[1] = 1; // 1.0
+        rating_stats.histogram[8] = 2; // 4.5
+        assert_eq!(rating_stats.rating_count(), 3);
+        assert_eq!(rating_stats.average_rating(), Some(10.0 / 3.0));
+    }
 }
@@ -3,6 +3,7 @@ use uuid::Uuid;
 use fedimovies_utils::id::generate_ulid;
 
 use crate::database::{catch_unique_violation, DatabaseClient, DatabaseError};
+use crate::lists::queries::remove_from_owner_lists;
 use crate::notifications::queries::create_follow_notification;
 use crate::profiles::{
     queries::{update_follower_count, update_following_count, update_subscriber_count},
@@ -119,6 +120,8 @@ pub async fn unfollow(
     if !relationship_deleted && follow_request_deleted.is_none() {
         return Err(DatabaseError::NotFound("relationship"));
     };
+    // Lists can contain only followed accounts
+    remove_from_owner_lists(&transaction, source_id, target_id).await?;
     if relationship_deleted {
         // Also reset repost and reply visibility settings
         show_reposts(&transaction, source_id, target_id).await?;
@@ -131,20 +134,24 @@ pub async fn unfollow(
     Ok(follow_request_deleted)
 }
 
-// Follow remote actor
+// Follow remote actor or local actor that approves followers manually
 pub async fn create_follow_request(
     db_client: &impl DatabaseClient,
     source_id: &Uuid,
     target_id: &Uuid,
 ) -> Result<DbFollowRequest, DatabaseError> {
     let request_id = generate_ulid();
-    let row = db_client
-        .query_one(
+    // Rejected request can be sent again
+    let maybe_row = db_client
+        .query_opt(
             "
         INSERT INTO follow_request (
             id, source_id, target_id, request_status
         )
         VALUES ($1, $2, $3, $4)
+        ON CONFLICT (source_id, target_id)
+        DO UPDATE SET request_status = $4
+        WHERE follow_request.request_status = $5
         RETURNING follow_request
         ",
             &[
@@ -152,10 +159,11 @@ pub async fn create_follow_request(
                 &source_id,
                 &target_id,
                 &FollowRequestStatus::Pending,
+                &FollowRequestStatus::Rejected,
             ],
         )
-        .await
-        .map_err(catch_unique_violation("follow request"))?;
+        .await?;
+    let row = maybe_row.ok_or(DatabaseError::AlreadyExists("follow request"))?;
     let request = row.try_get("follow_request")?;
     Ok(request)
 }
@@ -180,7 +188,12 @@ pub async fn create_remote_follow_request_opt(
         )
         VALUES ($1, $2, $3, $4, $5)
         ON CONFLICT (source_id, target_id)
-        DO UPDATE SET activity_id = $4
+        DO UPDATE SET
+            activity_id = $4,
+            request_status = CASE
+                WHEN follow_request.request_status = $6 THEN $5
+                ELSE follow_request.request_status
+            END
         RETURNING follow_request
         ",
             &[
@@ -189,6 +202,7 @@ pub async fn create_remote_follow_request_opt(
                 &target_id,
                 &activity_id,
                 &FollowRequestStatus::Pending,
+                &FollowRequestStatus::Rejected,
             ],
         )
         .await?;
@@ -302,6 +316,53 @@ pub async fn get_follow_request_by_activity_id(
     Ok(request)
 }
 
+pub async fn get_follow_request_by_participants(
+    db_client: &impl DatabaseClient,
+    source_id: &Uuid,
+    target_id: &Uuid,
+) -> Result<DbFollowRequest, DatabaseError> {
+    let maybe_row = db_client
+        .query_opt(
+            "
+        SELECT follow_request
+        FROM follow_request
+        WHERE source_id = $1 AND target_id = $2
+        ",
+            &[&source_id, &target_id],
+        )
+        .await?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("follow request"))?;
+    let request = row.try_get("follow_request")?;
+    Ok(request)
+}
+
+/// Returns profiles with pending follow requests, newest first
+pub async fn get_follow_requests(
+    db_client: &impl DatabaseClient,
+    profile_id: &Uuid,
+) -> Result<Vec<DbActorProfile>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT actor_profile
+        FROM actor_profile
+        JOIN follow_request
+        ON (actor_profile.id = follow_request.source_id)
+        WHERE
+            follow_request.target_id = $1
+            AND follow_request.request_status = $2
+        ORDER BY follow_request.id DESC
+        ",
+            &[&profile_id, &FollowRequestStatus::Pending],
+        )
+        .await?;
+    let profiles = rows
+        .iter()
+        .map(|row| row.try_get("actor_profile"))
+        .collect::<Result<_, _>>()?;
+    Ok(profiles)
+}
+
 pub async fn get_followers(
     db_client: &impl DatabaseClient,
     profile_id: &Uuid,
@@ -672,10 +733,93 @@ pub async fn is_muted(
     Ok(rows.len() > 0)
 }
 
+/// Creates block relationship and removes follows in both directions.
+/// Returns ID of deleted follow request if source was following remote target.
+pub async fn block(
+    db_client: &mut impl DatabaseClient,
+    source_id: &Uuid,
+    target_id: &Uuid,
+) -> Result<Option<Uuid>, DatabaseError> {
+    let mut transaction = db_client.transaction().await?;
+    transaction
+        .execute(
+            "
+        INSERT INTO relationship (source_id, target_id, relationship_type)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (source_id, target_id, relationship_type) DO NOTHING
+        ",
+            &[&source_id, &target_id, &RelationshipType::Block],
+        )
+        .await?;
+    let follow_request_deleted = match unfollow(&mut transaction, source_id, target_id).await {
+        Ok(maybe_follow_request_id) => maybe_follow_request_id,
+        Err(DatabaseError::NotFound(_)) => None, // not following
+        Err(other_error) => return Err(other_error),
+    };
+    match unfollow(&mut transaction, target_id, source_id).await {
+        Ok(_) => (),
+        Err(DatabaseError::NotFound(_)) => (), // not followed
+        Err(other_error) => return Err(other_error),
+    };
+    remove_from_owner_lists(&transaction, source_id, target_id).await?;
+    remove_from_owner_lists(&transaction, target_id, source_id).await?;
+    transaction.commit().await?;
+    Ok(follow_request_deleted)
+}
+
+pub async fn unblock(
+    db_client: &impl DatabaseClient,
+    source_id: &Uuid,
+    target_id: &Uuid,
+) -> Result<(), DatabaseError> {
+    let deleted_count = db_client
+        .execute(
+            "
+        DELETE FROM relationship
+        WHERE
+            source_id = $1 AND target_id = $2
+            AND relationship_type = $3
+        ",
+            &[&source_id, &target_id, &RelationshipType::Block],
+        )
+        .await?;
+    if deleted_count == 0 {
+        return Err(DatabaseError::NotFound("relationship"));
+    };
+    Ok(())
+}
+
+/// Returns true if either of the profiles blocks the other one
+pub async fn has_block(
+    db_client: &impl DatabaseClient,
+    profile_id_1: &Uuid,
+    profile_id_2: &Uuid,
+) -> Result<bool, DatabaseError> {
+    let maybe_row = db_client
+        .query_opt(
+            "
+        SELECT 1
+        FROM relationship
+        WHERE
+            (
+                source_id = $1 AND target_id = $2
+                OR
+                source_id = $2 AND target_id = $1
+            )
+            AND relationship_type = $3
+        LIMIT 1
+        ",
+            &[&profile_id_1, &profile_id_2, &RelationshipType::Block],
+        )
+        .await?;
+    Ok(maybe_row.is_some())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::database::{test_utils::create_test_database, DatabaseError};
+    use crate::lists::queries::{add_list_members, create_list, get_list_members};
     use crate::profiles::{
         queries::create_profile,
         types::{DbActor, ProfileCreateData},
@@ -785,4 +929,132 @@ mod tests {
             .unwrap();
         assert_eq!(follow_request.request_status, FollowRequestStatus::Accepted);
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_follow_request_rejected() {
+        let db_client = &mut create_test_database().await;
+        let source_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let source = create_user(db_client, source_data).await.unwrap();
+        let target_data = UserCreateData {
+            username: "locked".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let target = create_user(db_client, target_data).await.unwrap();
+        let follow_request = create_follow_request(db_client, &source.id, &target.id)
+            .await
+            .unwrap();
+        let error = create_follow_request(db_client, &source.id, &target.id)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            DatabaseError::AlreadyExists("follow request")
+        ));
+        let requests = get_follow_requests(db_client, &target.id).await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].id, source.id);
+
+        follow_request_rejected(db_client, &follow_request.id)
+            .await
+            .unwrap();
+        let requests = get_follow_requests(db_client, &target.id).await.unwrap();
+        assert!(requests.is_empty());
+        let followers = get_followers(db_client, &target.id).await.unwrap();
+        assert!(followers.is_empty());
+
+        // Request can be sent again after rejection
+        let follow_request = create_follow_request(db_client, &source.id, &target.id)
+            .await
+            .unwrap();
+        assert_eq!(follow_request.request_status, FollowRequestStatus::Pending);
+        let follow_request = get_follow_request_by_participants(db_client, &source.id, &target.id)
+            .await
+            .unwrap();
+        assert_eq!(follow_request.request_status, FollowRequestStatus::Pending);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_unfollow_removes_list_membership() {
+        let db_client = &mut create_test_database().await;
+        let source_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let source = create_user(db_client, source_data).await.unwrap();
+        let target_data = UserCreateData {
+            username: "followed".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let target = create_user(db_client, target_data).await.unwrap();
+        follow(db_client, &source.id, &target.id).await.unwrap();
+        let list = create_list(db_client, &source.id, "Test").await.unwrap();
+        add_list_members(db_client, &list.id, &[target.id])
+            .await
+            .unwrap();
+
+        unfollow(db_client, &source.id, &target.id).await.unwrap();
+        let members = get_list_members(db_client, &list.id).await.unwrap();
+        assert!(members.is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_block() {
+        let db_client = &mut create_test_database().await;
+        let source_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let source = create_user(db_client, source_data).await.unwrap();
+        let target_data = UserCreateData {
+            username: "blocked".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let target = create_user(db_client, target_data).await.unwrap();
+        follow(db_client, &source.id, &target.id).await.unwrap();
+        follow(db_client, &target.id, &source.id).await.unwrap();
+        let source_list = create_list(db_client, &source.id, "Test").await.unwrap();
+        add_list_members(db_client, &source_list.id, &[target.id])
+            .await
+            .unwrap();
+        let target_list = create_list(db_client, &target.id, "Test").await.unwrap();
+        add_list_members(db_client, &target_list.id, &[source.id])
+            .await
+            .unwrap();
+
+        let follow_request_id = block(db_client, &source.id, &target.id).await.unwrap();
+        assert_eq!(follow_request_id, None);
+        assert!(has_block(db_client, &source.id, &target.id).await.unwrap());
+        assert!(has_block(db_client, &target.id, &source.id).await.unwrap());
+        // Follows are removed in both directions
+        let following = get_following(db_client, &source.id).await.unwrap();
+        assert!(following.is_empty());
+        let followers = get_followers(db_client, &source.id).await.unwrap();
+        assert!(followers.is_empty());
+        // Blocked accounts are removed from lists
+        let members = get_list_members(db_client, &source_list.id).await.unwrap();
+        assert!(members.is_empty());
+        let members = get_list_members(db_client, &target_list.id).await.unwrap();
+        assert!(members.is_empty());
+
+        unblock(db_client, &source.id, &target.id).await.unwrap();
+        assert!(!has_block(db_client, &source.id, &target.id).await.unwrap());
+        let error = unblock(db_client, &source.id, &target.id)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, DatabaseError::NotFound("relationship")));
+    }
 }
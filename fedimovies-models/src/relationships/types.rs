@@ -18,6 +18,7 @@ pub enum RelationshipType {
     HideReposts,
     HideReplies,
     Mute,
+    Block,
 }
 
 impl From<&RelationshipType> for i16 {
@@ -29,6 +30,7 @@ impl From<&RelationshipType> for i16 {
             RelationshipType::HideReposts => 4,
             RelationshipType::HideReplies => 5,
             RelationshipType::Mute => 6,
+            RelationshipType::Block => 7,
         }
     }
 }
@@ -44,6 +46,7 @@ impl TryFrom<i16> for RelationshipType {
             4 => Self::HideReposts,
             5 => Self::HideReplies,
             6 => Self::Mute,
+            7 => Self::Block,
             _ => return Err(DatabaseTypeError),
         };
         Ok(relationship_type)
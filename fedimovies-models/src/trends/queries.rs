@@ -0,0 +1,224 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::database::{DatabaseClient, DatabaseError};
+use crate::posts::types::Visibility;
+use crate::profiles::types::AccountKind;
+use crate::properties::queries::{get_internal_property, set_internal_property};
+
+use super::types::{TrendingMovie, TrendingTag, Trends};
+
+const TRENDS_PROPERTY_NAME: &str = "trends";
+
+/// Finds public posts with the most interactions,
+/// reposts made by movie accounts are not counted
+pub async fn find_trending_posts(
+    db_client: &impl DatabaseClient,
+    created_after: &DateTime<Utc>,
+    limit: u16,
+) -> Result<Vec<Uuid>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT post_id
+        FROM (
+            SELECT
+                post.id AS post_id,
+                post.reaction_count + post.reply_count + (
+                    SELECT count(*)
+                    FROM post AS repost
+                    JOIN actor_profile AS reposter
+                    ON repost.author_id = reposter.id
+                    WHERE
+                        repost.repost_of_id = post.id
+                        AND reposter.account_kind = $2
+                ) AS score
+            FROM post
+            WHERE
+                post.visibility = $1
+                AND post.repost_of_id IS NULL
+                AND post.created_at > $3
+        ) AS post_score
+        WHERE score > 0
+        ORDER BY score DESC, post_id DESC
+        LIMIT $4
+        ",
+            &[
+                &Visibility::Public,
+                &AccountKind::User,
+                &created_after,
+                &i64::from(limit),
+            ],
+        )
+        .await?;
+    let posts_ids = rows
+        .iter()
+        .map(|row| row.try_get("post_id"))
+        .collect::<Result<_, _>>()?;
+    Ok(posts_ids)
+}
+
+/// Finds hashtags used by the largest number of accounts
+pub async fn find_trending_tags(
+    db_client: &impl DatabaseClient,
+    created_after: &DateTime<Utc>,
+    limit: u16,
+) -> Result<Vec<TrendingTag>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT
+            tag.tag_name,
+            count(post.id) AS uses,
+            count(DISTINCT post.author_id) AS accounts
+        FROM post_tag
+        JOIN tag ON post_tag.tag_id = tag.id
+        JOIN post ON post_tag.post_id = post.id
+        WHERE post.visibility = $1 AND post.created_at > $2
+        GROUP BY tag.tag_name
+        ORDER BY accounts DESC, uses DESC, tag.tag_name
+        LIMIT $3
+        ",
+            &[&Visibility::Public, &created_after, &i64::from(limit)],
+        )
+        .await?;
+    let mut tags = vec![];
+    for row in rows {
+        let tag = TrendingTag {
+            tag_name: row.try_get("tag_name")?,
+            uses: row.try_get("uses")?,
+            accounts: row.try_get("accounts")?,
+        };
+        tags.push(tag);
+    }
+    Ok(tags)
+}
+
+/// Finds movies and TV shows mentioned by the largest number of accounts
+pub async fn find_trending_movies(
+    db_client: &impl DatabaseClient,
+    created_after: &DateTime<Utc>,
+    limit: u16,
+) -> Result<Vec<TrendingMovie>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT
+            mention.profile_id,
+            count(DISTINCT post.author_id) AS review_count
+        FROM mention
+        JOIN post ON mention.post_id = post.id
+        JOIN actor_profile ON mention.profile_id = actor_profile.id
+        WHERE
+            actor_profile.account_kind NOT IN ($1, $2)
+            AND post.visibility = $3
+            AND post.repost_of_id IS NULL
+            AND post.created_at > $4
+        GROUP BY mention.profile_id
+        ORDER BY review_count DESC, mention.profile_id
+        LIMIT $5
+        ",
+            &[
+                &AccountKind::User,
+                &AccountKind::MoviePerson,
+                &Visibility::Public,
+                &created_after,
+                &i64::from(limit),
+            ],
+        )
+        .await?;
+    let mut movies = vec![];
+    for row in rows {
+        let movie = TrendingMovie {
+            profile_id: row.try_get("profile_id")?,
+            review_count: row.try_get("review_count")?,
+        };
+        movies.push(movie);
+    }
+    Ok(movies)
+}
+
+/// Computes trends and saves them to cache
+pub async fn update_trends(
+    db_client: &impl DatabaseClient,
+    created_after: &DateTime<Utc>,
+    limit: u16,
+) -> Result<Trends, DatabaseError> {
+    let trends = Trends {
+        posts: find_trending_posts(db_client, created_after, limit).await?,
+        tags: find_trending_tags(db_client, created_after, limit).await?,
+        movies: find_trending_movies(db_client, created_after, limit).await?,
+    };
+    set_internal_property(db_client, TRENDS_PROPERTY_NAME, &trends).await?;
+    Ok(trends)
+}
+
+/// Returns cached trends
+pub async fn get_trends(db_client: &impl DatabaseClient) -> Result<Trends, DatabaseError> {
+    let maybe_trends = get_internal_property(db_client, TRENDS_PROPERTY_NAME).await?;
+    Ok(maybe_trends.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::create_test_database;
+    use crate::posts::{queries::create_post, types::PostCreateData};
+    use crate::reactions::queries::create_reaction;
+    use crate::users::{queries::create_user, types::UserCreateData};
+    use chrono::Duration;
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_update_trends() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Dune_2021_438631".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(438631),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+        let post_data = PostCreateData {
+            content: "review".to_string(),
+            mentions: vec![movie.id],
+            tags: vec!["scifi".to_string()],
+            created_at: Utc::now(),
+            ..Default::default()
+        };
+        let post = create_post(db_client, &user.id, post_data).await.unwrap();
+        // Old posts are not counted
+        let post_data = PostCreateData {
+            content: "old review".to_string(),
+            mentions: vec![movie.id],
+            tags: vec!["old".to_string()],
+            created_at: Utc::now() - Duration::days(30),
+            ..Default::default()
+        };
+        create_post(db_client, &user.id, post_data).await.unwrap();
+
+        let created_after = Utc::now() - Duration::days(7);
+        let trends = update_trends(db_client, &created_after, 10).await.unwrap();
+        // Post without interactions is not trending
+        assert!(trends.posts.is_empty());
+        assert_eq!(trends.tags.len(), 1);
+        assert_eq!(trends.tags[0].tag_name, "scifi");
+        assert_eq!(trends.tags[0].accounts, 1);
+        assert_eq!(trends.movies.len(), 1);
+        assert_eq!(trends.movies[0].profile_id, movie.id);
+        assert_eq!(trends.movies[0].review_count, 1);
+
+        create_reaction(db_client, &movie.id, &post.id, None)
+            .await
+            .unwrap();
+        update_trends(db_client, &created_after, 10).await.unwrap();
+        let trends = get_trends(db_client).await.unwrap();
+        assert_eq!(trends.posts, vec![post.id]);
+    }
+}
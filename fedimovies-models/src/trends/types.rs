@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize)]
+pub struct TrendingTag {
+    pub tag_name: String,
+    pub uses: i64,
+    pub accounts: i64,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct TrendingMovie {
+    pub profile_id: Uuid,
+    pub review_count: i64,
+}
+
+/// Cached trends
+#[derive(Default, Deserialize, Serialize)]
+pub struct Trends {
+    pub posts: Vec<Uuid>,
+    pub tags: Vec<TrendingTag>,
+    pub movies: Vec<TrendingMovie>,
+}
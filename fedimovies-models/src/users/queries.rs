@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
@@ -6,7 +7,7 @@ use fedimovies_utils::{currencies::Currency, did::Did, did_pkh::DidPkh};
 use crate::database::{catch_unique_violation, DatabaseClient, DatabaseError};
 use crate::profiles::{
     queries::create_profile,
-    types::{DbActorProfile, ProfileCreateData},
+    types::{AccountKind, DbActorProfile, ProfileCreateData},
 };
 
 use super::types::{
@@ -71,7 +72,12 @@ pub async fn create_user(
     db_client: &mut impl DatabaseClient,
     user_data: UserCreateData,
 ) -> Result<User, DatabaseError> {
-    assert!(user_data.password_hash.is_some() || user_data.wallet_address.is_some());
+    // Movie accounts don't have credentials
+    assert!(
+        user_data.password_hash.is_some()
+            || user_data.wallet_address.is_some()
+            || user_data.account_kind != AccountKind::User
+    );
     let mut transaction = db_client.transaction().await?;
     // Prevent changes to actor_profile table
     transaction
@@ -124,7 +130,21 @@ pub async fn create_user(
         emojis: vec![],
         actor_json: None,
     };
-    let profile = create_profile(&mut transaction, profile_data).await?;
+    let mut profile = create_profile(&mut transaction, profile_data).await?;
+    if user_data.account_kind != AccountKind::User {
+        let row = transaction
+            .query_one(
+                "
+            UPDATE actor_profile
+            SET account_kind = $2, tmdb_id = $3
+            WHERE id = $1
+            RETURNING actor_profile
+            ",
+                &[&profile.id, &user_data.account_kind, &user_data.tmdb_id],
+            )
+            .await?;
+        profile = row.try_get("actor_profile")?;
+    };
     // Create user
     let row = transaction
         .query_one(
@@ -177,6 +197,26 @@ pub async fn set_user_password(
     Ok(())
 }
 
+pub async fn set_watchlist_visibility(
+    db_client: &impl DatabaseClient,
+    user_id: &Uuid,
+    show_watchlist: bool,
+) -> Result<(), DatabaseError> {
+    let updated_count = db_client
+        .execute(
+            "
+        UPDATE user_account SET show_watchlist = $1
+        WHERE id = $2
+        ",
+            &[&show_watchlist, &user_id],
+        )
+        .await?;
+    if updated_count == 0 {
+        return Err(DatabaseError::NotFound("user"));
+    };
+    Ok(())
+}
+
 pub async fn set_user_role(
     db_client: &impl DatabaseClient,
     user_id: &Uuid,
@@ -261,6 +301,119 @@ pub async fn get_user_by_name(
     Ok(user)
 }
 
+/// Returns users whose usernames consist of the given prefix
+/// and a numeric suffix (e.g. `name_123` for the prefix `name`)
+pub async fn get_users_by_name_prefix(
+    db_client: &impl DatabaseClient,
+    prefix: &str,
+) -> Result<Vec<User>, DatabaseError> {
+    let pattern = format!("{}\\_%", prefix.replace('_', "\\_"));
+    let rows = db_client
+        .query(
+            "
+        SELECT user_account, actor_profile
+        FROM user_account JOIN actor_profile USING (id)
+        WHERE lower(actor_profile.username) LIKE lower($1)
+        ORDER BY actor_profile.username
+        ",
+            &[&pattern],
+        )
+        .await?;
+    let mut users = vec![];
+    for row in rows {
+        let db_user: DbUser = row.try_get("user_account")?;
+        let db_profile: DbActorProfile = row.try_get("actor_profile")?;
+        let suffix = db_profile
+            .username
+            .get(prefix.len() + 1..)
+            .unwrap_or_default();
+        if suffix.is_empty() || !suffix.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        };
+        users.push(User::new(db_user, db_profile));
+    }
+    Ok(users)
+}
+
+pub async fn get_user_by_tmdb_id(
+    db_client: &impl DatabaseClient,
+    account_kind: &AccountKind,
+    tmdb_id: i32,
+) -> Result<User, DatabaseError> {
+    let maybe_row = db_client
+        .query_opt(
+            "
+        SELECT user_account, actor_profile
+        FROM user_account JOIN actor_profile USING (id)
+        WHERE actor_profile.account_kind = $1 AND actor_profile.tmdb_id = $2
+        ",
+            &[&account_kind, &tmdb_id],
+        )
+        .await?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("user"))?;
+    let db_user: DbUser = row.try_get("user_account")?;
+    let db_profile: DbActorProfile = row.try_get("actor_profile")?;
+    let user = User::new(db_user, db_profile);
+    Ok(user)
+}
+
+pub async fn get_users_by_account_kind(
+    db_client: &impl DatabaseClient,
+    account_kind: &AccountKind,
+) -> Result<Vec<User>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT user_account, actor_profile
+        FROM user_account JOIN actor_profile USING (id)
+        WHERE actor_profile.account_kind = $1
+        ORDER BY actor_profile.username
+        ",
+            &[&account_kind],
+        )
+        .await?;
+    let users = rows
+        .iter()
+        .map(|row| {
+            let db_user: DbUser = row.try_get("user_account")?;
+            let db_profile: DbActorProfile = row.try_get("actor_profile")?;
+            Ok(User::new(db_user, db_profile))
+        })
+        .collect::<Result<_, DatabaseError>>()?;
+    Ok(users)
+}
+
+/// Returns movie and TV show accounts that were not updated since the given time
+pub async fn find_outdated_media_users(
+    db_client: &impl DatabaseClient,
+    updated_before: &DateTime<Utc>,
+    limit: u16,
+) -> Result<Vec<User>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT user_account, actor_profile
+        FROM user_account JOIN actor_profile USING (id)
+        WHERE
+            actor_profile.account_kind != $1
+            AND actor_profile.updated_at < $2
+        ORDER BY actor_profile.updated_at
+        LIMIT $3
+        ",
+            &[&AccountKind::User, &updated_before, &i64::from(limit)],
+        )
+        .await?;
+    let users = rows
+        .iter()
+        .map(|row| {
+            let db_user: DbUser = row.try_get("user_account")?;
+            let db_profile: DbActorProfile = row.try_get("actor_profile")?;
+            Ok(User::new(db_user, db_profile))
+        })
+        .collect::<Result<_, DatabaseError>>()?;
+    Ok(users)
+}
+
 pub async fn is_registered_user(
     db_client: &impl DatabaseClient,
     username: &str,
@@ -335,9 +488,17 @@ pub async fn get_user_by_public_wallet_address(
     get_user_by_did(db_client, &did).await
 }
 
+/// Returns the number of local users, excluding movie accounts
 pub async fn get_user_count(db_client: &impl DatabaseClient) -> Result<i64, DatabaseError> {
     let row = db_client
-        .query_one("SELECT count(user_account) FROM user_account", &[])
+        .query_one(
+            "
+        SELECT count(user_account)
+        FROM user_account JOIN actor_profile USING (id)
+        WHERE actor_profile.account_kind = $1
+        ",
+            &[&AccountKind::User],
+        )
         .await?;
     let count = row.try_get("count")?;
     Ok(count)
@@ -432,4 +593,108 @@ mod tests {
             &client_config_value,
         );
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_users_by_name_prefix() {
+        let db_client = &mut create_test_database().await;
+        for username in [
+            "Movie_2022",
+            "Movie_2022_101",
+            "Movie_2022_102",
+            "Movie_2022_x1",
+        ] {
+            let user_data = UserCreateData {
+                username: username.to_string(),
+                password_hash: Some("test".to_string()),
+                ..Default::default()
+            };
+            create_user(db_client, user_data).await.unwrap();
+        }
+        let users = get_users_by_name_prefix(db_client, "Movie_2022")
+            .await
+            .unwrap();
+        let usernames: Vec<_> = users
+            .into_iter()
+            .map(|user| user.profile.username)
+            .collect();
+        assert_eq!(usernames, vec!["Movie_2022_101", "Movie_2022_102"]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_movie_user() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        create_user(db_client, user_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Movie_2022_101".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(101),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+        assert_eq!(movie.password_hash, None);
+        assert_eq!(movie.profile.account_kind, AccountKind::Movie);
+        assert_eq!(movie.profile.tmdb_id, Some(101));
+
+        let user = get_user_by_tmdb_id(db_client, &AccountKind::Movie, 101)
+            .await
+            .unwrap();
+        assert_eq!(user.id, movie.id);
+        let user_count = get_user_count(db_client).await.unwrap();
+        assert_eq!(user_count, 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_find_outdated_media_users() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        create_user(db_client, user_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Movie_2022_101".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(101),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+        let users = find_outdated_media_users(db_client, &Utc::now(), 10)
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id, movie.id);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_users_by_account_kind() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        create_user(db_client, user_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Movie_2022_101".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(101),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+        let users = get_users_by_account_kind(db_client, &AccountKind::Movie)
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id, movie.id);
+    }
 }
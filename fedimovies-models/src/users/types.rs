@@ -13,7 +13,7 @@ use crate::database::{
     json_macro::json_from_sql,
     DatabaseTypeError,
 };
-use crate::profiles::types::DbActorProfile;
+use crate::profiles::types::{AccountKind, DbActorProfile};
 
 #[allow(dead_code)]
 #[derive(FromSql)]
@@ -127,6 +127,7 @@ pub struct DbUser {
     invite_code: Option<String>,
     user_role: Role,
     client_config: DbClientConfig,
+    show_watchlist: bool,
     created_at: DateTime<Utc>,
 }
 
@@ -140,6 +141,7 @@ pub struct User {
     pub private_key: String,
     pub role: Role,
     pub client_config: ClientConfig,
+    pub show_watchlist: bool,
     pub profile: DbActorProfile,
 }
 
@@ -153,6 +155,7 @@ impl User {
             private_key: db_user.private_key,
             role: db_user.user_role,
             client_config: db_user.client_config.into_inner(),
+            show_watchlist: db_user.show_watchlist,
             profile: db_profile,
         }
     }
@@ -184,6 +187,8 @@ pub struct UserCreateData {
     pub wallet_address: Option<String>,
     pub invite_code: Option<String>,
     pub role: Role,
+    pub account_kind: AccountKind,
+    pub tmdb_id: Option<i32>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,389 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use fedimovies_utils::id::generate_ulid;
+
+use crate::database::{DatabaseClient, DatabaseError};
+use crate::profiles::types::AccountKind;
+use crate::relationships::types::RelationshipType;
+
+use super::types::{DbWatchLogEntry, WatchLogEntry, WatchLogEntryCreateData};
+
+pub async fn create_watch_log_entry(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    entry_data: WatchLogEntryCreateData,
+) -> Result<DbWatchLogEntry, DatabaseError> {
+    let entry_id = generate_ulid();
+    // Only movies and TV shows can be logged
+    let maybe_row = db_client
+        .query_opt(
+            "
+        INSERT INTO watch_log_entry (
+            id,
+            owner_id,
+            movie_id,
+            watched_on,
+            is_rewatch,
+            rating,
+            is_federated
+        )
+        SELECT $1, $2, $3, $4, $5, $6, $7
+        WHERE EXISTS (
+            SELECT 1 FROM actor_profile
+            WHERE actor_profile.id = $3
+                AND actor_profile.account_kind NOT IN ($8, $9)
+        )
+        RETURNING watch_log_entry
+        ",
+            &[
+                &entry_id,
+                &owner_id,
+                &entry_data.movie_id,
+                &entry_data.watched_on,
+                &entry_data.is_rewatch,
+                &entry_data.rating,
+                &entry_data.is_federated,
+                &AccountKind::User,
+                &AccountKind::MoviePerson,
+            ],
+        )
+        .await?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("movie"))?;
+    let entry = row.try_get("watch_log_entry")?;
+    Ok(entry)
+}
+
+pub async fn get_watch_log_entry(
+    db_client: &impl DatabaseClient,
+    entry_id: &Uuid,
+) -> Result<WatchLogEntry, DatabaseError> {
+    let maybe_row = db_client
+        .query_opt(
+            "
+        SELECT watch_log_entry, owner, movie
+        FROM watch_log_entry
+        JOIN actor_profile AS owner
+        ON watch_log_entry.owner_id = owner.id
+        JOIN actor_profile AS movie
+        ON watch_log_entry.movie_id = movie.id
+        WHERE watch_log_entry.id = $1
+        ",
+            &[&entry_id],
+        )
+        .await?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("watch log entry"))?;
+    let entry = WatchLogEntry::try_from(&row)?;
+    Ok(entry)
+}
+
+async fn get_watch_log(
+    db_client: &impl DatabaseClient,
+    condition: &str,
+    profile_id: &Uuid,
+    current_user_id: Option<&Uuid>,
+    max_entry_id: Option<Uuid>,
+    limit: Option<u16>,
+) -> Result<Vec<WatchLogEntry>, DatabaseError> {
+    // Entries are ordered by watch date,
+    // pagination uses position of the entry with max_entry_id.
+    // Other users can see only federated entries,
+    // and only if there is no block between them and the owner.
+    let statement = format!(
+        "
+        SELECT watch_log_entry, owner, movie
+        FROM watch_log_entry
+        JOIN actor_profile AS owner
+        ON watch_log_entry.owner_id = owner.id
+        JOIN actor_profile AS movie
+        ON watch_log_entry.movie_id = movie.id
+        WHERE {condition}
+            AND (
+                watch_log_entry.owner_id = $4
+                OR watch_log_entry.is_federated AND NOT EXISTS (
+                    SELECT 1 FROM relationship
+                    WHERE
+                        relationship_type = {relationship_block}
+                        AND (
+                            source_id = $4 AND target_id = watch_log_entry.owner_id
+                            OR source_id = watch_log_entry.owner_id AND target_id = $4
+                        )
+                )
+            )
+            AND (
+                $2::uuid IS NULL
+                OR (watch_log_entry.watched_on, watch_log_entry.id) < (
+                    SELECT watched_on, id FROM watch_log_entry WHERE id = $2
+                )
+            )
+        ORDER BY watch_log_entry.watched_on DESC, watch_log_entry.id DESC
+        LIMIT $3
+        ",
+        condition = condition,
+        relationship_block = i16::from(&RelationshipType::Block),
+    );
+    let limit = limit.map(i64::from);
+    let rows = db_client
+        .query(
+            &statement,
+            &[&profile_id, &max_entry_id, &limit, &current_user_id],
+        )
+        .await?;
+    let entries = rows
+        .iter()
+        .map(WatchLogEntry::try_from)
+        .collect::<Result<_, _>>()?;
+    Ok(entries)
+}
+
+/// Returns entries created by the user, latest first.
+/// All entries are returned if limit is not set.
+pub async fn get_watch_log_by_owner(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    current_user_id: Option<&Uuid>,
+    max_entry_id: Option<Uuid>,
+    limit: Option<u16>,
+) -> Result<Vec<WatchLogEntry>, DatabaseError> {
+    get_watch_log(
+        db_client,
+        "watch_log_entry.owner_id = $1",
+        owner_id,
+        current_user_id,
+        max_entry_id,
+        limit,
+    )
+    .await
+}
+
+/// Returns entries about the movie, latest first
+pub async fn get_watch_log_by_movie(
+    db_client: &impl DatabaseClient,
+    movie_id: &Uuid,
+    current_user_id: Option<&Uuid>,
+    max_entry_id: Option<Uuid>,
+    limit: u16,
+) -> Result<Vec<WatchLogEntry>, DatabaseError> {
+    get_watch_log(
+        db_client,
+        "watch_log_entry.movie_id = $1",
+        movie_id,
+        current_user_id,
+        max_entry_id,
+        Some(limit),
+    )
+    .await
+}
+
+pub async fn has_watch_log_entry(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    movie_id: &Uuid,
+    watched_on: &NaiveDate,
+) -> Result<bool, DatabaseError> {
+    let row = db_client
+        .query_one(
+            "
+        SELECT EXISTS (
+            SELECT 1
+            FROM watch_log_entry
+            WHERE owner_id = $1 AND movie_id = $2 AND watched_on = $3
+        ) AS has_entry
+        ",
+            &[&owner_id, &movie_id, &watched_on],
+        )
+        .await?;
+    let has_entry = row.try_get("has_entry")?;
+    Ok(has_entry)
+}
+
+pub async fn delete_watch_log_entry(
+    db_client: &impl DatabaseClient,
+    entry_id: &Uuid,
+    owner_id: &Uuid,
+) -> Result<(), DatabaseError> {
+    let deleted_count = db_client
+        .execute(
+            "
+        DELETE FROM watch_log_entry
+        WHERE id = $1 AND owner_id = $2
+        ",
+            &[&entry_id, &owner_id],
+        )
+        .await?;
+    if deleted_count == 0 {
+        return Err(DatabaseError::NotFound("watch log entry"));
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::create_test_database;
+    use crate::relationships::queries::block;
+    use crate::users::{queries::create_user, types::UserCreateData};
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_and_delete_watch_log_entry() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Movie_2022_101".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(101),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+
+        let entry_data = WatchLogEntryCreateData {
+            movie_id: movie.id,
+            watched_on: NaiveDate::from_ymd_opt(2023, 1, 10).unwrap(),
+            is_rewatch: false,
+            rating: Some(4.5),
+            is_federated: false,
+        };
+        let entry_1 = create_watch_log_entry(db_client, &user.id, entry_data)
+            .await
+            .unwrap();
+        assert_eq!(entry_1.owner_id, user.id);
+        assert_eq!(entry_1.movie_id, movie.id);
+        let entry_data = WatchLogEntryCreateData {
+            movie_id: movie.id,
+            watched_on: NaiveDate::from_ymd_opt(2023, 2, 1).unwrap(),
+            is_rewatch: true,
+            rating: None,
+            is_federated: false,
+        };
+        let entry_2 = create_watch_log_entry(db_client, &user.id, entry_data)
+            .await
+            .unwrap();
+
+        let entries = get_watch_log_by_owner(db_client, &user.id, Some(&user.id), None, None)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, entry_2.id);
+        assert_eq!(entries[0].movie.id, movie.id);
+        assert_eq!(entries[1].id, entry_1.id);
+        let entries =
+            get_watch_log_by_movie(db_client, &movie.id, Some(&user.id), Some(entry_2.id), 10)
+                .await
+                .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, entry_1.id);
+        assert_eq!(entries[0].rating, Some(4.5));
+        let has_entry = has_watch_log_entry(
+            db_client,
+            &user.id,
+            &movie.id,
+            &NaiveDate::from_ymd_opt(2023, 1, 10).unwrap(),
+        )
+        .await
+        .unwrap();
+        assert!(has_entry);
+
+        delete_watch_log_entry(db_client, &entry_1.id, &user.id)
+            .await
+            .unwrap();
+        let error = get_watch_log_entry(db_client, &entry_1.id)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, DatabaseError::NotFound("watch log entry")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_watch_log_entry_not_movie() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let entry_data = WatchLogEntryCreateData {
+            movie_id: user.id,
+            watched_on: NaiveDate::from_ymd_opt(2023, 1, 10).unwrap(),
+            is_rewatch: false,
+            rating: None,
+            is_federated: false,
+        };
+        let error = create_watch_log_entry(db_client, &user.id, entry_data)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, DatabaseError::NotFound("movie")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_watch_log_visibility() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let viewer_data = UserCreateData {
+            username: "viewer".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let viewer = create_user(db_client, viewer_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Movie_2022_101".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(101),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+        for is_federated in [false, true] {
+            let entry_data = WatchLogEntryCreateData {
+                movie_id: movie.id,
+                watched_on: NaiveDate::from_ymd_opt(2023, 1, 10).unwrap(),
+                is_rewatch: false,
+                rating: None,
+                is_federated,
+            };
+            create_watch_log_entry(db_client, &user.id, entry_data)
+                .await
+                .unwrap();
+        }
+
+        let entries = get_watch_log_by_owner(db_client, &user.id, Some(&user.id), None, None)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        // Non-owners see only federated entries
+        let entries = get_watch_log_by_owner(db_client, &user.id, Some(&viewer.id), None, None)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].is_federated);
+        let entries = get_watch_log_by_movie(db_client, &movie.id, None, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+
+        // Blocked viewers don't see any entries
+        block(db_client, &user.id, &viewer.id).await.unwrap();
+        let entries = get_watch_log_by_owner(db_client, &user.id, Some(&viewer.id), None, None)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 0);
+        let entries = get_watch_log_by_movie(db_client, &movie.id, Some(&viewer.id), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 0);
+    }
+}
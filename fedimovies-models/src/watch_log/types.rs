@@ -0,0 +1,60 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use postgres_types::FromSql;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use crate::database::DatabaseError;
+use crate::profiles::types::DbActorProfile;
+
+#[derive(FromSql)]
+#[postgres(name = "watch_log_entry")]
+pub struct DbWatchLogEntry {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub movie_id: Uuid,
+    pub watched_on: NaiveDate,
+    pub is_rewatch: bool,
+    pub rating: Option<f32>,
+    pub is_federated: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct WatchLogEntryCreateData {
+    pub movie_id: Uuid,
+    pub watched_on: NaiveDate,
+    pub is_rewatch: bool,
+    pub rating: Option<f32>,
+    pub is_federated: bool,
+}
+
+pub struct WatchLogEntry {
+    pub id: Uuid,
+    pub owner: DbActorProfile,
+    pub movie: DbActorProfile,
+    pub watched_on: NaiveDate,
+    pub is_rewatch: bool,
+    pub rating: Option<f32>,
+    pub is_federated: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<&Row> for WatchLogEntry {
+    type Error = DatabaseError;
+
+    fn try_from(row: &Row) -> Result<Self, Self::Error> {
+        let db_entry: DbWatchLogEntry = row.try_get("watch_log_entry")?;
+        let db_owner: DbActorProfile = row.try_get("owner")?;
+        let db_movie: DbActorProfile = row.try_get("movie")?;
+        let entry = Self {
+            id: db_entry.id,
+            owner: db_owner,
+            movie: db_movie,
+            watched_on: db_entry.watched_on,
+            is_rewatch: db_entry.is_rewatch,
+            rating: db_entry.rating,
+            is_federated: db_entry.is_federated,
+            created_at: db_entry.created_at,
+        };
+        Ok(entry)
+    }
+}
@@ -0,0 +1,170 @@
+use uuid::Uuid;
+
+use fedimovies_utils::id::generate_ulid;
+
+use crate::database::{catch_unique_violation, DatabaseClient, DatabaseError};
+use crate::profiles::types::AccountKind;
+
+use super::types::{DbWatchlistEntry, WatchlistEntry};
+
+pub async fn add_to_watchlist(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    movie_id: &Uuid,
+) -> Result<DbWatchlistEntry, DatabaseError> {
+    let entry_id = generate_ulid();
+    // Only movies and TV shows can be added
+    let maybe_row = db_client
+        .query_opt(
+            "
+        INSERT INTO watchlist_entry (id, owner_id, movie_id)
+        SELECT $1, $2, $3
+        WHERE EXISTS (
+            SELECT 1 FROM actor_profile
+            WHERE actor_profile.id = $3
+                AND actor_profile.account_kind NOT IN ($4, $5)
+        )
+        RETURNING watchlist_entry
+        ",
+            &[
+                &entry_id,
+                &owner_id,
+                &movie_id,
+                &AccountKind::User,
+                &AccountKind::MoviePerson,
+            ],
+        )
+        .await
+        .map_err(catch_unique_violation("watchlist entry"))?;
+    let row = maybe_row.ok_or(DatabaseError::NotFound("movie"))?;
+    let entry = row.try_get("watchlist_entry")?;
+    Ok(entry)
+}
+
+pub async fn remove_from_watchlist(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    movie_id: &Uuid,
+) -> Result<(), DatabaseError> {
+    let deleted_count = db_client
+        .execute(
+            "
+        DELETE FROM watchlist_entry
+        WHERE owner_id = $1 AND movie_id = $2
+        ",
+            &[&owner_id, &movie_id],
+        )
+        .await?;
+    if deleted_count == 0 {
+        return Err(DatabaseError::NotFound("watchlist entry"));
+    };
+    Ok(())
+}
+
+/// Returns movies from the watchlist, most recently added first
+pub async fn get_watchlist(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+    max_entry_id: Option<Uuid>,
+    limit: u16,
+) -> Result<Vec<WatchlistEntry>, DatabaseError> {
+    let rows = db_client
+        .query(
+            "
+        SELECT watchlist_entry, actor_profile
+        FROM watchlist_entry
+        JOIN actor_profile
+        ON watchlist_entry.movie_id = actor_profile.id
+        WHERE
+            watchlist_entry.owner_id = $1
+            AND ($2::uuid IS NULL OR watchlist_entry.id < $2)
+        ORDER BY watchlist_entry.id DESC
+        LIMIT $3
+        ",
+            &[&owner_id, &max_entry_id, &i64::from(limit)],
+        )
+        .await?;
+    let entries = rows
+        .iter()
+        .map(WatchlistEntry::try_from)
+        .collect::<Result<_, _>>()?;
+    Ok(entries)
+}
+
+pub async fn get_watchlist_size(
+    db_client: &impl DatabaseClient,
+    owner_id: &Uuid,
+) -> Result<i64, DatabaseError> {
+    let row = db_client
+        .query_one(
+            "
+        SELECT count(watchlist_entry)
+        FROM watchlist_entry
+        WHERE owner_id = $1
+        ",
+            &[&owner_id],
+        )
+        .await?;
+    let count = row.try_get("count")?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::create_test_database;
+    use crate::users::{queries::create_user, types::UserCreateData};
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_add_to_watchlist() {
+        let db_client = &mut create_test_database().await;
+        let user_data = UserCreateData {
+            username: "test".to_string(),
+            password_hash: Some("test".to_string()),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+        let movie_data = UserCreateData {
+            username: "Movie_2022_101".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(101),
+            ..Default::default()
+        };
+        let movie = create_user(db_client, movie_data).await.unwrap();
+
+        let entry = add_to_watchlist(db_client, &user.id, &movie.id)
+            .await
+            .unwrap();
+        assert_eq!(entry.owner_id, user.id);
+        assert_eq!(entry.movie_id, movie.id);
+        let error = add_to_watchlist(db_client, &user.id, &movie.id)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            DatabaseError::AlreadyExists("watchlist entry"),
+        ));
+        // Regular accounts can't be added
+        let error = add_to_watchlist(db_client, &movie.id, &user.id)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, DatabaseError::NotFound("movie")));
+
+        let entries = get_watchlist(db_client, &user.id, None, 10).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, entry.id);
+        assert_eq!(entries[0].movie.id, movie.id);
+        let size = get_watchlist_size(db_client, &user.id).await.unwrap();
+        assert_eq!(size, 1);
+
+        remove_from_watchlist(db_client, &user.id, &movie.id)
+            .await
+            .unwrap();
+        let entries = get_watchlist(db_client, &user.id, None, 10).await.unwrap();
+        assert!(entries.is_empty());
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use postgres_types::FromSql;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use crate::database::DatabaseError;
+use crate::profiles::types::DbActorProfile;
+
+#[derive(FromSql)]
+#[postgres(name = "watchlist_entry")]
+pub struct DbWatchlistEntry {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub movie_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct WatchlistEntry {
+    pub id: Uuid,
+    pub movie: DbActorProfile,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<&Row> for WatchlistEntry {
+    type Error = DatabaseError;
+
+    fn try_from(row: &Row) -> Result<Self, Self::Error> {
+        let db_entry: DbWatchlistEntry = row.try_get("watchlist_entry")?;
+        let db_movie: DbActorProfile = row.try_get("actor_profile")?;
+        let entry = Self {
+            id: db_entry.id,
+            movie: db_movie,
+            created_at: db_entry.created_at,
+        };
+        Ok(entry)
+    }
+}
@@ -47,6 +47,16 @@ pub fn clean_html_all(html: &str) -> String {
     text
 }
 
+/// Removes all tags and returns unescaped text
+pub fn html_to_text(html: &str) -> String {
+    // Serializer escapes only these characters
+    clean_html_all(html)
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", "\u{a0}")
+        .replace("&amp;", "&")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +98,11 @@ mod tests {
         let text = clean_html_all(html);
         assert_eq!(text, "test bold with link and code");
     }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = "<p>Tom &amp; Jerry &lt;3</p><script>x</script>";
+        let text = html_to_text(html);
+        assert_eq!(text, "Tom & Jerry <3");
+    }
 }
@@ -1,5 +1,5 @@
 use fedimovies_models::profiles::types::{
-    ExtraField, IdentityProof, IdentityProofType, PaymentLink, PaymentOption,
+    ExtraField, IdentityProof, IdentityProofType, PaymentLink, PaymentOption, RatingStats,
 };
 
 use crate::activitypub::vocabulary::{IDENTITY_PROOF, LINK, PROPERTY_VALUE};
@@ -105,6 +105,44 @@ pub fn parse_extra_field(attachment: &ActorAttachment) -> Result<ExtraField, Val
     Ok(field)
 }
 
+fn attach_property_value(name: &str, value: String) -> ActorAttachment {
+    ActorAttachment {
+        object_type: PROPERTY_VALUE.to_string(),
+        name: name.to_string(),
+        value: Some(value),
+        href: None,
+        signature_algorithm: None,
+        signature_value: None,
+    }
+}
+
+/// Represents aggregated ratings as a list of PropertyValue attachments
+pub fn attach_rating_stats(rating_stats: &RatingStats) -> Vec<ActorAttachment> {
+    if rating_stats.review_count == 0 {
+        return vec![];
+    };
+    let mut attachments = vec![];
+    if let Some(average_rating) = rating_stats.average_rating() {
+        let average = format!(
+            "{:.1} / 5 ({} ratings)",
+            average_rating,
+            rating_stats.rating_count(),
+        );
+        attachments.push(attach_property_value("Average rating", average));
+        let histogram = rating_stats
+            .histogram
+            .iter()
+            .enumerate()
+            .map(|(index, count)| format!("{:.1}★ {}", (index + 1) as f32 * 0.5, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        attachments.push(attach_property_value("Rating histogram", histogram));
+    };
+    let review_count = rating_stats.review_count.to_string();
+    attachments.push(attach_property_value("Reviews", review_count));
+    attachments
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +165,31 @@ mod tests {
         assert_eq!(parsed_field.value, field.value);
     }
 
+    #[test]
+    fn test_rating_stats() {
+        let mut rating_stats = RatingStats {
+            review_count: 3,
+            ..Default::default()
+        };
+        rating_stats.histogram[7] = 1;
+        rating_stats.histogram[9] = 1;
+        let attachments = attach_rating_stats(&rating_stats);
+        assert_eq!(attachments.len(), 3);
+        assert_eq!(attachments[0].object_type, PROPERTY_VALUE);
+        assert_eq!(attachments[0].name, "Average rating");
+        assert_eq!(
+            attachments[0].value.as_deref().unwrap(),
+            "4.5 / 5 (2 ratings)",
+        );
+        assert_eq!(attachments[2].value.as_deref().unwrap(), "3");
+    }
+
+    #[test]
+    fn test_rating_stats_empty() {
+        let attachments = attach_rating_stats(&RatingStats::default());
+        assert!(attachments.is_empty());
+    }
+
     #[test]
     fn test_payment_option() {
         let username = "testuser";
@@ -1,6 +1,6 @@
 use super::attachments::{
-    attach_extra_field, attach_identity_proof, attach_payment_option, parse_extra_field,
-    parse_identity_proof, parse_payment_option,
+    attach_extra_field, attach_identity_proof, attach_payment_option, attach_rating_stats,
+    parse_extra_field, parse_identity_proof, parse_payment_option,
 };
 use crate::activitypub::types::build_default_context;
 use crate::activitypub::{
@@ -15,7 +15,9 @@ use crate::media::get_file_url;
 use crate::webfinger::types::ActorAddress;
 use fedimovies_config::Instance;
 use fedimovies_models::{
-    profiles::types::{DbActor, DbActorPublicKey, ExtraField, IdentityProof, PaymentOption},
+    profiles::types::{
+        AccountKind, DbActor, DbActorPublicKey, ExtraField, IdentityProof, PaymentOption,
+    },
     users::types::User,
 };
 use fedimovies_utils::{
@@ -114,6 +116,9 @@ pub struct Actor {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subscribers: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watchlist: Option<String>,
+
     pub public_key: PublicKey,
 
     #[serde(
@@ -245,6 +250,10 @@ pub fn get_local_actor(user: &User, instance_url: &str) -> Result<Actor, ActorKe
     let followers = LocalActorCollection::Followers.of(&actor_id);
     let following = LocalActorCollection::Following.of(&actor_id);
     let subscribers = LocalActorCollection::Subscribers.of(&actor_id);
+    // Watchlist is published only if user opted in
+    let maybe_watchlist = user
+        .show_watchlist
+        .then(|| LocalActorCollection::Watchlist.of(&actor_id));
 
     let private_key = deserialize_private_key(&user.private_key)?;
     let public_key_pem = get_public_key_pem(&private_key)?;
@@ -295,11 +304,27 @@ pub fn get_local_actor(user: &User, instance_url: &str) -> Result<Actor, ActorKe
             serde_json::to_value(attachment).expect("attachment should be serializable");
         attachments.push(attachment_value);
     }
+    // Only movies and TV shows are rated
+    if matches!(
+        user.profile.account_kind,
+        AccountKind::Movie | AccountKind::TvSeries | AccountKind::TvSeason,
+    ) {
+        for attachment in attach_rating_stats(&user.profile.rating_stats) {
+            let attachment_value =
+                serde_json::to_value(attachment).expect("attachment should be serializable");
+            attachments.push(attachment_value);
+        }
+    };
     let aliases = user.profile.aliases.clone().into_actor_ids();
+    let actor_type = if user.profile.is_media() {
+        SERVICE
+    } else {
+        PERSON
+    };
     let actor = Actor {
         context: Some(build_default_context()),
         id: actor_id.clone(),
-        object_type: PERSON.to_string(),
+        object_type: actor_type.to_string(),
         name: user.profile.display_name.clone(),
         preferred_username: username.to_string(),
         inbox,
@@ -308,13 +333,14 @@ pub fn get_local_actor(user: &User, instance_url: &str) -> Result<Actor, ActorKe
         followers: Some(followers),
         following: Some(following),
         subscribers: Some(subscribers),
+        watchlist: maybe_watchlist,
         public_key,
         icon: avatar,
         image: banner,
         summary: user.profile.bio.clone(),
         also_known_as: Some(json!(aliases)),
         attachment: attachments,
-        manually_approves_followers: false,
+        manually_approves_followers: user.profile.manually_approves_followers,
         tag: vec![],
         url: Some(actor_id),
     };
@@ -343,6 +369,7 @@ pub fn get_instance_actor(instance: &Instance) -> Result<Actor, ActorKeyError> {
         followers: None,
         following: None,
         subscribers: None,
+        watchlist: None,
         public_key,
         icon: None,
         image: None,
@@ -411,10 +438,51 @@ mod tests {
             actor.public_key.id,
             "https://example.com/users/testuser#main-key",
         );
+        assert_eq!(actor.watchlist, None);
         assert_eq!(actor.attachment.len(), 0);
         assert_eq!(actor.summary, user.profile.bio);
     }
 
+    #[test]
+    fn test_local_actor_with_watchlist() {
+        let private_key = generate_weak_rsa_key().unwrap();
+        let private_key_pem = serialize_private_key(&private_key).unwrap();
+        let profile = DbActorProfile {
+            username: "testuser".to_string(),
+            ..Default::default()
+        };
+        let user = User {
+            private_key: private_key_pem,
+            show_watchlist: true,
+            profile,
+            ..Default::default()
+        };
+        let actor = get_local_actor(&user, INSTANCE_URL).unwrap();
+        assert_eq!(
+            actor.watchlist.unwrap(),
+            "https://example.com/users/testuser/watchlist",
+        );
+    }
+
+    #[test]
+    fn test_local_actor_movie() {
+        let private_key = generate_weak_rsa_key().unwrap();
+        let private_key_pem = serialize_private_key(&private_key).unwrap();
+        let profile = DbActorProfile {
+            username: "Avatar_2009_19995".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(19995),
+            ..Default::default()
+        };
+        let user = User {
+            private_key: private_key_pem,
+            profile,
+            ..Default::default()
+        };
+        let actor = get_local_actor(&user, INSTANCE_URL).unwrap();
+        assert_eq!(actor.object_type, "Service");
+    }
+
     #[test]
     fn test_instance_actor() {
         let instance_url = "https://example.com/";
@@ -0,0 +1,86 @@
+use serde::Serialize;
+
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    profiles::types::{DbActor, DbActorProfile},
+    users::types::User,
+};
+use fedimovies_utils::id::generate_ulid;
+
+use crate::activitypub::{
+    deliverer::OutgoingActivity,
+    identifiers::{local_actor_id, local_object_id},
+    types::{build_default_context, Context},
+    vocabulary::BLOCK,
+};
+
+#[derive(Serialize)]
+pub(super) struct Block {
+    #[serde(rename = "@context")]
+    pub context: Context,
+
+    #[serde(rename = "type")]
+    pub activity_type: String,
+
+    pub id: String,
+    pub actor: String,
+    pub object: String,
+
+    pub to: Vec<String>,
+}
+
+pub(super) fn build_block(
+    instance_url: &str,
+    actor_profile: &DbActorProfile,
+    target_actor_id: &str,
+) -> Block {
+    // Blocks are not stored as objects, random ID is used
+    let activity_id = local_object_id(instance_url, &generate_ulid());
+    let actor_id = local_actor_id(instance_url, &actor_profile.username);
+    Block {
+        context: build_default_context(),
+        activity_type: BLOCK.to_string(),
+        id: activity_id,
+        actor: actor_id,
+        object: target_actor_id.to_string(),
+        to: vec![target_actor_id.to_string()],
+    }
+}
+
+pub fn prepare_block(
+    instance: &Instance,
+    sender: &User,
+    target_actor: &DbActor,
+) -> OutgoingActivity {
+    let activity = build_block(&instance.url(), &sender.profile, &target_actor.id);
+    let recipients = vec![target_actor.clone()];
+    OutgoingActivity::new(instance, sender, activity, recipients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSTANCE_URL: &str = "https://example.com";
+
+    #[test]
+    fn test_build_block() {
+        let actor_profile = DbActorProfile {
+            username: "user".to_string(),
+            ..Default::default()
+        };
+        let target_actor_id = "https://test.remote/users/123";
+        let activity = build_block(INSTANCE_URL, &actor_profile, target_actor_id);
+
+        assert!(activity
+            .id
+            .starts_with(&format!("{}/objects/", INSTANCE_URL)));
+        assert_eq!(activity.activity_type, "Block");
+        assert_eq!(
+            activity.actor,
+            format!("{}/users/{}", INSTANCE_URL, actor_profile.username),
+        );
+        assert_eq!(activity.object, target_actor_id);
+        assert_eq!(activity.to, vec![target_actor_id]);
+    }
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    database::{DatabaseClient, DatabaseError},
+    movie_lists::types::MovieList,
+    profiles::types::DbActor,
+    relationships::queries::get_followers,
+    users::types::User,
+};
+use fedimovies_utils::html::escape_html;
+
+use crate::activitypub::{
+    constants::AP_PUBLIC,
+    deliverer::OutgoingActivity,
+    identifiers::{local_actor_followers, local_actor_id, local_movie_list_id, profile_actor_id},
+    types::{build_default_context, Context},
+    vocabulary::{CREATE, ORDERED_COLLECTION},
+};
+
+/// Curated movie list, represented as OrderedCollection of movie actors
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovieListObject {
+    #[serde(rename = "@context")]
+    context: Context,
+
+    pub id: String,
+
+    #[serde(rename = "type")]
+    object_type: String,
+
+    pub attributed_to: String,
+    name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+
+    // Human-readable version of the list, with notes
+    content: String,
+
+    total_items: i32,
+    ordered_items: Vec<String>,
+
+    published: DateTime<Utc>,
+    updated: DateTime<Utc>,
+
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+}
+
+fn build_movie_list_content(instance_url: &str, list: &MovieList) -> String {
+    let mut content = "<ol>".to_string();
+    for item in list.items.iter() {
+        let movie_name = item
+            .movie
+            .display_name
+            .as_ref()
+            .unwrap_or(&item.movie.username);
+        content += &format!(
+            r#"<li><a href="{}">{}</a>"#,
+            profile_actor_id(instance_url, &item.movie),
+            escape_html(movie_name),
+        );
+        if let Some(ref note) = item.note {
+            content += &format!(": {}", escape_html(note));
+        };
+        content += "</li>";
+    }
+    content += "</ol>";
+    content
+}
+
+pub fn build_movie_list(instance_url: &str, list: &MovieList) -> MovieListObject {
+    let ordered_items: Vec<String> = list
+        .items
+        .iter()
+        .map(|item| profile_actor_id(instance_url, &item.movie))
+        .collect();
+    MovieListObject {
+        context: build_default_context(),
+        id: local_movie_list_id(instance_url, &list.id),
+        object_type: ORDERED_COLLECTION.to_string(),
+        attributed_to: local_actor_id(instance_url, &list.owner.username),
+        name: list.title.clone(),
+        // Description is plain text, but summary is HTML
+        summary: list
+            .description
+            .as_ref()
+            .map(|description| escape_html(description)),
+        content: build_movie_list_content(instance_url, list),
+        total_items: ordered_items.len() as i32,
+        ordered_items,
+        published: list.created_at,
+        updated: list.updated_at,
+        to: vec![AP_PUBLIC.to_string()],
+        cc: vec![local_actor_followers(instance_url, &list.owner.username)],
+    }
+}
+
+#[derive(Serialize)]
+struct CreateMovieList {
+    #[serde(rename = "@context")]
+    context: Context,
+
+    #[serde(rename = "type")]
+    activity_type: String,
+
+    id: String,
+    actor: String,
+    object: MovieListObject,
+
+    to: Vec<String>,
+    cc: Vec<String>,
+}
+
+fn build_create_movie_list(instance_url: &str, list: &MovieList) -> CreateMovieList {
+    let object = build_movie_list(instance_url, list);
+    let activity_id = format!("{}/create", object.id);
+    CreateMovieList {
+        context: build_default_context(),
+        activity_type: CREATE.to_string(),
+        id: activity_id,
+        actor: object.attributed_to.clone(),
+        to: object.to.clone(),
+        cc: object.cc.clone(),
+        object: object,
+    }
+}
+
+pub async fn get_movie_list_recipients(
+    db_client: &impl DatabaseClient,
+    owner: &User,
+) -> Result<Vec<DbActor>, DatabaseError> {
+    let followers = get_followers(db_client, &owner.id).await?;
+    let recipients = followers
+        .into_iter()
+        .filter_map(|profile| profile.actor_json)
+        .collect();
+    Ok(recipients)
+}
+
+pub async fn prepare_create_movie_list(
+    db_client: &impl DatabaseClient,
+    instance: &Instance,
+    owner: &User,
+    list: &MovieList,
+) -> Result<OutgoingActivity, DatabaseError> {
+    assert_eq!(owner.id, list.owner.id);
+    let activity = build_create_movie_list(&instance.url(), list);
+    let recipients = get_movie_list_recipients(db_client, owner).await?;
+    Ok(OutgoingActivity::new(instance, owner, activity, recipients))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fedimovies_models::{movie_lists::types::MovieListItem, profiles::types::DbActorProfile};
+    use fedimovies_utils::id::generate_ulid;
+
+    const INSTANCE_URL: &str = "https://example.com";
+
+    #[test]
+    fn test_build_create_movie_list() {
+        let owner = DbActorProfile {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let movie = DbActorProfile {
+            username: "Ran_1985_11645".to_string(),
+            display_name: Some("Ran (1985)".to_string()),
+            ..Default::default()
+        };
+        let list = MovieList {
+            id: generate_ulid(),
+            owner,
+            title: "Kurosawa ranked".to_string(),
+            description: Some("Best <3".to_string()),
+            items: vec![MovieListItem {
+                movie,
+                note: Some("King Lear & samurai".to_string()),
+            }],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let activity = build_create_movie_list(INSTANCE_URL, &list);
+        let list_id = format!("{}/collections/lists/{}", INSTANCE_URL, list.id);
+        assert_eq!(activity.id, format!("{}/create", list_id));
+        assert_eq!(activity.activity_type, "Create");
+        assert_eq!(activity.actor, "https://example.com/users/test");
+        assert_eq!(activity.object.id, list_id);
+        assert_eq!(activity.object.object_type, "OrderedCollection");
+        assert_eq!(activity.object.name, "Kurosawa ranked");
+        assert_eq!(activity.object.summary.as_deref(), Some("Best&#32;&lt;3"));
+        assert_eq!(activity.object.total_items, 1);
+        assert_eq!(
+            activity.object.ordered_items,
+            vec!["https://example.com/users/Ran_1985_11645"],
+        );
+        assert_eq!(
+            activity.object.content,
+            r#"<ol><li><a href="https://example.com/users/Ran_1985_11645">Ran&#32;(1985)</a>: King&#32;Lear&#32;&amp;&#32;samurai</li></ol>"#,
+        );
+        assert_eq!(activity.to, vec![AP_PUBLIC]);
+        assert_eq!(
+            activity.cc,
+            vec!["https://example.com/users/test/followers"],
+        );
+    }
+}
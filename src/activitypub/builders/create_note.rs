@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 
@@ -11,6 +13,7 @@ use fedimovies_models::{
     relationships::queries::{get_followers, get_subscribers},
     users::types::User,
 };
+use fedimovies_utils::html::escape_html;
 
 use crate::activitypub::{
     constants::{AP_MEDIA_TYPE, AP_PUBLIC},
@@ -42,7 +45,7 @@ pub struct Note {
     #[serde(rename = "@context")]
     context: Context,
 
-    id: String,
+    pub id: String,
 
     #[serde(rename = "type")]
     object_type: String,
@@ -50,16 +53,26 @@ pub struct Note {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     attachment: Vec<Attachment>,
 
-    attributed_to: String,
+    pub attributed_to: String,
 
     content: String,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_map: Option<HashMap<String, String>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     in_reply_to: Option<String>,
 
     published: DateTime<Utc>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated: Option<DateTime<Utc>>,
+
     sensitive: bool,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     tag: Vec<Tag>,
 
@@ -68,6 +81,9 @@ pub struct Note {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     quote_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rating_value: Option<f32>,
 }
 
 pub fn build_emoji_tag(instance_url: &str, emoji: &DbEmoji) -> EmojiTag {
@@ -193,12 +209,20 @@ pub fn build_note(instance_hostname: &str, instance_url: &str, post: &Post) -> N
         attributed_to: actor_id,
         in_reply_to: in_reply_to_object_id,
         content: post.content.clone(),
+        content_map: post
+            .language
+            .as_ref()
+            .map(|language| HashMap::from([(language.clone(), post.content.clone())])),
         published: post.created_at,
+        updated: post.updated_at,
         sensitive: post.is_sensitive,
+        // Content warning is plain text, but summary is HTML
+        summary: post.summary.as_ref().map(|summary| escape_html(summary)),
         tag: tags,
         to: primary_audience,
         cc: secondary_audience,
         quote_url: maybe_quote_url,
+        rating_value: post.rating,
     }
 }
 
@@ -355,6 +379,42 @@ mod tests {
         assert_eq!(tag.href, "https://example.com/collections/tags/test");
     }
 
+    #[test]
+    fn test_build_note_with_rating() {
+        let post = Post {
+            rating: Some(3.5),
+            ..Default::default()
+        };
+        let note = build_note(INSTANCE_HOSTNAME, INSTANCE_URL, &post);
+        let value = serde_json::to_value(note).unwrap();
+        assert_eq!(value["ratingValue"], json!(3.5));
+    }
+
+    #[test]
+    fn test_build_note_with_summary() {
+        let post = Post {
+            is_sensitive: true,
+            summary: Some("Tom & Jerry".to_string()),
+            ..Default::default()
+        };
+        let note = build_note(INSTANCE_HOSTNAME, INSTANCE_URL, &post);
+        let value = serde_json::to_value(note).unwrap();
+        assert_eq!(value["summary"], json!("Tom&#32;&amp;&#32;Jerry"));
+        assert_eq!(value["sensitive"], json!(true));
+    }
+
+    #[test]
+    fn test_build_note_with_language() {
+        let post = Post {
+            content: "<p>test</p>".to_string(),
+            language: Some("en".to_string()),
+            ..Default::default()
+        };
+        let note = build_note(INSTANCE_HOSTNAME, INSTANCE_URL, &post);
+        let value = serde_json::to_value(note).unwrap();
+        assert_eq!(value["contentMap"], json!({"en": "<p>test</p>"}));
+    }
+
     #[test]
     fn test_build_note_followers_only() {
         let post = Post {
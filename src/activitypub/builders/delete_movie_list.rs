@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    database::{DatabaseClient, DatabaseError},
+    movie_lists::types::MovieList,
+    users::types::User,
+};
+
+use crate::activitypub::{
+    deliverer::OutgoingActivity,
+    identifiers::local_movie_list_id,
+    types::{build_default_context, Context},
+    vocabulary::{DELETE, ORDERED_COLLECTION, TOMBSTONE},
+};
+
+use super::create_movie_list::{build_movie_list, get_movie_list_recipients, MovieListObject};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Tombstone {
+    id: String,
+
+    #[serde(rename = "type")]
+    object_type: String,
+
+    former_type: String,
+}
+
+#[derive(Serialize)]
+struct DeleteMovieList {
+    #[serde(rename = "@context")]
+    context: Context,
+
+    #[serde(rename = "type")]
+    activity_type: String,
+
+    id: String,
+    actor: String,
+    object: Tombstone,
+
+    to: Vec<String>,
+    cc: Vec<String>,
+}
+
+fn build_delete_movie_list(instance_url: &str, list: &MovieList) -> DeleteMovieList {
+    let object_id = local_movie_list_id(instance_url, &list.id);
+    let activity_id = format!("{}/delete", object_id);
+    let MovieListObject {
+        attributed_to,
+        to,
+        cc,
+        ..
+    } = build_movie_list(instance_url, list);
+    DeleteMovieList {
+        context: build_default_context(),
+        activity_type: DELETE.to_string(),
+        id: activity_id,
+        actor: attributed_to,
+        object: Tombstone {
+            id: object_id,
+            object_type: TOMBSTONE.to_string(),
+            former_type: ORDERED_COLLECTION.to_string(),
+        },
+        to: to,
+        cc: cc,
+    }
+}
+
+pub async fn prepare_delete_movie_list(
+    db_client: &impl DatabaseClient,
+    instance: &Instance,
+    owner: &User,
+    list: &MovieList,
+) -> Result<OutgoingActivity, DatabaseError> {
+    assert_eq!(owner.id, list.owner.id);
+    let activity = build_delete_movie_list(&instance.url(), list);
+    let recipients = get_movie_list_recipients(db_client, owner).await?;
+    Ok(OutgoingActivity::new(instance, owner, activity, recipients))
+}
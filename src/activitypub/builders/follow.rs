@@ -4,8 +4,10 @@ use uuid::Uuid;
 use fedimovies_config::Instance;
 use fedimovies_models::{
     database::{DatabaseClient, DatabaseError},
+    notifications::queries::create_follow_request_notification,
     profiles::types::{DbActor, DbActorProfile},
-    relationships::queries::{create_follow_request, follow},
+    relationships::queries::{create_follow_request, follow, has_relationship},
+    relationships::types::RelationshipType,
     users::types::User,
 };
 
@@ -82,6 +84,27 @@ pub async fn follow_or_create_request(
             Err(DatabaseError::AlreadyExists(_)) => (), // already following
             Err(other_error) => return Err(other_error),
         };
+    } else if target_profile.manually_approves_followers {
+        if has_relationship(
+            db_client,
+            &current_user.id,
+            &target_profile.id,
+            RelationshipType::Follow,
+        )
+        .await?
+        {
+            // Already following
+            return Ok(());
+        };
+        // Local actor must approve the request
+        match create_follow_request(db_client, &current_user.id, &target_profile.id).await {
+            Ok(_) => {
+                create_follow_request_notification(db_client, &current_user.id, &target_profile.id)
+                    .await?;
+            }
+            Err(DatabaseError::AlreadyExists(_)) => (), // already requested
+            Err(other_error) => return Err(other_error),
+        };
     } else {
         match follow(db_client, &current_user.id, &target_profile.id).await {
             Ok(_) => (),
@@ -1,14 +1,23 @@
 pub mod accept_follow;
 pub mod add_person;
 pub mod announce;
+pub mod block;
+pub mod create_movie_list;
 pub mod create_note;
+pub mod delete_movie_list;
 pub mod delete_note;
 pub mod delete_person;
 pub mod follow;
 pub mod like;
 pub mod move_person;
+pub mod reject_follow;
 pub mod remove_person;
 pub mod undo_announce;
+pub mod undo_block;
 pub mod undo_follow;
 pub mod undo_like;
+pub mod undo_view_movie;
+pub mod update_movie_list;
+pub mod update_note;
 pub mod update_person;
+pub mod view_movie;
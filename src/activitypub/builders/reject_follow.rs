@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    profiles::types::{DbActor, DbActorProfile},
+    users::types::User,
+};
+use fedimovies_utils::id::generate_ulid;
+
+use crate::activitypub::{
+    deliverer::OutgoingActivity,
+    identifiers::{local_actor_id, local_object_id},
+    types::{build_default_context, Context},
+    vocabulary::REJECT,
+};
+
+#[derive(Serialize)]
+struct RejectFollow {
+    #[serde(rename = "@context")]
+    context: Context,
+
+    #[serde(rename = "type")]
+    activity_type: String,
+
+    id: String,
+    actor: String,
+    object: String,
+
+    to: Vec<String>,
+}
+
+fn build_reject_follow(
+    instance_url: &str,
+    actor_profile: &DbActorProfile,
+    source_actor_id: &str,
+    follow_activity_id: &str,
+) -> RejectFollow {
+    let activity_id = local_object_id(instance_url, &generate_ulid());
+    let actor_id = local_actor_id(instance_url, &actor_profile.username);
+    RejectFollow {
+        context: build_default_context(),
+        activity_type: REJECT.to_string(),
+        id: activity_id,
+        actor: actor_id,
+        object: follow_activity_id.to_string(),
+        to: vec![source_actor_id.to_string()],
+    }
+}
+
+pub fn prepare_reject_follow(
+    instance: &Instance,
+    sender: &User,
+    source_actor: &DbActor,
+    follow_activity_id: &str,
+) -> OutgoingActivity {
+    let activity = build_reject_follow(
+        &instance.url(),
+        &sender.profile,
+        &source_actor.id,
+        follow_activity_id,
+    );
+    let recipients = vec![source_actor.clone()];
+    OutgoingActivity::new(instance, sender, activity, recipients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSTANCE_URL: &str = "https://example.com";
+
+    #[test]
+    fn test_build_reject_follow() {
+        let target = DbActorProfile {
+            username: "user".to_string(),
+            ..Default::default()
+        };
+        let follow_activity_id = "https://test.remote/objects/999";
+        let follower_id = "https://test.remote/users/123";
+        let activity = build_reject_follow(INSTANCE_URL, &target, follower_id, follow_activity_id);
+
+        assert!(activity.id.starts_with(INSTANCE_URL));
+        assert_eq!(activity.activity_type, "Reject");
+        assert_eq!(activity.actor, "https://example.com/users/user");
+        assert_eq!(activity.object, follow_activity_id);
+        assert_eq!(activity.to, vec![follower_id]);
+    }
+}
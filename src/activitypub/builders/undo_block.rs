@@ -0,0 +1,84 @@
+use serde::Serialize;
+
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    profiles::types::{DbActor, DbActorProfile},
+    users::types::User,
+};
+use fedimovies_utils::id::generate_ulid;
+
+use crate::activitypub::{
+    deliverer::OutgoingActivity,
+    identifiers::{local_actor_id, local_object_id},
+    types::{build_default_context, Context},
+    vocabulary::UNDO,
+};
+
+use super::block::{build_block, Block};
+
+#[derive(Serialize)]
+struct UndoBlock {
+    #[serde(rename = "@context")]
+    context: Context,
+
+    #[serde(rename = "type")]
+    activity_type: String,
+
+    id: String,
+    actor: String,
+    object: Block,
+
+    to: Vec<String>,
+}
+
+fn build_undo_block(
+    instance_url: &str,
+    actor_profile: &DbActorProfile,
+    target_actor_id: &str,
+) -> UndoBlock {
+    let object = build_block(instance_url, actor_profile, target_actor_id);
+    let activity_id = local_object_id(instance_url, &generate_ulid());
+    let actor_id = local_actor_id(instance_url, &actor_profile.username);
+    UndoBlock {
+        context: build_default_context(),
+        activity_type: UNDO.to_string(),
+        id: activity_id,
+        actor: actor_id,
+        object: object,
+        to: vec![target_actor_id.to_string()],
+    }
+}
+
+pub fn prepare_undo_block(
+    instance: &Instance,
+    sender: &User,
+    target_actor: &DbActor,
+) -> OutgoingActivity {
+    let activity = build_undo_block(&instance.url(), &sender.profile, &target_actor.id);
+    let recipients = vec![target_actor.clone()];
+    OutgoingActivity::new(instance, sender, activity, recipients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSTANCE_URL: &str = "https://example.com";
+
+    #[test]
+    fn test_build_undo_block() {
+        let actor_profile = DbActorProfile {
+            username: "user".to_string(),
+            ..Default::default()
+        };
+        let target_actor_id = "https://test.remote/users/123";
+        let activity = build_undo_block(INSTANCE_URL, &actor_profile, target_actor_id);
+
+        assert_eq!(activity.activity_type, "Undo");
+        assert_ne!(activity.id, activity.object.id);
+        assert_eq!(activity.object.activity_type, "Block");
+        assert_eq!(activity.object.actor, activity.actor);
+        assert_eq!(activity.object.object, target_actor_id);
+        assert_eq!(activity.to, vec![target_actor_id]);
+    }
+}
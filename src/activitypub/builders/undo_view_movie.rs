@@ -0,0 +1,103 @@
+use serde::Serialize;
+
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    database::{DatabaseClient, DatabaseError},
+    users::types::User,
+    watch_log::types::WatchLogEntry,
+};
+
+use crate::activitypub::{
+    deliverer::OutgoingActivity,
+    identifiers::local_actor_id,
+    types::{build_default_context, Context},
+    vocabulary::UNDO,
+};
+
+use super::view_movie::{build_view_movie, get_view_movie_recipients};
+
+#[derive(Serialize)]
+struct UndoViewMovie {
+    #[serde(rename = "@context")]
+    context: Context,
+
+    #[serde(rename = "type")]
+    activity_type: String,
+
+    id: String,
+    actor: String,
+    object: String,
+
+    to: Vec<String>,
+    cc: Vec<String>,
+}
+
+fn build_undo_view_movie(instance_url: &str, entry: &WatchLogEntry) -> UndoViewMovie {
+    let view = build_view_movie(instance_url, entry);
+    let activity_id = format!("{}/undo", view.id);
+    let actor_id = local_actor_id(instance_url, &entry.owner.username);
+    UndoViewMovie {
+        context: build_default_context(),
+        activity_type: UNDO.to_string(),
+        id: activity_id,
+        actor: actor_id,
+        object: view.id,
+        to: view.to,
+        cc: view.cc,
+    }
+}
+
+pub async fn prepare_undo_view_movie(
+    db_client: &impl DatabaseClient,
+    instance: &Instance,
+    sender: &User,
+    entry: &WatchLogEntry,
+) -> Result<OutgoingActivity, DatabaseError> {
+    let recipients = get_view_movie_recipients(db_client, sender, entry).await?;
+    let activity = build_undo_view_movie(&instance.url(), entry);
+    Ok(OutgoingActivity::new(
+        instance, sender, activity, recipients,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use fedimovies_models::profiles::types::DbActorProfile;
+    use fedimovies_utils::id::generate_ulid;
+
+    const INSTANCE_URL: &str = "https://example.com";
+
+    #[test]
+    fn test_build_undo_view_movie() {
+        let owner = DbActorProfile {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let movie = DbActorProfile {
+            username: "Dune_2021_438631".to_string(),
+            ..Default::default()
+        };
+        let entry = WatchLogEntry {
+            id: generate_ulid(),
+            owner,
+            movie,
+            watched_on: NaiveDate::from_ymd_opt(2021, 10, 22).unwrap(),
+            is_rewatch: false,
+            rating: None,
+            is_federated: true,
+            created_at: Utc::now(),
+        };
+        let activity = build_undo_view_movie(INSTANCE_URL, &entry);
+        let view_id = format!("{}/activities/views/{}", INSTANCE_URL, entry.id);
+        assert_eq!(activity.id, format!("{}/undo", view_id));
+        assert_eq!(activity.activity_type, "Undo");
+        assert_eq!(activity.actor, "https://example.com/users/test");
+        assert_eq!(activity.object, view_id);
+        assert_eq!(
+            activity.to,
+            vec!["https://example.com/users/Dune_2021_438631"],
+        );
+    }
+}
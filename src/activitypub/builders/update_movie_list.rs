@@ -0,0 +1,61 @@
+use serde::Serialize;
+
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    database::{DatabaseClient, DatabaseError},
+    movie_lists::types::MovieList,
+    users::types::User,
+};
+use fedimovies_utils::id::generate_ulid;
+
+use crate::activitypub::{
+    deliverer::OutgoingActivity,
+    identifiers::local_object_id,
+    types::{build_default_context, Context},
+    vocabulary::UPDATE,
+};
+
+use super::create_movie_list::{build_movie_list, get_movie_list_recipients, MovieListObject};
+
+#[derive(Serialize)]
+struct UpdateMovieList {
+    #[serde(rename = "@context")]
+    context: Context,
+
+    #[serde(rename = "type")]
+    activity_type: String,
+
+    id: String,
+    actor: String,
+    object: MovieListObject,
+
+    to: Vec<String>,
+    cc: Vec<String>,
+}
+
+fn build_update_movie_list(instance_url: &str, list: &MovieList) -> UpdateMovieList {
+    let object = build_movie_list(instance_url, list);
+    // Lists can be updated many times, each update gets random ID
+    let activity_id = local_object_id(instance_url, &generate_ulid());
+    UpdateMovieList {
+        context: build_default_context(),
+        activity_type: UPDATE.to_string(),
+        id: activity_id,
+        actor: object.attributed_to.clone(),
+        to: object.to.clone(),
+        cc: object.cc.clone(),
+        object: object,
+    }
+}
+
+pub async fn prepare_update_movie_list(
+    db_client: &impl DatabaseClient,
+    instance: &Instance,
+    owner: &User,
+    list: &MovieList,
+) -> Result<OutgoingActivity, DatabaseError> {
+    assert_eq!(owner.id, list.owner.id);
+    let activity = build_update_movie_list(&instance.url(), list);
+    let recipients = get_movie_list_recipients(db_client, owner).await?;
+    Ok(OutgoingActivity::new(instance, owner, activity, recipients))
+}
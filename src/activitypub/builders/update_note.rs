@@ -0,0 +1,104 @@
+use serde::Serialize;
+
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    database::{DatabaseClient, DatabaseError},
+    posts::types::Post,
+    users::types::User,
+};
+use fedimovies_utils::id::generate_ulid;
+
+use crate::activitypub::{
+    deliverer::OutgoingActivity,
+    identifiers::local_object_id,
+    types::{build_default_context, Context},
+    vocabulary::UPDATE,
+};
+
+use super::create_note::{build_note, get_note_recipients, Note};
+
+#[derive(Serialize)]
+struct UpdateNote {
+    #[serde(rename = "@context")]
+    context: Context,
+
+    #[serde(rename = "type")]
+    activity_type: String,
+
+    id: String,
+    actor: String,
+    object: Note,
+
+    to: Vec<String>,
+    cc: Vec<String>,
+}
+
+fn build_update_note(instance_hostname: &str, instance_url: &str, post: &Post) -> UpdateNote {
+    let object = build_note(instance_hostname, instance_url, post);
+    // Posts can be edited many times, each update gets random ID
+    let activity_id = local_object_id(instance_url, &generate_ulid());
+    UpdateNote {
+        context: build_default_context(),
+        activity_type: UPDATE.to_string(),
+        id: activity_id,
+        actor: object.attributed_to.clone(),
+        to: object.to.clone(),
+        cc: object.cc.clone(),
+        object: object,
+    }
+}
+
+pub async fn prepare_update_note(
+    db_client: &impl DatabaseClient,
+    instance: &Instance,
+    author: &User,
+    post: &Post,
+) -> Result<OutgoingActivity, DatabaseError> {
+    assert_eq!(author.id, post.author.id);
+    let activity = build_update_note(&instance.hostname(), &instance.url(), post);
+    let recipients = get_note_recipients(db_client, author, post).await?;
+    Ok(OutgoingActivity::new(
+        instance, author, activity, recipients,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use fedimovies_models::profiles::types::DbActorProfile;
+    use serde_json::json;
+
+    const INSTANCE_HOSTNAME: &str = "example.com";
+    const INSTANCE_URL: &str = "https://example.com";
+
+    #[test]
+    fn test_build_update_note() {
+        let author = DbActorProfile {
+            username: "author".to_string(),
+            ..Default::default()
+        };
+        let post = Post {
+            author,
+            content: "<p>updated</p>".to_string(),
+            updated_at: Some(Utc::now()),
+            ..Default::default()
+        };
+        let activity = build_update_note(INSTANCE_HOSTNAME, INSTANCE_URL, &post);
+        assert_eq!(activity.activity_type, "Update");
+        assert!(activity.id.starts_with("https://example.com/objects/"));
+        assert_ne!(activity.id, activity.object.id);
+        assert_eq!(activity.actor, "https://example.com/users/author");
+        assert_eq!(
+            activity.object.id,
+            format!("{}/objects/{}", INSTANCE_URL, post.id),
+        );
+        assert_eq!(
+            activity.to,
+            vec!["https://www.w3.org/ns/activitystreams#Public"]
+        );
+        let value = serde_json::to_value(&activity.object).unwrap();
+        assert_eq!(value["content"], json!("<p>updated</p>"));
+        assert!(value["updated"].is_string());
+    }
+}
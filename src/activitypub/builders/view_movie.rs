@@ -0,0 +1,144 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    database::{DatabaseClient, DatabaseError},
+    profiles::types::DbActor,
+    relationships::queries::get_followers,
+    users::types::User,
+    watch_log::types::WatchLogEntry,
+};
+
+use crate::activitypub::{
+    deliverer::OutgoingActivity,
+    identifiers::{
+        local_actor_followers, local_actor_id, local_view_activity_id, profile_actor_id,
+    },
+    types::{build_default_context, Context},
+    vocabulary::VIEW,
+};
+
+/// Watch log entry, represented as View activity
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewMovie {
+    #[serde(rename = "@context")]
+    context: Context,
+
+    #[serde(rename = "type")]
+    activity_type: String,
+
+    pub id: String,
+    actor: String,
+    object: String,
+    published: DateTime<Utc>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rating_value: Option<f32>,
+
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+}
+
+pub fn build_view_movie(instance_url: &str, entry: &WatchLogEntry) -> ViewMovie {
+    let activity_id = local_view_activity_id(instance_url, &entry.id);
+    let actor_id = local_actor_id(instance_url, &entry.owner.username);
+    let movie_id = profile_actor_id(instance_url, &entry.movie);
+    let published = Utc.from_utc_datetime(
+        &entry
+            .watched_on
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight should be valid"),
+    );
+    ViewMovie {
+        context: build_default_context(),
+        activity_type: VIEW.to_string(),
+        id: activity_id,
+        actor: actor_id,
+        object: movie_id.clone(),
+        published,
+        rating_value: entry.rating,
+        to: vec![movie_id],
+        cc: vec![local_actor_followers(instance_url, &entry.owner.username)],
+    }
+}
+
+pub async fn get_view_movie_recipients(
+    db_client: &impl DatabaseClient,
+    sender: &User,
+    entry: &WatchLogEntry,
+) -> Result<Vec<DbActor>, DatabaseError> {
+    let followers = get_followers(db_client, &sender.id).await?;
+    let mut recipients: Vec<DbActor> = followers
+        .into_iter()
+        .filter_map(|profile| profile.actor_json)
+        .collect();
+    if let Some(remote_actor) = entry.movie.actor_json.as_ref() {
+        recipients.push(remote_actor.clone());
+    };
+    Ok(recipients)
+}
+
+pub async fn prepare_view_movie(
+    db_client: &impl DatabaseClient,
+    instance: &Instance,
+    sender: &User,
+    entry: &WatchLogEntry,
+) -> Result<OutgoingActivity, DatabaseError> {
+    let recipients = get_view_movie_recipients(db_client, sender, entry).await?;
+    let activity = build_view_movie(&instance.url(), entry);
+    Ok(OutgoingActivity::new(
+        instance, sender, activity, recipients,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use fedimovies_models::profiles::types::DbActorProfile;
+    use fedimovies_utils::id::generate_ulid;
+
+    const INSTANCE_URL: &str = "https://example.com";
+
+    #[test]
+    fn test_build_view_movie() {
+        let owner = DbActorProfile {
+            username: "test".to_string(),
+            ..Default::default()
+        };
+        let movie = DbActorProfile {
+            username: "Dune_2021_438631".to_string(),
+            ..Default::default()
+        };
+        let entry = WatchLogEntry {
+            id: generate_ulid(),
+            owner,
+            movie,
+            watched_on: NaiveDate::from_ymd_opt(2021, 10, 22).unwrap(),
+            is_rewatch: false,
+            rating: Some(4.5),
+            is_federated: true,
+            created_at: Utc::now(),
+        };
+        let activity = build_view_movie(INSTANCE_URL, &entry);
+        assert_eq!(
+            activity.id,
+            format!("{}/activities/views/{}", INSTANCE_URL, entry.id),
+        );
+        assert_eq!(activity.activity_type, "View");
+        assert_eq!(activity.actor, "https://example.com/users/test");
+        assert_eq!(
+            activity.object,
+            "https://example.com/users/Dune_2021_438631",
+        );
+        assert_eq!(activity.published.to_rfc3339(), "2021-10-22T00:00:00+00:00");
+        assert_eq!(activity.rating_value, Some(4.5));
+        assert_eq!(activity.to, vec![activity.object.clone()]);
+        assert_eq!(
+            activity.cc,
+            vec!["https://example.com/users/test/followers"],
+        );
+    }
+}
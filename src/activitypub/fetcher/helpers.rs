@@ -22,6 +22,7 @@ use crate::activitypub::{
 };
 use crate::errors::ValidationError;
 use crate::media::MediaStorage;
+use crate::tmdb::MovieMetadataProvider;
 use crate::webfinger::types::ActorAddress;
 
 pub async fn get_or_import_profile_by_actor_id(
@@ -153,8 +154,7 @@ pub async fn import_post(
     db_client: &mut impl DatabaseClient,
     instance: &Instance,
     storage: &MediaStorage,
-    tmdb_api_key: Option<String>,
-    default_movie_user_password: Option<String>,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
     object_id: String,
     object_received: Option<Object>,
 ) -> Result<Post, HandlerError> {
@@ -251,8 +251,7 @@ pub async fn import_post(
             db_client,
             instance,
             storage,
-            tmdb_api_key.clone(),
-            default_movie_user_password.clone(),
+            movie_provider,
             object,
             &redirects,
         )
@@ -17,6 +17,7 @@ use crate::activitypub::{
 };
 use crate::errors::ValidationError;
 use crate::media::MediaStorage;
+use crate::tmdb::get_movie_metadata_provider;
 
 #[derive(Deserialize)]
 struct Announce {
@@ -54,14 +55,12 @@ pub async fn handle_announce(
         Ok(post_id) => post_id,
         Err(_) => {
             // Try to get remote post
-            let tmdb_api_key = config.tmdb_api_key.clone();
-            let default_movie_user_password = config.movie_user_password.clone();
+            let movie_provider = get_movie_metadata_provider(config);
             let post = import_post(
                 db_client,
                 &instance,
                 &storage,
-                tmdb_api_key,
-                default_movie_user_password,
+                movie_provider.as_deref(),
                 activity.object,
                 None,
             )
@@ -0,0 +1,38 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use fedimovies_config::Config;
+use fedimovies_models::{
+    database::DatabaseClient, profiles::queries::get_profile_by_remote_actor_id,
+    relationships::queries::block, users::queries::get_user_by_name,
+};
+
+use super::HandlerResult;
+use crate::activitypub::{
+    identifiers::parse_local_actor_id, receiver::deserialize_into_object_id, vocabulary::PERSON,
+};
+use crate::errors::ValidationError;
+
+#[derive(Deserialize)]
+struct Block {
+    actor: String,
+    #[serde(deserialize_with = "deserialize_into_object_id")]
+    object: String,
+}
+
+pub async fn handle_block(
+    config: &Config,
+    db_client: &mut impl DatabaseClient,
+    activity: Value,
+) -> HandlerResult {
+    // Block(Person)
+    let activity: Block = serde_json::from_value(activity.clone()).map_err(|_| {
+        ValidationError(format!("unexpected Block activity structure: {}", activity))
+    })?;
+    let source_profile = get_profile_by_remote_actor_id(db_client, &activity.actor).await?;
+    let target_username = parse_local_actor_id(&config.instance_url(), &activity.object)?;
+    let target_user = get_user_by_name(db_client, &target_username).await?;
+    // Follows are removed in both directions, activities are not sent
+    block(db_client, &source_profile.id, &target_user.id).await?;
+    Ok(Some(PERSON))
+}
@@ -17,7 +17,6 @@ use fedimovies_models::{
     },
     profiles::types::DbActorProfile,
     relationships::queries::has_local_followers,
-    users::queries::get_user_by_name,
 };
 use fedimovies_utils::{html::clean_html, urls::get_hostname};
 
@@ -36,12 +35,15 @@ use crate::activitypub::{
 };
 use crate::errors::ValidationError;
 use crate::media::MediaStorage;
-use crate::tmdb::lookup_and_create_movie_user;
+use crate::tmdb::{
+    get_movie_metadata_provider, get_or_create_media_user, MovieError, MovieMetadataProvider,
+};
 use crate::validators::{
     emojis::{validate_emoji_name, EMOJI_MEDIA_TYPES},
     posts::{
-        content_allowed_classes, ATTACHMENT_LIMIT, CONTENT_MAX_SIZE, EMOJI_LIMIT, LINK_LIMIT,
-        MENTION_LIMIT, OBJECT_ID_SIZE_MAX,
+        clean_summary, content_allowed_classes, validate_language, validate_rating,
+        ATTACHMENT_LIMIT, CONTENT_MAX_SIZE, EMOJI_LIMIT, LINK_LIMIT, MENTION_LIMIT,
+        OBJECT_ID_SIZE_MAX,
     },
     tags::validate_hashtag,
 };
@@ -93,6 +95,37 @@ pub fn get_object_content(object: &Object) -> Result<String, ValidationError> {
     Ok(content_safe)
 }
 
+/// Returns rating if it is valid, invalid ratings are ignored
+pub fn get_object_rating(object: &Object) -> Option<f32> {
+    object.rating_value.filter(|rating| {
+        validate_rating(*rating)
+            .map_err(|_| log::warn!("invalid rating {} in {}", rating, object.id))
+            .is_ok()
+    })
+}
+
+/// Returns content warning, invalid summaries are ignored
+pub fn get_object_summary(object: &Object) -> Option<String> {
+    let summary = object.summary.as_ref()?;
+    clean_summary(summary)
+        .map_err(|error| log::warn!("invalid summary in {}: {}", object.id, error))
+        .ok()
+        .flatten()
+}
+
+/// Returns language of the content if it is specified in contentMap
+pub fn get_object_language(object: &Object) -> Option<String> {
+    let content_map = object.content_map.as_ref()?.as_object()?;
+    let language = content_map.keys().next()?;
+    match validate_language(language) {
+        Ok(_) => Some(language.clone()),
+        Err(_) => {
+            log::warn!("invalid language {} in {}", language, object.id);
+            None
+        }
+    }
+}
+
 pub fn create_content_link(url: String) -> String {
     format!(r#"<p><a href="{0}" rel="noopener">{0}</a></p>"#, url,)
 }
@@ -312,8 +345,7 @@ pub async fn get_object_tags(
     db_client: &mut impl DatabaseClient,
     instance: &Instance,
     storage: &MediaStorage,
-    api_key: Option<String>,
-    default_movie_user_password: Option<String>,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
     object: &Object,
     redirects: &HashMap<String, String>,
 ) -> Result<(Vec<Uuid>, Vec<String>, Vec<Uuid>, Vec<Uuid>), HandlerError> {
@@ -355,30 +387,23 @@ pub async fn get_object_tags(
             if let Some(href) = tag.href {
                 if let Ok(username) = parse_local_actor_id(&instance.url(), &href) {
                     // Check if local Movie account exists and if not, create the movie, if valid.
-                    let user = match get_user_by_name(db_client, &username).await {
-                        Ok(user) => user,
-                        Err(DatabaseError::NotFound(_)) => {
-                            if let Some(api_key) = &api_key {
-                                log::warn!("failed to find mentioned user by name {}, checking if its a valid movie...", username);
-                                lookup_and_create_movie_user(
-                                    instance,
-                                    db_client,
-                                    api_key,
-                                    &storage.media_dir,
-                                    &username,
-                                    default_movie_user_password.clone(),
-                                )
-                                .await
-                                .map_err(|err| {
-                                    log::warn!("failed to create movie user {username}: {err}");
-                                    HandlerError::LocalObject
-                                })?
-                            } else {
-                                return Err(HandlerError::LocalObject);
-                            }
+                    let user = get_or_create_media_user(
+                        instance,
+                        db_client,
+                        movie_provider,
+                        &storage.media_dir,
+                        &username,
+                    )
+                    .await
+                    .map_err(|err| match err {
+                        MovieError::DatabaseError(db_error) => db_error.into(),
+                        other_error => {
+                            log::warn!(
+                                "failed to find or create movie user {username}: {other_error}"
+                            );
+                            HandlerError::LocalObject
                         }
-                        Err(error) => return Err(error.into()),
-                    };
+                    })?;
                     if !mentions.contains(&user.id) {
                         mentions.push(user.id);
                     };
@@ -533,8 +558,7 @@ pub async fn handle_note(
     db_client: &mut impl DatabaseClient,
     instance: &Instance,
     storage: &MediaStorage,
-    tmdb_api_key: Option<String>,
-    default_movie_user_password: Option<String>,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
     object: Object,
     redirects: &HashMap<String, String>,
 ) -> Result<Post, HandlerError> {
@@ -589,8 +613,7 @@ pub async fn handle_note(
         db_client,
         instance,
         storage,
-        tmdb_api_key,
-        default_movie_user_password,
+        movie_provider,
         &object,
         redirects,
     )
@@ -614,6 +637,9 @@ pub async fn handle_note(
         );
     };
     let is_sensitive = object.sensitive.unwrap_or(false);
+    let summary = get_object_summary(&object);
+    let rating = get_object_rating(&object);
+    let language = get_object_language(&object);
     let created_at = object.published.unwrap_or(Utc::now());
     let post_data = PostCreateData {
         content: content,
@@ -627,6 +653,10 @@ pub async fn handle_note(
         links: links,
         emojis: emojis,
         object_id: Some(object.id),
+        rating,
+        language,
+        summary,
+        content_source: None,
         created_at,
     };
     let post = create_post(db_client, &author.id, post_data).await?;
@@ -683,14 +713,12 @@ pub async fn handle_create(
         None
     };
 
-    let tmdb_api_key = config.tmdb_api_key.clone();
-    let default_movie_user_password = config.movie_user_password.clone();
+    let movie_provider = get_movie_metadata_provider(config);
     import_post(
         db_client,
         &config.instance(),
         &MediaStorage::from(config),
-        tmdb_api_key,
-        default_movie_user_password,
+        movie_provider.as_deref(),
         object_id,
         object_received,
     )
@@ -702,8 +730,13 @@ pub async fn handle_create(
 mod tests {
     use super::*;
     use crate::activitypub::{types::Object, vocabulary::NOTE};
-    use fedimovies_models::profiles::types::DbActor;
+    use crate::tmdb::FileMetadataProvider;
+    use fedimovies_models::{
+        database::test_utils::create_test_database, profiles::types::DbActor,
+        users::queries::get_user_by_name,
+    };
     use serde_json::json;
+    use serial_test::serial;
 
     #[test]
     fn test_get_object_attributed_to() {
@@ -727,6 +760,54 @@ mod tests {
         assert_eq!(content, "test");
     }
 
+    #[test]
+    fn test_get_object_rating() {
+        let object = Object {
+            object_type: NOTE.to_string(),
+            rating_value: Some(4.5),
+            ..Default::default()
+        };
+        assert_eq!(get_object_rating(&object), Some(4.5));
+    }
+
+    #[test]
+    fn test_get_object_rating_invalid() {
+        let object = Object {
+            object_type: NOTE.to_string(),
+            rating_value: Some(11.0),
+            ..Default::default()
+        };
+        assert_eq!(get_object_rating(&object), None);
+    }
+
+    #[test]
+    fn test_get_object_summary() {
+        let object = Object {
+            summary: Some("<p>Spoilers</p>".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(get_object_summary(&object), Some("Spoilers".to_string()));
+        let object = Object {
+            summary: Some("".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(get_object_summary(&object), None);
+    }
+
+    #[test]
+    fn test_get_object_language() {
+        let object = Object {
+            content_map: Some(json!({"pt-BR": "<p>teste</p>"})),
+            ..Default::default()
+        };
+        assert_eq!(get_object_language(&object), Some("pt-BR".to_string()));
+        let object = Object {
+            content_map: Some(json!({"Portuguese": "<p>teste</p>"})),
+            ..Default::default()
+        };
+        assert_eq!(get_object_language(&object), None);
+    }
+
     #[test]
     fn test_get_object_content_from_video() {
         let object = Object {
@@ -807,4 +888,43 @@ mod tests {
         let visibility = get_object_visibility(&author, &audience);
         assert_eq!(visibility, Visibility::Direct);
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_object_tags_create_movie() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let storage = MediaStorage {
+            media_dir: std::env::temp_dir(),
+            file_size_limit: 0,
+            emoji_size_limit: 0,
+        };
+        let movie_provider = FileMetadataProvider::for_test();
+        let object = Object {
+            object_type: NOTE.to_string(),
+            tag: vec![json!({
+                "type": "Mention",
+                "name": "@AvatarTheWayOfWater_2022@example.com",
+                "href": "https://example.com/users/AvatarTheWayOfWater_2022",
+            })],
+            ..Default::default()
+        };
+        let (mentions, hashtags, links, emojis) = get_object_tags(
+            db_client,
+            &instance,
+            &storage,
+            Some(&movie_provider),
+            &object,
+            &HashMap::new(),
+        )
+        .await
+        .unwrap();
+        let user = get_user_by_name(db_client, "AvatarTheWayOfWater_2022_76600")
+            .await
+            .unwrap();
+        assert_eq!(mentions, vec![user.id]);
+        assert!(hashtags.is_empty());
+        assert!(links.is_empty());
+        assert!(emojis.is_empty());
+    }
 }
@@ -14,6 +14,7 @@ use crate::activitypub::{
 };
 use crate::errors::ValidationError;
 use crate::media::remove_media;
+use crate::tmdb::prepare_undo_movie_reposts;
 
 use super::HandlerResult;
 
@@ -61,7 +62,11 @@ pub async fn handle_delete(
     if post.author.id != actor_profile.id {
         return Err(ValidationError("actor is not an author".to_string()).into());
     };
+    let undo_reposts = prepare_undo_movie_reposts(db_client, &config.instance(), &post).await?;
     let deletion_queue = delete_post(db_client, &post.id).await?;
+    for undo_repost in undo_reposts {
+        undo_repost.enqueue(db_client).await?;
+    }
     let config = config.clone();
     tokio::spawn(async move {
         remove_media(&config, deletion_queue).await;
@@ -4,15 +4,22 @@ use serde_json::Value;
 use fedimovies_config::Config;
 use fedimovies_models::{
     database::{DatabaseClient, DatabaseError},
-    relationships::queries::{create_remote_follow_request_opt, follow_request_accepted},
+    notifications::queries::create_follow_request_notification,
+    relationships::queries::{
+        create_remote_follow_request_opt, follow_request_accepted,
+        get_follow_request_by_participants, has_block,
+    },
+    relationships::types::FollowRequestStatus,
     users::queries::get_user_by_name,
 };
 
 use super::{HandlerError, HandlerResult};
 use crate::activitypub::{
-    builders::accept_follow::prepare_accept_follow,
-    fetcher::helpers::get_or_import_profile_by_actor_id, identifiers::parse_local_actor_id,
-    receiver::deserialize_into_object_id, vocabulary::PERSON,
+    builders::{accept_follow::prepare_accept_follow, reject_follow::prepare_reject_follow},
+    fetcher::helpers::get_or_import_profile_by_actor_id,
+    identifiers::parse_local_actor_id,
+    receiver::deserialize_into_object_id,
+    vocabulary::PERSON,
 };
 use crate::errors::ValidationError;
 use crate::media::MediaStorage;
@@ -47,6 +54,27 @@ pub async fn handle_follow(
     let source_actor = source_profile.actor_json.ok_or(HandlerError::LocalObject)?;
     let target_username = parse_local_actor_id(&config.instance_url(), &activity.object)?;
     let target_user = get_user_by_name(db_client, &target_username).await?;
+    if has_block(db_client, &source_profile.id, &target_user.id).await? {
+        // Reject follow requests from blocked actors
+        prepare_reject_follow(
+            &config.instance(),
+            &target_user,
+            &source_actor,
+            &activity.id,
+        )
+        .enqueue(db_client)
+        .await?;
+        return Ok(Some(PERSON));
+    };
+    // Follow activity can be re-sent by remote server
+    let was_pending =
+        match get_follow_request_by_participants(db_client, &source_profile.id, &target_user.id)
+            .await
+        {
+            Ok(follow_request) => follow_request.request_status == FollowRequestStatus::Pending,
+            Err(DatabaseError::NotFound(_)) => false,
+            Err(other_error) => return Err(other_error.into()),
+        };
     let follow_request = create_remote_follow_request_opt(
         db_client,
         &source_profile.id,
@@ -54,6 +82,16 @@ pub async fn handle_follow(
         &activity.id,
     )
     .await?;
+    if target_user.profile.manually_approves_followers
+        && follow_request.request_status == FollowRequestStatus::Pending
+    {
+        // Wait for approval
+        if !was_pending {
+            create_follow_request_notification(db_client, &source_profile.id, &target_user.id)
+                .await?;
+        };
+        return Ok(Some(PERSON));
+    };
     match follow_request_accepted(db_client, &follow_request.id).await {
         Ok(_) => (),
         // Proceed even if relationship already exists
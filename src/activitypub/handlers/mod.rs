@@ -6,6 +6,7 @@ pub type HandlerResult = Result<Option<&'static str>, HandlerError>;
 pub mod accept;
 pub mod add;
 pub mod announce;
+pub mod block;
 pub mod create;
 pub mod delete;
 pub mod follow;
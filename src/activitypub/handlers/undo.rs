@@ -7,13 +7,13 @@ use fedimovies_models::{
     posts::queries::{delete_post, get_post_by_remote_object_id},
     profiles::queries::{get_profile_by_acct, get_profile_by_remote_actor_id},
     reactions::queries::{delete_reaction, get_reaction_by_remote_activity_id},
-    relationships::queries::{get_follow_request_by_activity_id, unfollow},
+    relationships::queries::{get_follow_request_by_activity_id, unblock, unfollow},
 };
 
 use crate::activitypub::{
     identifiers::parse_local_actor_id,
     receiver::{deserialize_into_object_id, find_object_id},
-    vocabulary::{ANNOUNCE, FOLLOW, LIKE},
+    vocabulary::{ANNOUNCE, BLOCK, FOLLOW, LIKE},
 };
 use crate::errors::ValidationError;
 
@@ -50,6 +50,37 @@ async fn handle_undo_follow(
     Ok(Some(FOLLOW))
 }
 
+#[derive(Deserialize)]
+struct UndoBlock {
+    actor: String,
+    object: Value,
+}
+
+async fn handle_undo_block(
+    config: &Config,
+    db_client: &impl DatabaseClient,
+    activity: Value,
+) -> HandlerResult {
+    let activity: UndoBlock = serde_json::from_value(activity.clone()).map_err(|_| {
+        ValidationError(format!(
+            "unexpected UndoBlock activity structure: {}",
+            activity
+        ))
+    })?;
+    let source_profile = get_profile_by_remote_actor_id(db_client, &activity.actor).await?;
+    let target_actor_id = find_object_id(&activity.object["object"])?;
+    let target_username = parse_local_actor_id(&config.instance_url(), &target_actor_id)?;
+    // acct equals username if profile is local
+    let target_profile = get_profile_by_acct(db_client, &target_username).await?;
+    match unblock(db_client, &source_profile.id, &target_profile.id).await {
+        Ok(_) => (),
+        // Ignore Undo if relationship doesn't exist
+        Err(DatabaseError::NotFound(_)) => return Ok(None),
+        Err(other_error) => return Err(other_error.into()),
+    };
+    Ok(Some(BLOCK))
+}
+
 #[derive(Deserialize)]
 struct Undo {
     actor: String,
@@ -66,6 +97,10 @@ pub async fn handle_undo(
         // Undo() with nested follow activity
         return handle_undo_follow(config, db_client, activity).await;
     };
+    if let Some(BLOCK) = activity["object"]["type"].as_str() {
+        // Undo() with nested block activity
+        return handle_undo_block(config, db_client, activity).await;
+    };
 
     let activity: Undo = serde_json::from_value(activity.clone()).map_err(|_| {
         ValidationError(format!("unexpected Undo activity structure: {}", activity))
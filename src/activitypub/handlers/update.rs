@@ -7,7 +7,7 @@ use serde_json::Value;
 use fedimovies_config::Config;
 use fedimovies_models::{
     database::{DatabaseClient, DatabaseError},
-    posts::queries::{get_post_by_remote_object_id, update_post},
+    posts::queries::{get_post_by_id, get_post_by_remote_object_id, update_post},
     posts::types::PostUpdateData,
     profiles::queries::get_profile_by_remote_actor_id,
 };
@@ -15,15 +15,16 @@ use fedimovies_models::{
 use crate::activitypub::{
     actors::{helpers::update_remote_profile, types::Actor},
     handlers::create::{
-        create_content_link, get_object_attachments, get_object_content, get_object_tags,
-        get_object_url,
+        create_content_link, get_object_attachments, get_object_content, get_object_language,
+        get_object_rating, get_object_summary, get_object_tags, get_object_url,
     },
     identifiers::profile_actor_id,
     types::Object,
     vocabulary::{NOTE, PERSON},
 };
 use crate::errors::ValidationError;
-use crate::media::MediaStorage;
+use crate::media::{remove_media, MediaStorage};
+use crate::tmdb::{get_movie_metadata_provider, undo_unmentioned_movie_reposts};
 
 use super::HandlerResult;
 
@@ -58,6 +59,9 @@ async fn handle_update_note(
         content += &create_content_link(object_url);
     };
     let is_sensitive = object.sensitive.unwrap_or(false);
+    let summary = get_object_summary(&object);
+    let rating = get_object_rating(&object);
+    let language = get_object_language(&object);
     let storage = MediaStorage::from(config);
     let (attachments, unprocessed) =
         get_object_attachments(db_client, &instance, &storage, &object, &post.author).await?;
@@ -68,14 +72,12 @@ async fn handle_update_note(
         return Err(ValidationError("post is empty".to_string()).into());
     };
 
-    let tmdb_api_key = config.tmdb_api_key.clone();
-    let default_movie_user_password = config.movie_user_password.clone();
+    let movie_provider = get_movie_metadata_provider(config);
     let (mentions, hashtags, links, emojis) = get_object_tags(
         db_client,
         &instance,
         &storage,
-        tmdb_api_key,
-        default_movie_user_password,
+        movie_provider.as_deref(),
         &object,
         &HashMap::new(),
     )
@@ -83,7 +85,11 @@ async fn handle_update_note(
     let updated_at = object.updated.unwrap_or(Utc::now());
     let post_data = PostUpdateData {
         content,
+        content_source: None,
         is_sensitive,
+        rating,
+        language,
+        summary,
         attachments,
         mentions,
         tags: hashtags,
@@ -91,7 +97,13 @@ async fn handle_update_note(
         emojis,
         updated_at,
     };
-    update_post(db_client, &post.id, post_data).await?;
+    let deletion_queue = update_post(db_client, &post.id, post_data).await?;
+    let config = config.clone();
+    tokio::spawn(async move {
+        remove_media(&config, deletion_queue).await;
+    });
+    let post = get_post_by_id(db_client, &post.id).await?;
+    undo_unmentioned_movie_reposts(db_client, &instance, &post).await?;
     Ok(Some(NOTE))
 }
 
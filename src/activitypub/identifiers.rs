@@ -14,6 +14,7 @@ pub enum LocalActorCollection {
     Followers,
     Following,
     Subscribers,
+    Watchlist,
 }
 
 impl LocalActorCollection {
@@ -24,6 +25,7 @@ impl LocalActorCollection {
             Self::Followers => "followers",
             Self::Following => "following",
             Self::Subscribers => "subscribers",
+            Self::Watchlist => "watchlist",
         };
         format!("{}/{}", actor_id, name)
     }
@@ -59,6 +61,11 @@ pub fn local_actor_subscribers(instance_url: &str, username: &str) -> String {
     LocalActorCollection::Subscribers.of(&actor_id)
 }
 
+pub fn local_actor_watchlist(instance_url: &str, username: &str) -> String {
+    let actor_id = local_actor_id(instance_url, username);
+    LocalActorCollection::Watchlist.of(&actor_id)
+}
+
 pub fn local_instance_actor_id(instance_url: &str) -> String {
     format!("{}/actor", instance_url)
 }
@@ -79,6 +86,14 @@ pub fn local_tag_collection(instance_url: &str, tag_name: &str) -> String {
     format!("{}/collections/tags/{}", instance_url, tag_name)
 }
 
+pub fn local_movie_list_id(instance_url: &str, list_id: &Uuid) -> String {
+    format!("{}/collections/lists/{}", instance_url, list_id)
+}
+
+pub fn local_view_activity_id(instance_url: &str, entry_id: &Uuid) -> String {
+    format!("{}/activities/views/{}", instance_url, entry_id)
+}
+
 pub fn validate_object_id(object_id: &str) -> Result<(), ValidationError> {
     get_hostname(object_id).map_err(|_| ValidationError("invalid object ID".to_string()))?;
     Ok(())
@@ -11,6 +11,7 @@ use super::handlers::{
     accept::handle_accept,
     add::handle_add,
     announce::handle_announce,
+    block::handle_block,
     create::{handle_create, is_unsolicited_message, CreateNote},
     delete::handle_delete,
     follow::handle_follow,
@@ -144,6 +145,7 @@ pub async fn handle_activity(
         ACCEPT => handle_accept(config, db_client, activity).await?,
         ADD => handle_add(config, db_client, activity).await?,
         ANNOUNCE => handle_announce(config, db_client, activity).await?,
+        BLOCK => handle_block(config, db_client, activity).await?,
         CREATE => handle_create(config, db_client, activity, is_authenticated).await?,
         DELETE => handle_delete(config, db_client, activity).await?,
         FOLLOW => handle_follow(config, db_client, activity).await?,
@@ -112,8 +112,11 @@ pub struct Object {
     pub attributed_to: Option<Value>,
     pub in_reply_to: Option<String>,
     pub content: Option<String>,
+    pub content_map: Option<Value>,
     pub quote_url: Option<String>,
+    pub rating_value: Option<f32>,
     pub sensitive: Option<bool>,
+    pub summary: Option<String>,
 
     #[serde(default, deserialize_with = "deserialize_value_array")]
     pub tag: Vec<Value>,
@@ -151,6 +154,7 @@ pub fn build_default_context() -> Context {
             "schema": SCHEMA_ORG_CONTEXT,
             "PropertyValue": "schema:PropertyValue",
             "value": "schema:value",
+            "ratingValue": "schema:ratingValue",
             "IdentityProof": "toot:IdentityProof",
             "discoverable": "toot:discoverable",
             "Device": "toot:Device",
@@ -12,20 +12,26 @@ use fedimovies_config::Config;
 use fedimovies_models::{
     database::{get_database_client, DatabaseError, DbPool},
     emojis::queries::get_local_emoji_by_name,
+    movie_lists::queries::get_movie_list,
     posts::helpers::{add_related_posts, can_view_post},
     posts::queries::{get_post_by_id, get_posts_by_author},
     users::queries::get_user_by_name,
+    watch_log::queries::get_watch_log_entry,
+    watchlist::queries::{get_watchlist, get_watchlist_size},
 };
 
 use super::actors::types::{get_instance_actor, get_local_actor};
 use super::builders::{
     announce::build_announce,
+    create_movie_list::build_movie_list,
     create_note::{build_create_note, build_emoji_tag, build_note},
+    view_movie::build_view_movie,
 };
 use super::collections::{OrderedCollection, OrderedCollectionPage};
 use super::constants::{AP_MEDIA_TYPE, AS_MEDIA_TYPE};
 use super::identifiers::{
     local_actor_followers, local_actor_following, local_actor_outbox, local_actor_subscribers,
+    local_actor_watchlist, profile_actor_id,
 };
 use super::receiver::{receive_activity, HandlerError};
 use crate::errors::HttpError;
@@ -245,6 +251,48 @@ async fn subscribers_collection(
     Ok(response)
 }
 
+#[get("/watchlist")]
+async fn watchlist_collection(
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    username: web::Path<String>,
+    query_params: web::Query<CollectionQueryParams>,
+) -> Result<HttpResponse, HttpError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let user = get_user_by_name(db_client, &username).await?;
+    if !user.show_watchlist {
+        // Watchlist is hidden
+        return Err(HttpError::PermissionError);
+    };
+    let instance_url = config.instance_url();
+    let collection_id = local_actor_watchlist(&instance_url, &username);
+    let first_page_id = format!("{}?page=true", collection_id);
+    if query_params.page.is_none() {
+        let watchlist_size = get_watchlist_size(db_client, &user.id).await?;
+        let collection = OrderedCollection::new(
+            collection_id,
+            Some(first_page_id),
+            i32::try_from(watchlist_size).ok(),
+        );
+        let response = HttpResponse::Ok()
+            .content_type(AP_MEDIA_TYPE)
+            .json(collection);
+        return Ok(response);
+    };
+    // Movies are ordered by the time they were added
+    const COLLECTION_PAGE_SIZE: u16 = 20;
+    let entries = get_watchlist(db_client, &user.id, None, COLLECTION_PAGE_SIZE).await?;
+    let items = entries
+        .iter()
+        .map(|entry| profile_actor_id(&instance_url, &entry.movie).into())
+        .collect();
+    let collection_page = OrderedCollectionPage::new(first_page_id, items);
+    let response = HttpResponse::Ok()
+        .content_type(AP_MEDIA_TYPE)
+        .json(collection_page);
+    Ok(response)
+}
+
 pub fn actor_scope() -> Scope {
     web::scope("/users/{username}")
         .service(actor_view)
@@ -254,6 +302,7 @@ pub fn actor_scope() -> Scope {
         .service(followers_collection)
         .service(following_collection)
         .service(subscribers_collection)
+        .service(watchlist_collection)
 }
 
 #[get("")]
@@ -341,6 +390,46 @@ pub async fn tag_view(
     Ok(response)
 }
 
+#[get("/collections/lists/{list_id}")]
+pub async fn movie_list_view(
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    request: HttpRequest,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HttpError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let list = get_movie_list(db_client, &list_id).await?;
+    if !is_activitypub_request(request.headers()) {
+        let page_url = get_profile_page_url(&config.instance_url(), &list.owner.username);
+        let response = HttpResponse::Found()
+            .append_header((http_header::LOCATION, page_url))
+            .finish();
+        return Ok(response);
+    };
+    let object = build_movie_list(&config.instance_url(), &list);
+    let response = HttpResponse::Ok().content_type(AP_MEDIA_TYPE).json(object);
+    Ok(response)
+}
+
+#[get("/activities/views/{entry_id}")]
+pub async fn view_movie_activity_view(
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    entry_id: web::Path<Uuid>,
+) -> Result<HttpResponse, HttpError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let entry = get_watch_log_entry(db_client, &entry_id).await?;
+    // Activity exists only if watch log entry was federated
+    if !entry.is_federated {
+        return Err(HttpError::NotFoundError("activity"));
+    };
+    let activity = build_view_movie(&config.instance_url(), &entry);
+    let response = HttpResponse::Ok()
+        .content_type(AP_MEDIA_TYPE)
+        .json(activity);
+    Ok(response)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,6 +4,7 @@
 pub const ACCEPT: &str = "Accept";
 pub const ADD: &str = "Add";
 pub const ANNOUNCE: &str = "Announce";
+pub const BLOCK: &str = "Block";
 pub const CREATE: &str = "Create";
 pub const DELETE: &str = "Delete";
 pub const DISLIKE: &str = "Dislike";
@@ -16,8 +17,10 @@ pub const REJECT: &str = "Reject";
 pub const REMOVE: &str = "Remove";
 pub const UNDO: &str = "Undo";
 pub const UPDATE: &str = "Update";
+pub const VIEW: &str = "View";
 
 // Actor types
+pub const APPLICATION: &str = "Application";
 pub const GROUP: &str = "Group";
 pub const PERSON: &str = "Person";
 pub const SERVICE: &str = "Service";
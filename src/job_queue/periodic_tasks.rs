@@ -1,12 +1,17 @@
 use anyhow::Error;
 
 use crate::activitypub::builders::announce::prepare_announce;
+use crate::activitypub::builders::update_person::prepare_update_person;
 use fedimovies_config::Config;
-use fedimovies_models::database::DatabaseError;
+use fedimovies_models::database::{DatabaseClient, DatabaseError};
 use fedimovies_models::notifications::queries::{delete_notification, get_mention_notifications};
+use fedimovies_models::notifications::types::Notification;
 use fedimovies_models::posts::queries::create_post;
 use fedimovies_models::posts::types::PostCreateData;
-use fedimovies_models::users::queries::get_user_by_id;
+use fedimovies_models::profiles::queries::update_profile;
+use fedimovies_models::profiles::types::ProfileUpdateData;
+use fedimovies_models::trends::queries::update_trends as update_cached_trends;
+use fedimovies_models::users::queries::{find_outdated_media_users, get_user_by_id};
 use fedimovies_models::{
     database::{get_database_client, DbPool},
     emojis::queries::{delete_emoji, find_unused_remote_emojis},
@@ -19,6 +24,14 @@ use crate::activitypub::queues::{
     process_queued_incoming_activities, process_queued_outgoing_activities,
 };
 use crate::media::remove_media;
+use crate::tmdb::{
+    check_boost_policy, get_movie_metadata_provider, process_queued_movie_credits,
+    refresh_media_user,
+};
+
+const TRENDS_PERIOD_DAYS: u32 = 7;
+const TRENDS_LIMIT: u16 = 100;
+const MOVIE_REFRESH_BATCH_SIZE: u16 = 10;
 
 pub async fn incoming_activity_queue_executor(
     config: &Config,
@@ -79,70 +92,152 @@ pub async fn prune_remote_emojis(config: &Config, db_pool: &DbPool) -> Result<()
     Ok(())
 }
 
-// Finds mention notifications and repost them
-pub async fn handle_movies_mentions(
-    config: &Config,
-    db_pool: &DbPool,
-) -> Result<(), anyhow::Error> {
+pub async fn refresh_movie_metadata(config: &Config, db_pool: &DbPool) -> Result<(), Error> {
+    let movie_provider = match get_movie_metadata_provider(config) {
+        Some(movie_provider) => movie_provider,
+        None => return Ok(()), // not configured
+    };
     let db_client = &mut **get_database_client(db_pool).await?;
-    log::debug!("Reviewing mentions..");
-    // for each mention notification do repost
-    let mut transaction = db_client.transaction().await?;
+    let instance = config.instance();
+    let updated_before = days_before_now(config.tmdb_refresh_days);
+    let users =
+        find_outdated_media_users(db_client, &updated_before, MOVIE_REFRESH_BATCH_SIZE).await?;
+    for user in users {
+        match refresh_media_user(
+            &instance,
+            db_client,
+            movie_provider.as_ref(),
+            &config.media_dir(),
+            &user,
+        )
+        .await
+        {
+            Ok((user, deletion_queue, is_changed)) => {
+                remove_media(config, deletion_queue).await;
+                if is_changed {
+                    prepare_update_person(db_client, &instance, &user, None)
+                        .await?
+                        .enqueue(db_client)
+                        .await?;
+                    log::info!("refreshed metadata of {}", user.profile.username);
+                };
+            }
+            Err(error) => {
+                log::warn!(
+                    "failed to refresh metadata of {}: {}",
+                    user.profile.username,
+                    error,
+                );
+                // Touch the profile to postpone the next attempt
+                let profile_data = ProfileUpdateData::from(&user.profile);
+                if let Err(error) = update_profile(db_client, &user.id, profile_data).await {
+                    log::error!(
+                        "failed to postpone refresh of {}: {}",
+                        user.profile.username,
+                        error,
+                    );
+                };
+            }
+        };
+    }
+    Ok(())
+}
 
-    let mention_notifications = match get_mention_notifications(&transaction, 50).await {
-        Ok(mention_notifications) => mention_notifications,
-        Err(DatabaseError::DatabaseClientError(err)) => {
-            return Err(anyhow::anyhow!("Error in client: {err}"))
-        }
-        Err(err) => return Err(err.into()),
-    };
+pub async fn movie_credits_queue_executor(config: &Config, db_pool: &DbPool) -> Result<(), Error> {
+    let db_client = &mut **get_database_client(db_pool).await?;
+    process_queued_movie_credits(config, db_client).await?;
+    Ok(())
+}
 
-    for mention_notification in mention_notifications {
-        log::info!("Reviewing mention notification {}", mention_notification.id);
-        if let Some(post_with_mention) = mention_notification.post {
-            // Does not repost private posts or reposts
-            if !post_with_mention.is_public() || post_with_mention.repost_of_id.is_some() {
-                continue;
-            }
-            let mut post = post_with_mention.clone();
-            let post_id = post.id;
-            let current_user =
-                get_user_by_id(&transaction, &mention_notification.recipient.id).await?;
+pub async fn update_trends(_config: &Config, db_pool: &DbPool) -> Result<(), Error> {
+    let db_client = &**get_database_client(db_pool).await?;
+    let created_after = days_before_now(TRENDS_PERIOD_DAYS);
+    let trends = update_cached_trends(db_client, &created_after, TRENDS_LIMIT).await?;
+    log::info!(
+        "updated trends: {} posts, {} tags, {} movies",
+        trends.posts.len(),
+        trends.tags.len(),
+        trends.movies.len(),
+    );
+    Ok(())
+}
 
-            // Repost
-            let repost_data = PostCreateData::repost(post.id, None);
-            let mut repost =
-                match create_post(&mut transaction, &current_user.id, repost_data).await {
-                    Ok(repost) => repost,
-                    Err(DatabaseError::AlreadyExists(_err)) => {
+async fn handle_movie_mention(
+    config: &Config,
+    db_client: &mut impl DatabaseClient,
+    notification: Notification,
+) -> Result<(), DatabaseError> {
+    let mut transaction = db_client.transaction().await?;
+    if let Some(mut post) = notification.post {
+        let movie_user = get_user_by_id(&transaction, &notification.recipient.id).await?;
+        match check_boost_policy(&transaction, config, &movie_user.profile, &post).await? {
+            Some(reason) => {
+                log::info!(
+                    "{} will not repost {}: {}",
+                    movie_user.profile.username,
+                    post.id,
+                    reason,
+                );
+            }
+            None => {
+                let repost_data = PostCreateData::repost(post.id, None);
+                match create_post(&mut transaction, &movie_user.id, repost_data).await {
+                    Ok(mut repost) => {
+                        let post_id = post.id;
+                        post.repost_count += 1;
+                        repost.repost_of = Some(Box::new(post));
+                        prepare_announce(&transaction, &config.instance(), &movie_user, &repost)
+                            .await?
+                            .enqueue(&transaction)
+                            .await?;
+                        log::info!("{} reposted {}", movie_user.profile.username, post_id);
+                    }
+                    Err(DatabaseError::AlreadyExists(_)) => {
                         log::info!(
-                            "Review as Mention of {} already reposted the post with id {}",
-                            current_user.profile.username,
-                            post_id
+                            "{} has already reposted {}",
+                            movie_user.profile.username,
+                            post.id,
                         );
-                        delete_notification(&transaction, mention_notification.id).await?;
-                        continue;
                     }
-                    Err(err) => return Err(err.into()),
+                    Err(other_error) => return Err(other_error),
                 };
-            post.repost_count += 1;
-            repost.repost_of = Some(Box::new(post));
-
-            // Federate
-            prepare_announce(&transaction, &config.instance(), &current_user, &repost)
-                .await?
-                .enqueue(&transaction)
-                .await?;
-
-            // Delete notification to avoid re-processing
-            delete_notification(&transaction, mention_notification.id).await?;
+            }
+        };
+    };
+    // Delete notification to avoid re-processing
+    delete_notification(&transaction, notification.id).await?;
+    transaction.commit().await?;
+    Ok(())
+}
 
-            log::info!(
-                "Review as Mention of {} reposted with post id {}",
-                current_user.profile.username,
-                post_id
-            );
-        }
+// Finds mention notifications and reposts mentioning posts
+// if they are allowed by boost policy
+pub async fn handle_movies_mentions(config: &Config, db_pool: &DbPool) -> Result<(), Error> {
+    let db_client = &mut **get_database_client(db_pool).await?;
+    let notifications = get_mention_notifications(db_client, 50).await?;
+    for notification in notifications {
+        let notification_id = notification.id;
+        match handle_movie_mention(config, db_client, notification).await {
+            Ok(()) => (),
+            Err(error @ (DatabaseError::NotFound(_) | DatabaseError::DatabaseTypeError(_))) => {
+                // Permanent error, delete notification
+                // to prevent it from blocking the queue
+                log::warn!(
+                    "failed to handle mention notification {}: {}",
+                    notification_id,
+                    error,
+                );
+                delete_notification(db_client, notification_id).await?;
+            }
+            Err(other_error) => {
+                // Failures are isolated, notification will be processed again
+                log::error!(
+                    "failed to handle mention notification {}: {}",
+                    notification_id,
+                    other_error,
+                );
+            }
+        };
     }
-    Ok(transaction.commit().await?)
+    Ok(())
 }
@@ -17,6 +17,9 @@ enum PeriodicTask {
     DeleteEmptyProfiles,
     PruneRemoteEmojis,
     HandleMoviesMentions,
+    RefreshMovieMetadata,
+    MovieCreditsQueueExecutor,
+    UpdateTrends,
 }
 
 impl PeriodicTask {
@@ -30,6 +33,9 @@ impl PeriodicTask {
             Self::DeleteEmptyProfiles => 3600,
             Self::PruneRemoteEmojis => 3600,
             Self::HandleMoviesMentions => 5,
+            Self::RefreshMovieMetadata => 3600,
+            Self::MovieCreditsQueueExecutor => 5,
+            Self::UpdateTrends => 900,
         }
     }
 
@@ -52,6 +58,7 @@ pub fn run(config: Config, db_pool: DbPool) -> () {
             (PeriodicTask::OutgoingActivityQueueExecutor, None),
             (PeriodicTask::PruneRemoteEmojis, None),
             (PeriodicTask::HandleMoviesMentions, None),
+            (PeriodicTask::UpdateTrends, None),
         ]);
         if config.retention.extraneous_posts.is_some() {
             scheduler_state.insert(PeriodicTask::DeleteExtraneousPosts, None);
@@ -59,6 +66,10 @@ pub fn run(config: Config, db_pool: DbPool) -> () {
         if config.retention.empty_profiles.is_some() {
             scheduler_state.insert(PeriodicTask::DeleteEmptyProfiles, None);
         };
+        if config.tmdb_api_key.is_some() {
+            scheduler_state.insert(PeriodicTask::RefreshMovieMetadata, None);
+            scheduler_state.insert(PeriodicTask::MovieCreditsQueueExecutor, None);
+        };
 
         let mut interval = tokio::time::interval(Duration::from_secs(5));
         loop {
@@ -86,6 +97,13 @@ pub fn run(config: Config, db_pool: DbPool) -> () {
                     PeriodicTask::HandleMoviesMentions => {
                         handle_movies_mentions(&config, &db_pool).await
                     }
+                    PeriodicTask::RefreshMovieMetadata => {
+                        refresh_movie_metadata(&config, &db_pool).await
+                    }
+                    PeriodicTask::MovieCreditsQueueExecutor => {
+                        movie_credits_queue_executor(&config, &db_pool).await
+                    }
+                    PeriodicTask::UpdateTrends => update_trends(&config, &db_pool).await,
                 };
                 task_result.unwrap_or_else(|err| {
                     log::error!("{:?}: {}", task, err);
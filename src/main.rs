@@ -14,11 +14,16 @@ use fedimovies::job_queue::scheduler;
 use fedimovies::logger::configure_logger;
 use fedimovies::mastodon_api::accounts::views::account_api_scope;
 use fedimovies::mastodon_api::apps::views::application_api_scope;
+use fedimovies::mastodon_api::bookmarks::views::bookmark_api_scope;
 use fedimovies::mastodon_api::custom_emojis::views::custom_emoji_api_scope;
 use fedimovies::mastodon_api::directory::views::directory_api_scope;
+use fedimovies::mastodon_api::follow_requests::views::follow_request_api_scope;
 use fedimovies::mastodon_api::instance::views::instance_api_scope;
+use fedimovies::mastodon_api::lists::views::list_api_scope;
 use fedimovies::mastodon_api::markers::views::marker_api_scope;
 use fedimovies::mastodon_api::media::views::media_api_scope;
+use fedimovies::mastodon_api::movie_lists::views::movie_list_api_scope;
+use fedimovies::mastodon_api::movies::views::movie_api_scope;
 use fedimovies::mastodon_api::notifications::views::notification_api_scope;
 use fedimovies::mastodon_api::oauth::views::oauth_api_scope;
 use fedimovies::mastodon_api::search::views::search_api_scope;
@@ -26,6 +31,9 @@ use fedimovies::mastodon_api::settings::views::settings_api_scope;
 use fedimovies::mastodon_api::statuses::views::status_api_scope;
 use fedimovies::mastodon_api::subscriptions::views::subscription_api_scope;
 use fedimovies::mastodon_api::timelines::views::timeline_api_scope;
+use fedimovies::mastodon_api::trends::views::trends_api_scope;
+use fedimovies::mastodon_api::watch_log::views::watch_log_api_scope;
+use fedimovies::mastodon_api::watchlist::views::watchlist_api_scope;
 use fedimovies::nodeinfo::views as nodeinfo;
 use fedimovies::web_client::views as web_client;
 use fedimovies::webfinger::views as webfinger;
@@ -126,23 +134,33 @@ async fn main() -> std::io::Result<()> {
             .service(oauth_api_scope())
             .service(account_api_scope())
             .service(application_api_scope())
+            .service(bookmark_api_scope())
             .service(custom_emoji_api_scope())
             .service(directory_api_scope())
+            .service(follow_request_api_scope())
             .service(instance_api_scope())
+            .service(list_api_scope())
             .service(marker_api_scope())
             .service(media_api_scope())
+            .service(movie_list_api_scope())
+            .service(movie_api_scope())
             .service(notification_api_scope())
             .service(search_api_scope())
             .service(settings_api_scope())
             .service(status_api_scope())
             .service(subscription_api_scope())
             .service(timeline_api_scope())
+            .service(trends_api_scope())
+            .service(watch_log_api_scope())
+            .service(watchlist_api_scope())
             .service(webfinger::webfinger_view)
             .service(activitypub::actor_scope())
             .service(activitypub::instance_actor_scope())
             .service(activitypub::object_view)
             .service(activitypub::emoji_view)
             .service(activitypub::tag_view)
+            .service(activitypub::movie_list_view)
+            .service(activitypub::view_movie_activity_view)
             .service(atom_scope())
             .service(nodeinfo::get_nodeinfo_jrd)
             .service(nodeinfo::get_nodeinfo_2_0)
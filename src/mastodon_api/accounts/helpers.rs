@@ -57,6 +57,13 @@ pub async fn get_relationship(
                     relationship_map.muting = true;
                 };
             }
+            RelationshipType::Block => {
+                if relationship.is_direct(source_id, target_id)? {
+                    relationship_map.blocking = true;
+                } else {
+                    relationship_map.blocked_by = true;
+                };
+            }
         };
     }
     Ok(relationship_map)
@@ -5,7 +5,9 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use fedimovies_models::{
-    profiles::types::{DbActorProfile, ExtraField, PaymentOption, ProfileImage, ProfileUpdateData},
+    profiles::types::{
+        DbActorProfile, ExtraField, PaymentOption, ProfileImage, ProfileUpdateData, RatingStats,
+    },
     subscriptions::types::Subscription,
     users::types::{ClientConfig, Permission, Role, User},
 };
@@ -37,11 +39,38 @@ pub enum AccountPaymentOption {
     EthereumSubscription,
     MoneroSubscription { price: u64 },
 }
+
+/// Aggregated ratings of reviews mentioning the account
+#[derive(Serialize)]
+pub struct AccountRating {
+    pub average: Option<f32>,
+    /// Number of ratings for each half-star step (0.5 - 5.0)
+    pub histogram: Vec<i32>,
+    pub ratings_count: i32,
+    pub reviews_count: i32,
+}
+
+impl AccountRating {
+    fn from_stats(rating_stats: &RatingStats) -> Option<Self> {
+        if rating_stats.review_count == 0 {
+            return None;
+        };
+        let account_rating = Self {
+            average: rating_stats.average_rating(),
+            histogram: rating_stats.histogram.to_vec(),
+            ratings_count: rating_stats.rating_count(),
+            reviews_count: rating_stats.review_count,
+        };
+        Some(account_rating)
+    }
+}
+
 /// https://docs.joinmastodon.org/entities/source/
 #[derive(Serialize)]
 pub struct Source {
     pub note: Option<String>,
     pub fields: Vec<AccountField>,
+    pub show_watchlist: bool,
 }
 
 /// https://docs.joinmastodon.org/entities/Role/
@@ -55,7 +84,7 @@ pub struct ApiRole {
 impl ApiRole {
     fn from_db(role: Role) -> Self {
         let role_name = match role {
-            Role::Guest => unimplemented!(),
+            Role::Guest => "guest",
             Role::NormalUser => "user",
             Role::Admin => "admin",
             Role::ReadOnlyUser => "read_only_user",
@@ -106,6 +135,9 @@ pub struct Account {
     pub subscribers_count: i32,
     pub statuses_count: i32,
 
+    // Extra fields
+    pub rating: Option<AccountRating>,
+
     // CredentialAccount attributes
     pub source: Option<Source>,
     pub role: Option<ApiRole>,
@@ -179,6 +211,12 @@ impl Account {
             .into_iter()
             .map(|db_emoji| CustomEmoji::from_db(base_url, db_emoji))
             .collect();
+        // Only media accounts are reviewed
+        let rating = if profile.account_kind.is_media() {
+            AccountRating::from_stats(&profile.rating_stats)
+        } else {
+            None
+        };
 
         Self {
             id: profile.id,
@@ -200,6 +238,7 @@ impl Account {
             following_count: profile.following_count,
             subscribers_count: profile.subscriber_count,
             statuses_count: profile.post_count,
+            rating,
             source: None,
             role: None,
             client_config: None,
@@ -222,6 +261,7 @@ impl Account {
         let source = Source {
             note: user.profile.bio_source.clone(),
             fields: fields_sources,
+            show_watchlist: user.show_watchlist,
         };
         let role = ApiRole::from_db(user.role);
         let mut account = Self::from_profile(base_url, instance_url, user.profile);
@@ -259,6 +299,8 @@ pub struct AccountUpdateData {
     header: Option<String>,
     header_media_type: Option<String>,
     fields_attributes: Option<Vec<AccountFieldSource>>,
+    locked: Option<bool>,
+    pub show_watchlist: Option<bool>,
 }
 
 fn process_b64_image_field_value(
@@ -335,7 +377,7 @@ impl AccountUpdateData {
             bio_source: self.note,
             avatar,
             banner,
-            manually_approves_followers: false,
+            manually_approves_followers: self.locked.unwrap_or(profile.manually_approves_followers),
             identity_proofs,
             payment_options,
             extra_fields,
@@ -403,6 +445,8 @@ pub struct RelationshipMap {
     pub showing_reblogs: bool,
     pub showing_replies: bool,
     pub muting: bool,
+    pub blocking: bool,
+    pub blocked_by: bool,
 }
 
 fn default_showing_reblogs() -> bool {
@@ -425,6 +469,8 @@ impl Default for RelationshipMap {
             showing_reblogs: default_showing_reblogs(),
             showing_replies: default_showing_replies(),
             muting: false,
+            blocking: false,
+            blocked_by: false,
         }
     }
 }
@@ -529,7 +575,7 @@ pub struct Aliases {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use fedimovies_models::profiles::types::ProfileImage;
+    use fedimovies_models::profiles::types::{AccountKind, ProfileImage};
 
     const INSTANCE_URL: &str = "https://example.com";
 
@@ -548,6 +594,34 @@ mod tests {
         assert!(account.source.is_none());
     }
 
+    #[test]
+    fn test_create_account_with_rating() {
+        let mut rating_stats = RatingStats {
+            review_count: 2,
+            ..Default::default()
+        };
+        rating_stats.histogram[7] = 1;
+        let profile = DbActorProfile {
+            account_kind: AccountKind::Movie,
+            rating_stats: rating_stats.clone(),
+            ..Default::default()
+        };
+        let account = Account::from_profile(INSTANCE_URL, INSTANCE_URL, profile);
+        let rating = account.rating.unwrap();
+
+        assert_eq!(rating.average, Some(4.0));
+        assert_eq!(rating.ratings_count, 1);
+        assert_eq!(rating.reviews_count, 2);
+
+        // Regular accounts don't have ratings
+        let profile = DbActorProfile {
+            rating_stats,
+            ..Default::default()
+        };
+        let account = Account::from_profile(INSTANCE_URL, INSTANCE_URL, profile);
+        assert!(account.rating.is_none());
+    }
+
     #[test]
     fn test_create_account_from_user() {
         let bio_source = "test";
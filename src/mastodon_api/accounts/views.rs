@@ -6,18 +6,22 @@ use fedimovies_config::{Config, DefaultRole, RegistrationType};
 use fedimovies_models::relationships::queries::{mute_posts, unmute_posts};
 use fedimovies_models::{
     database::{get_database_client, DatabaseError, DbPool},
+    movie_lists::queries::get_movie_lists_by_owner,
     posts::queries::get_posts_by_author,
     profiles::helpers::find_verified_aliases,
     profiles::queries::{
         get_profile_by_acct, get_profile_by_id, search_profiles_by_did, update_profile,
     },
+    profiles::types::AccountKind,
     relationships::queries::{
-        get_followers_paginated, get_following_paginated, hide_replies, hide_reposts, show_replies,
-        show_reposts, unfollow,
+        block, get_followers_paginated, get_following_paginated, has_block, hide_replies,
+        hide_reposts, show_replies, show_reposts, unblock, unfollow,
     },
     subscriptions::queries::get_incoming_subscriptions,
-    users::queries::{create_user, is_valid_invite_code},
+    users::queries::{create_user, get_user_by_id, is_valid_invite_code, set_watchlist_visibility},
     users::types::{Role, UserCreateData},
+    watch_log::queries::{get_watch_log_by_movie, get_watch_log_by_owner},
+    watchlist::queries::get_watchlist,
 };
 use fedimovies_utils::{
     canonicalization::canonicalize_object,
@@ -34,16 +38,25 @@ use super::types::{
     SearchDidQueryParams, StatusListQueryParams, UnsignedActivity,
 };
 use crate::activitypub::builders::{
+    block::prepare_block,
     follow::follow_or_create_request,
+    undo_block::prepare_undo_block,
     undo_follow::prepare_undo_follow,
     update_person::{build_update_person, prepare_update_person},
 };
 use crate::errors::ValidationError;
 use crate::http::{get_request_base_url, FormOrJson};
 use crate::mastodon_api::{
-    errors::MastodonError, oauth::auth::get_current_user, pagination::get_paginated_response,
-    search::helpers::search_profiles_only, statuses::helpers::build_status_list,
+    errors::MastodonError,
+    follow_requests::helpers::accept_pending_follow_requests,
+    movie_lists::types::ApiMovieList,
+    oauth::auth::get_current_user,
+    pagination::get_paginated_response,
+    search::helpers::search_profiles_only,
+    statuses::helpers::build_status_list,
     statuses::types::Status,
+    watch_log::types::{ApiWatchLogEntry, WatchLogQueryParams},
+    watchlist::types::WatchlistQueryParams,
 };
 use crate::validators::{profiles::clean_profile_update_data, users::validate_local_username};
 
@@ -101,6 +114,8 @@ pub async fn create_account(
         wallet_address: None,
         invite_code,
         role,
+        account_kind: AccountKind::User,
+        tmdb_id: None,
     };
     let user = match create_user(db_client, user_data).await {
         Ok(user) => user,
@@ -145,11 +160,26 @@ async fn update_credentials(
 ) -> Result<HttpResponse, MastodonError> {
     let db_client = &mut **get_database_client(&db_pool).await?;
     let mut current_user = get_current_user(db_client, auth.token()).await?;
-    let mut profile_data = account_data
-        .into_inner()
-        .into_profile_data(&current_user.profile, &config.media_dir())?;
+    let account_data = account_data.into_inner();
+    let maybe_show_watchlist = account_data.show_watchlist;
+    let mut profile_data =
+        account_data.into_profile_data(&current_user.profile, &config.media_dir())?;
     clean_profile_update_data(&mut profile_data)?;
-    current_user.profile = update_profile(db_client, &current_user.id, profile_data).await?;
+    let was_locked = current_user.profile.manually_approves_followers;
+    let mut transaction = db_client.transaction().await?;
+    if let Some(show_watchlist) = maybe_show_watchlist {
+        set_watchlist_visibility(&transaction, &current_user.id, show_watchlist).await?;
+        current_user.show_watchlist = show_watchlist;
+    };
+    current_user.profile = update_profile(&mut transaction, &current_user.id, profile_data).await?;
+    transaction.commit().await?;
+    if was_locked && !current_user.profile.manually_approves_followers {
+        if let Err(error) =
+            accept_pending_follow_requests(db_client, &config.instance(), &current_user).await
+        {
+            log::error!("failed to accept pending follow requests: {}", error);
+        };
+    };
 
     // Federate
     prepare_update_person(db_client, &config.instance(), &current_user, None)
@@ -308,6 +338,9 @@ async fn follow_account(
     let db_client = &mut **get_database_client(&db_pool).await?;
     let current_user = get_current_user(db_client, auth.token()).await?;
     let target = get_profile_by_id(db_client, &account_id).await?;
+    if has_block(db_client, &current_user.id, &target.id).await? {
+        return Err(MastodonError::PermissionError);
+    };
     follow_or_create_request(db_client, &config.instance(), &current_user, &target).await?;
     if follow_data.reblogs {
         show_reposts(db_client, &current_user.id, &target.id).await?;
@@ -335,16 +368,17 @@ async fn unfollow_account(
     let target = get_profile_by_id(db_client, &account_id).await?;
     match unfollow(db_client, &current_user.id, &target.id).await {
         Ok(Some(follow_request_id)) => {
-            // Remote follow
-            let remote_actor = target.actor_json.ok_or(MastodonError::InternalError)?;
-            prepare_undo_follow(
-                &config.instance(),
-                &current_user,
-                &remote_actor,
-                &follow_request_id,
-            )
-            .enqueue(db_client)
-            .await?;
+            if let Some(ref remote_actor) = target.actor_json {
+                // Remote follow
+                prepare_undo_follow(
+                    &config.instance(),
+                    &current_user,
+                    remote_actor,
+                    &follow_request_id,
+                )
+                .enqueue(db_client)
+                .await?;
+            };
         }
         Ok(None) => (),                        // local follow
         Err(DatabaseError::NotFound(_)) => (), // not following
@@ -390,6 +424,62 @@ async fn unmute_account(
     Ok(HttpResponse::Ok().json(relationship))
 }
 
+#[post("/{account_id}/block")]
+async fn block_account(
+    auth: BearerAuth,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &mut **get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let target = get_profile_by_id(db_client, &account_id).await?;
+    if target.id == current_user.id {
+        return Err(ValidationError("can't block yourself".to_string()).into());
+    };
+    let maybe_follow_request_id = block(db_client, &current_user.id, &target.id).await?;
+    if let Some(ref remote_actor) = target.actor_json {
+        let instance = config.instance();
+        if let Some(follow_request_id) = maybe_follow_request_id {
+            prepare_undo_follow(&instance, &current_user, remote_actor, &follow_request_id)
+                .enqueue(db_client)
+                .await?;
+        };
+        prepare_block(&instance, &current_user, remote_actor)
+            .enqueue(db_client)
+            .await?;
+    };
+
+    let relationship = get_relationship(db_client, &current_user.id, &target.id).await?;
+    Ok(HttpResponse::Ok().json(relationship))
+}
+
+#[post("/{account_id}/unblock")]
+async fn unblock_account(
+    auth: BearerAuth,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &mut **get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let target = get_profile_by_id(db_client, &account_id).await?;
+    match unblock(db_client, &current_user.id, &target.id).await {
+        Ok(()) => {
+            if let Some(ref remote_actor) = target.actor_json {
+                prepare_undo_block(&config.instance(), &current_user, remote_actor)
+                    .enqueue(db_client)
+                    .await?;
+            };
+        }
+        Err(DatabaseError::NotFound(_)) => (), // not blocking
+        Err(other_error) => return Err(other_error.into()),
+    };
+
+    let relationship = get_relationship(db_client, &current_user.id, &target.id).await?;
+    Ok(HttpResponse::Ok().json(relationship))
+}
+
 #[get("/{account_id}/statuses")]
 async fn get_account_statuses(
     auth: Option<BearerAuth>,
@@ -432,6 +522,119 @@ async fn get_account_statuses(
     Ok(HttpResponse::Ok().json(statuses))
 }
 
+/// Returns watch log of a user, or watch log entries
+/// about a movie if account represents a movie
+#[get("/{account_id}/watch_log")]
+async fn get_account_watch_log(
+    auth: Option<BearerAuth>,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+    query_params: web::Query<WatchLogQueryParams>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let maybe_current_user = match auth {
+        Some(auth) => Some(get_current_user(db_client, auth.token()).await?),
+        None => None,
+    };
+    let maybe_current_user_id = maybe_current_user.as_ref().map(|user| &user.id);
+    let profile = get_profile_by_id(db_client, &account_id).await?;
+    let entries = if profile.is_media() {
+        get_watch_log_by_movie(
+            db_client,
+            &profile.id,
+            maybe_current_user_id,
+            query_params.max_id,
+            query_params.limit.inner(),
+        )
+        .await?
+    } else {
+        get_watch_log_by_owner(
+            db_client,
+            &profile.id,
+            maybe_current_user_id,
+            query_params.max_id,
+            Some(query_params.limit.inner()),
+        )
+        .await?
+    };
+    let base_url = get_request_base_url(connection_info);
+    let instance_url = config.instance_url();
+    let entries: Vec<ApiWatchLogEntry> = entries
+        .into_iter()
+        .map(|entry| ApiWatchLogEntry::from_db(&base_url, &instance_url, entry))
+        .collect();
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+#[get("/{account_id}/watchlist")]
+async fn get_account_watchlist(
+    auth: Option<BearerAuth>,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+    query_params: web::Query<WatchlistQueryParams>,
+    request: HttpRequest,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let maybe_current_user = match auth {
+        Some(auth) => Some(get_current_user(db_client, auth.token()).await?),
+        None => None,
+    };
+    let profile = get_profile_by_id(db_client, &account_id).await?;
+    let is_visible = if profile.is_local() {
+        let user = get_user_by_id(db_client, &profile.id).await?;
+        user.show_watchlist
+            || maybe_current_user.map_or(false, |current_user| current_user.id == user.id)
+    } else {
+        false
+    };
+    if !is_visible {
+        // Watchlist is hidden
+        let accounts: Vec<Account> = vec![];
+        return Ok(HttpResponse::Ok().json(accounts));
+    };
+    let entries = get_watchlist(
+        db_client,
+        &profile.id,
+        query_params.max_id,
+        query_params.limit.inner(),
+    )
+    .await?;
+    let max_index = usize::from(query_params.limit.inner().saturating_sub(1));
+    let maybe_last_id = entries.get(max_index).map(|entry| entry.id);
+    let base_url = get_request_base_url(connection_info);
+    let instance_url = config.instance_url();
+    let accounts: Vec<Account> = entries
+        .into_iter()
+        .map(|entry| Account::from_profile(&base_url, &instance_url, entry.movie))
+        .collect();
+    let response =
+        get_paginated_response(&instance_url, request.uri().path(), accounts, maybe_last_id);
+    Ok(response)
+}
+
+#[get("/{account_id}/movie_lists")]
+async fn get_account_movie_lists(
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let profile = get_profile_by_id(db_client, &account_id).await?;
+    let lists = get_movie_lists_by_owner(db_client, &profile.id).await?;
+    let base_url = get_request_base_url(connection_info);
+    let instance_url = config.instance_url();
+    let lists: Vec<ApiMovieList> = lists
+        .into_iter()
+        .map(|list| ApiMovieList::from_db(&base_url, &instance_url, list))
+        .collect();
+    Ok(HttpResponse::Ok().json(lists))
+}
+
 #[get("/{account_id}/followers")]
 async fn get_account_followers(
     auth: BearerAuth,
@@ -591,7 +794,12 @@ pub fn account_api_scope() -> Scope {
         .service(unfollow_account)
         .service(mute_account)
         .service(unmute_account)
+        .service(block_account)
+        .service(unblock_account)
         .service(get_account_statuses)
+        .service(get_account_watch_log)
+        .service(get_account_watchlist)
+        .service(get_account_movie_lists)
         .service(get_account_followers)
         .service(get_account_following)
         .service(get_account_subscribers)
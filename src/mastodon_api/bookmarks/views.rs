@@ -0,0 +1,58 @@
+/// https://docs.joinmastodon.org/methods/bookmarks/
+use actix_web::{dev::ConnectionInfo, get, web, HttpRequest, HttpResponse, Scope};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+
+use fedimovies_config::Config;
+use fedimovies_models::{
+    bookmarks::queries::get_bookmarks,
+    database::{get_database_client, DbPool},
+};
+
+use crate::http::get_request_base_url;
+use crate::mastodon_api::{
+    errors::MastodonError, oauth::auth::get_current_user, pagination::get_paginated_response,
+    statuses::helpers::build_status_list, timelines::types::TimelineQueryParams,
+};
+
+#[get("")]
+async fn get_bookmarks_view(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    query_params: web::Query<TimelineQueryParams>,
+    request: HttpRequest,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let bookmarks = get_bookmarks(
+        db_client,
+        &current_user.id,
+        query_params.max_id,
+        query_params.limit.inner(),
+    )
+    .await?;
+    // Pagination uses bookmark IDs
+    let max_index = usize::from(query_params.limit.inner().saturating_sub(1));
+    let maybe_last_id = bookmarks.get(max_index).map(|bookmark| bookmark.id);
+    let posts = bookmarks
+        .into_iter()
+        .map(|bookmark| bookmark.post)
+        .collect();
+    let instance_url = config.instance_url();
+    let statuses = build_status_list(
+        db_client,
+        &get_request_base_url(connection_info),
+        &instance_url,
+        Some(&current_user),
+        posts,
+    )
+    .await?;
+    let response =
+        get_paginated_response(&instance_url, request.uri().path(), statuses, maybe_last_id);
+    Ok(response)
+}
+
+pub fn bookmark_api_scope() -> Scope {
+    web::scope("/api/v1/bookmarks").service(get_bookmarks_view)
+}
@@ -0,0 +1,69 @@
+use fedimovies_config::Instance;
+use fedimovies_models::{
+    database::DatabaseClient,
+    profiles::types::DbActorProfile,
+    relationships::queries::{
+        follow_request_accepted, get_follow_request_by_participants, get_follow_requests,
+    },
+    relationships::types::DbFollowRequest,
+    users::types::User,
+};
+
+use crate::activitypub::builders::accept_follow::prepare_accept_follow;
+use crate::mastodon_api::errors::MastodonError;
+
+pub async fn accept_follow_request(
+    db_client: &mut impl DatabaseClient,
+    instance: &Instance,
+    current_user: &User,
+    source: &DbActorProfile,
+    follow_request: DbFollowRequest,
+) -> Result<(), MastodonError> {
+    follow_request_accepted(db_client, &follow_request.id).await?;
+    if let Some(ref remote_actor) = source.actor_json {
+        let follow_activity_id = follow_request
+            .activity_id
+            .ok_or(MastodonError::InternalError)?;
+        prepare_accept_follow(instance, current_user, remote_actor, &follow_activity_id)
+            .enqueue(db_client)
+            .await?;
+    };
+    Ok(())
+}
+
+/// Accepts all pending follow requests (when account is unlocked).
+/// Failed requests are logged and skipped.
+pub async fn accept_pending_follow_requests(
+    db_client: &mut impl DatabaseClient,
+    instance: &Instance,
+    current_user: &User,
+) -> Result<(), MastodonError> {
+    let sources = get_follow_requests(db_client, &current_user.id).await?;
+    for source in sources {
+        let result = async {
+            let mut transaction = db_client.transaction().await?;
+            let follow_request =
+                get_follow_request_by_participants(&transaction, &source.id, &current_user.id)
+                    .await?;
+            accept_follow_request(
+                &mut transaction,
+                instance,
+                current_user,
+                &source,
+                follow_request,
+            )
+            .await?;
+            transaction.commit().await?;
+            Ok::<_, MastodonError>(())
+        }
+        .await;
+        if let Err(error) = result {
+            log::warn!(
+                "failed to accept follow request from {}: {}",
+                source.id,
+                error,
+            );
+        };
+    }
+    Ok(())
+}
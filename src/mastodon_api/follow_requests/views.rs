@@ -0,0 +1,116 @@
+/// https://docs.joinmastodon.org/methods/follow_requests/
+use actix_web::{dev::ConnectionInfo, get, post, web, HttpResponse, Scope};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use uuid::Uuid;
+
+use fedimovies_config::Config;
+use fedimovies_models::{
+    database::{get_database_client, DatabaseClient, DbPool},
+    profiles::queries::get_profile_by_id,
+    relationships::queries::{
+        follow_request_rejected, get_follow_request_by_participants, get_follow_requests,
+    },
+    relationships::types::{DbFollowRequest, FollowRequestStatus},
+    users::types::User,
+};
+
+use super::helpers::accept_follow_request;
+use crate::activitypub::builders::reject_follow::prepare_reject_follow;
+use crate::http::get_request_base_url;
+use crate::mastodon_api::{
+    accounts::helpers::get_relationship, accounts::types::Account, errors::MastodonError,
+    oauth::auth::get_current_user,
+};
+
+async fn get_pending_follow_request(
+    db_client: &impl DatabaseClient,
+    current_user: &User,
+    source_id: &Uuid,
+) -> Result<DbFollowRequest, MastodonError> {
+    let follow_request =
+        get_follow_request_by_participants(db_client, source_id, &current_user.id).await?;
+    if follow_request.request_status != FollowRequestStatus::Pending {
+        return Err(MastodonError::NotFoundError("follow request"));
+    };
+    Ok(follow_request)
+}
+
+#[get("")]
+async fn get_follow_requests_view(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let base_url = get_request_base_url(connection_info);
+    let instance_url = config.instance_url();
+    let accounts: Vec<Account> = get_follow_requests(db_client, &current_user.id)
+        .await?
+        .into_iter()
+        .map(|profile| Account::from_profile(&base_url, &instance_url, profile))
+        .collect();
+    Ok(HttpResponse::Ok().json(accounts))
+}
+
+#[post("/{account_id}/authorize")]
+async fn authorize_view(
+    auth: BearerAuth,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &mut **get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let source = get_profile_by_id(db_client, &account_id).await?;
+    let follow_request = get_pending_follow_request(db_client, &current_user, &source.id).await?;
+    accept_follow_request(
+        db_client,
+        &config.instance(),
+        &current_user,
+        &source,
+        follow_request,
+    )
+    .await?;
+
+    let relationship = get_relationship(db_client, &current_user.id, &source.id).await?;
+    Ok(HttpResponse::Ok().json(relationship))
+}
+
+#[post("/{account_id}/reject")]
+async fn reject_view(
+    auth: BearerAuth,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let source = get_profile_by_id(db_client, &account_id).await?;
+    let follow_request = get_pending_follow_request(db_client, &current_user, &source.id).await?;
+    follow_request_rejected(db_client, &follow_request.id).await?;
+    if let Some(ref remote_actor) = source.actor_json {
+        let follow_activity_id = follow_request
+            .activity_id
+            .ok_or(MastodonError::InternalError)?;
+        prepare_reject_follow(
+            &config.instance(),
+            &current_user,
+            remote_actor,
+            &follow_activity_id,
+        )
+        .enqueue(db_client)
+        .await?;
+    };
+
+    let relationship = get_relationship(db_client, &current_user.id, &source.id).await?;
+    Ok(HttpResponse::Ok().json(relationship))
+}
+
+pub fn follow_request_api_scope() -> Scope {
+    web::scope("/api/v1/follow_requests")
+        .service(get_follow_requests_view)
+        .service(authorize_view)
+        .service(reject_view)
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use fedimovies_models::lists::types::DbList;
+
+#[derive(Deserialize)]
+pub struct ListData {
+    pub title: String,
+}
+
+#[derive(Deserialize)]
+pub struct ListAccountsData {
+    #[serde(alias = "account_ids[]")]
+    pub account_ids: Vec<Uuid>,
+}
+
+/// https://docs.joinmastodon.org/entities/List/
+#[derive(Serialize)]
+pub struct ApiList {
+    pub id: Uuid,
+    pub title: String,
+}
+
+impl ApiList {
+    pub fn from_db(list: DbList) -> Self {
+        Self {
+            id: list.id,
+            title: list.title,
+        }
+    }
+}
@@ -0,0 +1,169 @@
+/// https://docs.joinmastodon.org/methods/lists/
+use actix_web::{delete, dev::ConnectionInfo, get, post, put, web, HttpResponse, Scope};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use uuid::Uuid;
+
+use fedimovies_config::Config;
+use fedimovies_models::{
+    database::{get_database_client, DbPool},
+    lists::queries::{
+        add_list_members, create_list, delete_list, get_list, get_list_members, get_lists,
+        remove_list_members, update_list,
+    },
+    relationships::queries::has_relationship,
+    relationships::types::RelationshipType,
+};
+
+use super::types::{ApiList, ListAccountsData, ListData};
+use crate::errors::ValidationError;
+use crate::http::{get_request_base_url, FormOrJson};
+use crate::mastodon_api::{
+    accounts::types::Account, errors::MastodonError, oauth::auth::get_current_user,
+};
+use crate::validators::lists::{validate_list_title, LIST_MEMBER_LIMIT};
+
+#[get("")]
+async fn get_lists_view(
+    auth: BearerAuth,
+    db_pool: web::Data<DbPool>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let lists: Vec<ApiList> = get_lists(db_client, &current_user.id)
+        .await?
+        .into_iter()
+        .map(ApiList::from_db)
+        .collect();
+    Ok(HttpResponse::Ok().json(lists))
+}
+
+#[post("")]
+async fn create_list_view(
+    auth: BearerAuth,
+    db_pool: web::Data<DbPool>,
+    list_data: FormOrJson<ListData>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let title = list_data.title.trim();
+    validate_list_title(title)?;
+    let list = create_list(db_client, &current_user.id, title).await?;
+    Ok(HttpResponse::Ok().json(ApiList::from_db(list)))
+}
+
+#[get("/{list_id}")]
+async fn get_list_view(
+    auth: BearerAuth,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let list = get_list(db_client, &list_id, &current_user.id).await?;
+    Ok(HttpResponse::Ok().json(ApiList::from_db(list)))
+}
+
+#[put("/{list_id}")]
+async fn update_list_view(
+    auth: BearerAuth,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+    list_data: FormOrJson<ListData>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let title = list_data.title.trim();
+    validate_list_title(title)?;
+    let list = update_list(db_client, &list_id, &current_user.id, title).await?;
+    Ok(HttpResponse::Ok().json(ApiList::from_db(list)))
+}
+
+#[delete("/{list_id}")]
+async fn delete_list_view(
+    auth: BearerAuth,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    delete_list(db_client, &list_id, &current_user.id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[get("/{list_id}/accounts")]
+async fn get_list_accounts_view(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let list = get_list(db_client, &list_id, &current_user.id).await?;
+    let base_url = get_request_base_url(connection_info);
+    let instance_url = config.instance_url();
+    let accounts: Vec<Account> = get_list_members(db_client, &list.id)
+        .await?
+        .into_iter()
+        .map(|profile| Account::from_profile(&base_url, &instance_url, profile))
+        .collect();
+    Ok(HttpResponse::Ok().json(accounts))
+}
+
+#[post("/{list_id}/accounts")]
+async fn add_list_accounts_view(
+    auth: BearerAuth,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+    accounts_data: FormOrJson<ListAccountsData>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let list = get_list(db_client, &list_id, &current_user.id).await?;
+    let account_ids = &accounts_data.account_ids;
+    if account_ids.len() > LIST_MEMBER_LIMIT {
+        return Err(ValidationError("too many accounts".to_string()).into());
+    };
+    // Only followed accounts can be added to list
+    for account_id in account_ids.iter() {
+        let is_following = has_relationship(
+            db_client,
+            &current_user.id,
+            account_id,
+            RelationshipType::Follow,
+        )
+        .await?;
+        if !is_following {
+            return Err(ValidationError("account is not followed".to_string()).into());
+        };
+    }
+    add_list_members(db_client, &list.id, account_ids).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[delete("/{list_id}/accounts")]
+async fn remove_list_accounts_view(
+    auth: BearerAuth,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+    accounts_data: FormOrJson<ListAccountsData>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let list = get_list(db_client, &list_id, &current_user.id).await?;
+    remove_list_members(db_client, &list.id, &accounts_data.account_ids).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn list_api_scope() -> Scope {
+    web::scope("/api/v1/lists")
+        .service(get_lists_view)
+        .service(create_list_view)
+        .service(get_list_view)
+        .service(update_list_view)
+        .service(delete_list_view)
+        .service(get_list_accounts_view)
+        .service(add_list_accounts_view)
+        .service(remove_list_accounts_view)
+}
@@ -1,10 +1,15 @@
 pub mod accounts;
 pub mod apps;
+pub mod bookmarks;
 pub mod custom_emojis;
 pub mod directory;
+pub mod follow_requests;
 pub mod instance;
+pub mod lists;
 pub mod markers;
 pub mod media;
+pub mod movie_lists;
+pub mod movies;
 pub mod notifications;
 pub mod oauth;
 pub mod search;
@@ -12,6 +17,9 @@ pub mod settings;
 pub mod statuses;
 pub mod subscriptions;
 pub mod timelines;
+pub mod trends;
+pub mod watch_log;
+pub mod watchlist;
 
 mod errors;
 mod pagination;
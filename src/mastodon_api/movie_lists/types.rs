@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use fedimovies_models::movie_lists::types::{
+    MovieList, MovieListData as DbMovieListData, MovieListItemData as DbMovieListItemData,
+};
+
+use crate::activitypub::identifiers::local_movie_list_id;
+use crate::mastodon_api::accounts::types::Account;
+
+#[derive(Deserialize)]
+pub struct MovieListItemData {
+    pub movie_id: Uuid,
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct MovieListData {
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub items: Vec<MovieListItemData>,
+}
+
+impl MovieListData {
+    pub fn into_db_data(self) -> DbMovieListData {
+        let items = self
+            .items
+            .into_iter()
+            .map(|item| DbMovieListItemData {
+                movie_id: item.movie_id,
+                // Empty notes are not stored
+                note: item.note.filter(|note| !note.trim().is_empty()),
+            })
+            .collect();
+        DbMovieListData {
+            title: self.title.trim().to_string(),
+            description: self
+                .description
+                .filter(|description| !description.trim().is_empty()),
+            items,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ApiMovieListItem {
+    pub account: Account,
+    pub note: Option<String>,
+}
+
+/// Curated movie list (not a part of Mastodon API)
+#[derive(Serialize)]
+pub struct ApiMovieList {
+    pub id: Uuid,
+    pub uri: String,
+    pub account: Account,
+    pub title: String,
+    pub description: Option<String>,
+    pub items: Vec<ApiMovieListItem>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ApiMovieList {
+    pub fn from_db(base_url: &str, instance_url: &str, list: MovieList) -> Self {
+        let items = list
+            .items
+            .into_iter()
+            .map(|item| ApiMovieListItem {
+                account: Account::from_profile(base_url, instance_url, item.movie),
+                note: item.note,
+            })
+            .collect();
+        Self {
+            id: list.id,
+            uri: local_movie_list_id(instance_url, &list.id),
+            account: Account::from_profile(base_url, instance_url, list.owner),
+            title: list.title,
+            description: list.description,
+            items,
+            created_at: list.created_at,
+            updated_at: list.updated_at,
+        }
+    }
+}
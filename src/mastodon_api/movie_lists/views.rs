@@ -0,0 +1,119 @@
+use actix_web::{delete, dev::ConnectionInfo, get, post, put, web, HttpResponse, Scope};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use uuid::Uuid;
+
+use fedimovies_config::Config;
+use fedimovies_models::{
+    database::{get_database_client, DbPool},
+    movie_lists::queries::{
+        create_movie_list, delete_movie_list, get_movie_list, update_movie_list,
+    },
+};
+
+use super::types::{ApiMovieList, MovieListData};
+use crate::activitypub::builders::{
+    create_movie_list::prepare_create_movie_list, delete_movie_list::prepare_delete_movie_list,
+    update_movie_list::prepare_update_movie_list,
+};
+use crate::http::get_request_base_url;
+use crate::mastodon_api::{errors::MastodonError, oauth::auth::get_current_user};
+use crate::validators::movie_lists::validate_movie_list_data;
+
+#[post("")]
+async fn create_list_view(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    list_data: web::Json<MovieListData>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &mut **get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let list_data = list_data.into_inner().into_db_data();
+    validate_movie_list_data(&list_data)?;
+    let list = create_movie_list(db_client, &current_user.id, list_data).await?;
+    let instance = config.instance();
+    prepare_create_movie_list(db_client, &instance, &current_user, &list)
+        .await?
+        .enqueue(db_client)
+        .await?;
+    let list = ApiMovieList::from_db(
+        &get_request_base_url(connection_info),
+        &instance.url(),
+        list,
+    );
+    Ok(HttpResponse::Ok().json(list))
+}
+
+#[get("/{list_id}")]
+async fn get_list_view(
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let list = get_movie_list(db_client, &list_id).await?;
+    let list = ApiMovieList::from_db(
+        &get_request_base_url(connection_info),
+        &config.instance_url(),
+        list,
+    );
+    Ok(HttpResponse::Ok().json(list))
+}
+
+#[put("/{list_id}")]
+async fn update_list_view(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+    list_data: web::Json<MovieListData>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &mut **get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let list_data = list_data.into_inner().into_db_data();
+    validate_movie_list_data(&list_data)?;
+    let list = update_movie_list(db_client, &list_id, &current_user.id, list_data).await?;
+    let instance = config.instance();
+    prepare_update_movie_list(db_client, &instance, &current_user, &list)
+        .await?
+        .enqueue(db_client)
+        .await?;
+    let list = ApiMovieList::from_db(
+        &get_request_base_url(connection_info),
+        &instance.url(),
+        list,
+    );
+    Ok(HttpResponse::Ok().json(list))
+}
+
+#[delete("/{list_id}")]
+async fn delete_list_view(
+    auth: BearerAuth,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let list = get_movie_list(db_client, &list_id).await?;
+    if list.owner.id != current_user.id {
+        return Err(MastodonError::PermissionError);
+    };
+    delete_movie_list(db_client, &list.id, &current_user.id).await?;
+    prepare_delete_movie_list(db_client, &config.instance(), &current_user, &list)
+        .await?
+        .enqueue(db_client)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn movie_list_api_scope() -> Scope {
+    web::scope("/api/v1/movie_lists")
+        .service(create_list_view)
+        .service(get_list_view)
+        .service(update_list_view)
+        .service(delete_list_view)
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use fedimovies_models::profiles::types::DbActorProfile;
+
+use crate::mastodon_api::{accounts::types::Account, pagination::PageSize};
+use crate::tmdb::{MovieInfo, MovieUsername, TvUsername};
+
+fn default_autocomplete_page_size() -> PageSize {
+    PageSize::new(10)
+}
+
+#[derive(Deserialize)]
+pub struct MovieAutocompleteQueryParams {
+    pub q: String,
+
+    #[serde(default = "default_autocomplete_page_size")]
+    pub limit: PageSize,
+}
+
+/// Movie suggestion (not a part of Mastodon API)
+#[derive(Serialize)]
+pub struct MovieSuggestion {
+    /// Username that can be used in mentions
+    pub handle: String,
+    pub title: String,
+    pub year: Option<u32>,
+    pub poster_url: Option<String>,
+    pub tmdb_id: Option<i32>,
+    /// Local account, if it exists
+    pub account: Option<Account>,
+}
+
+impl MovieSuggestion {
+    pub fn from_profile(base_url: &str, instance_url: &str, profile: DbActorProfile) -> Self {
+        let maybe_year = TvUsername::parse(&profile.username)
+            .map(|tv_username| tv_username.year)
+            .or_else(|_| {
+                MovieUsername::parse(&profile.username).map(|movie_username| movie_username.year)
+            })
+            .ok();
+        let tmdb_id = profile.tmdb_id;
+        let account = Account::from_profile(base_url, instance_url, profile);
+        Self {
+            handle: account.username.clone(),
+            title: account
+                .display_name
+                .clone()
+                .unwrap_or_else(|| account.username.clone()),
+            year: maybe_year,
+            poster_url: account.avatar.clone(),
+            tmdb_id,
+            account: Some(account),
+        }
+    }
+
+    /// Creates suggestion from TMDB search result.
+    /// Handle always includes TMDB ID, because search results
+    /// don't show whether other movies have the same short form.
+    pub fn from_movie_info(movie_info: MovieInfo) -> Self {
+        Self {
+            handle: movie_info.movie_username_with_id(),
+            year: movie_info
+                .release_date
+                .split('-')
+                .next()
+                .and_then(|year| year.parse().ok()),
+            poster_url: movie_info.poster_url(),
+            tmdb_id: i32::try_from(movie_info.id).ok(),
+            title: movie_info.title,
+            account: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSTANCE_URL: &str = "https://example.com";
+
+    #[test]
+    fn test_movie_suggestion_from_profile() {
+        let profile = DbActorProfile {
+            username: "BreakingBad_TV_2008_1396".to_string(),
+            display_name: Some("Breaking Bad".to_string()),
+            tmdb_id: Some(1396),
+            ..Default::default()
+        };
+        let suggestion = MovieSuggestion::from_profile(INSTANCE_URL, INSTANCE_URL, profile);
+        assert_eq!(suggestion.handle, "BreakingBad_TV_2008_1396");
+        assert_eq!(suggestion.title, "Breaking Bad");
+        assert_eq!(suggestion.year, Some(2008));
+        assert_eq!(suggestion.tmdb_id, Some(1396));
+        assert!(suggestion.account.is_some());
+    }
+
+    #[test]
+    fn test_movie_suggestion_from_movie_info() {
+        let movie_info: MovieInfo = serde_json::from_str(
+            r#"{"adult":false,"backdrop_path":null,"id":76600,"original_language":"en","original_title":"Avatar: The Way of Water","overview":"","popularity":1210.4,"poster_path":"/poster.jpg","release_date":"2022-12-14","title":"Avatar: The Way of Water","video":false,"vote_average":7.7,"vote_count":5000}"#,
+        )
+        .unwrap();
+        let suggestion = MovieSuggestion::from_movie_info(movie_info);
+        assert_eq!(suggestion.handle, "AvatarTheWayOfWater_2022_76600");
+        assert_eq!(suggestion.title, "Avatar: The Way of Water");
+        assert_eq!(suggestion.year, Some(2022));
+        assert_eq!(
+            suggestion.poster_url.as_deref(),
+            Some("https://image.tmdb.org/t/p/w185/poster.jpg"),
+        );
+        assert!(suggestion.account.is_none());
+    }
+}
@@ -0,0 +1,50 @@
+use actix_web::{dev::ConnectionInfo, get, web, HttpResponse, Scope};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+
+use fedimovies_config::Config;
+use fedimovies_models::database::{get_database_client, DbPool};
+
+use super::types::{MovieAutocompleteQueryParams, MovieSuggestion};
+use crate::http::get_request_base_url;
+use crate::mastodon_api::{
+    errors::MastodonError, oauth::auth::get_current_user, search::helpers::autocomplete_movies,
+};
+
+#[get("/autocomplete")]
+async fn autocomplete_view(
+    auth: Option<BearerAuth>,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    query_params: web::Query<MovieAutocompleteQueryParams>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    // Only authorized users can make TMDB API queries
+    let lookup = match auth {
+        Some(auth) => {
+            get_current_user(db_client, auth.token()).await?;
+            true
+        }
+        None => false,
+    };
+    let (profiles, movies) = autocomplete_movies(
+        &config,
+        db_client,
+        &query_params.q,
+        lookup,
+        query_params.limit.inner(),
+    )
+    .await?;
+    let base_url = get_request_base_url(connection_info);
+    let instance_url = config.instance_url();
+    let mut suggestions: Vec<MovieSuggestion> = profiles
+        .into_iter()
+        .map(|profile| MovieSuggestion::from_profile(&base_url, &instance_url, profile))
+        .collect();
+    suggestions.extend(movies.into_iter().map(MovieSuggestion::from_movie_info));
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+
+pub fn movie_api_scope() -> Scope {
+    web::scope("/api/v1/movies").service(autocomplete_view)
+}
@@ -39,6 +39,9 @@ async fn authorize_view(
 ) -> Result<HttpResponse, MastodonError> {
     let db_client = &**get_database_client(&db_pool).await?;
     let user = get_user_by_name(db_client, &form_data.username).await?;
+    if user.profile.is_media() {
+        return Err(ValidationError("this account can't log in".to_string()).into());
+    };
     let password_hash = user
         .password_hash
         .as_ref()
@@ -107,6 +110,9 @@ async fn token_view(
             return Err(ValidationError("unsupported grant type".to_string()).into());
         }
     };
+    if user.profile.is_media() {
+        return Err(ValidationError("this account can't log in".to_string()).into());
+    };
     if request_data.grant_type == "password" || request_data.grant_type == "ethereum" {
         let password = request_data
             .password
@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use chrono::{Datelike, Utc};
 use regex::Regex;
 use url::Url;
 
@@ -10,10 +11,13 @@ use fedimovies_models::{
         helpers::{can_view_post, get_local_post_by_id},
         types::Post,
     },
-    profiles::queries::{search_profiles, search_profiles_by_did_only},
-    profiles::types::DbActorProfile,
+    profiles::queries::{search_media_profiles, search_profiles, search_profiles_by_did_only},
+    profiles::types::{AccountKind, DbActorProfile},
     tags::queries::search_tags,
-    users::{queries::get_user_by_name, types::User},
+    users::{
+        queries::{get_user_by_name, get_user_by_tmdb_id},
+        types::User,
+    },
 };
 use fedimovies_utils::did::Did;
 
@@ -25,14 +29,37 @@ use crate::activitypub::{
     HandlerError,
 };
 use crate::errors::ValidationError;
+use crate::mastodon_api::errors::MastodonError;
 use crate::media::MediaStorage;
+use crate::tmdb::{
+    get_movie_metadata_provider, get_or_create_media_user, get_or_create_movie_user_by_id,
+    get_or_create_tv_series_user_by_id, MovieError, MovieInfo,
+};
 use crate::webfinger::types::ActorAddress;
 
 const SEARCH_FETCHER_TIMEOUT: u64 = 15;
+// Maximum number of movie accounts created for a single title query
+const MOVIE_IMPORT_LIMIT: usize = 3;
+
+#[derive(Debug, PartialEq)]
+enum TmdbObject {
+    Movie(u32),
+    TvSeries(u32),
+}
+
+#[derive(Debug, PartialEq)]
+struct MovieQuery {
+    text: String,
+    title: String,
+    year: Option<u32>,
+}
 
 enum SearchQuery {
     ProfileQuery(String, Option<String>),
+    ProfileOrMovieQuery(String, MovieQuery),
     TagQuery(String),
+    MovieQuery(MovieQuery),
+    TmdbUrl(TmdbObject),
     Url(String),
     Did(Did),
     Unknown,
@@ -69,12 +96,65 @@ fn parse_tag_query(query: &str) -> Result<String, ValidationError> {
     Ok(tag)
 }
 
+fn parse_tmdb_url(query: &str) -> Result<TmdbObject, ValidationError> {
+    // e.g. https://www.themoviedb.org/movie/438631-dune
+    let tmdb_url_re = Regex::new(
+        r"^https?://(www\.)?themoviedb\.org/(?P<kind>movie|tv)/(?P<id>\d+)(-[\w-]*)?/?(\?.*)?$",
+    )
+    .unwrap();
+    let tmdb_url_caps = tmdb_url_re
+        .captures(query)
+        .ok_or(ValidationError("invalid TMDB URL".to_string()))?;
+    let tmdb_id = tmdb_url_caps["id"]
+        .parse::<u32>()
+        .map_err(|_| ValidationError("invalid TMDB ID".to_string()))?;
+    let tmdb_object = match &tmdb_url_caps["kind"] {
+        "movie" => TmdbObject::Movie(tmdb_id),
+        _ => TmdbObject::TvSeries(tmdb_id),
+    };
+    Ok(tmdb_object)
+}
+
+/// Parses free-text title, optionally followed by release year
+/// (e.g. "Dune 2021" or "Dune (2021)")
+fn parse_movie_query(query: &str) -> Result<MovieQuery, ValidationError> {
+    let text = query.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() < 2 {
+        return Err(ValidationError("invalid movie query".to_string()));
+    };
+    let year_query_re = Regex::new(r"^(?P<title>.+?)\s*\(?(?P<year>\d{4})\)?$").unwrap();
+    let max_year = Utc::now().year() as u32 + 10;
+    let maybe_title_year = year_query_re.captures(&text).and_then(|caps| {
+        let year = caps["year"].parse::<u32>().ok()?;
+        if !(1870..=max_year).contains(&year) {
+            return None;
+        };
+        Some((caps["title"].to_string(), year))
+    });
+    let movie_query = match maybe_title_year {
+        Some((title, year)) => MovieQuery {
+            text,
+            title,
+            year: Some(year),
+        },
+        None => MovieQuery {
+            title: text.clone(),
+            text,
+            year: None,
+        },
+    };
+    Ok(movie_query)
+}
+
 fn parse_search_query(search_query: &str) -> SearchQuery {
     let search_query = search_query.trim();
     // DID is a valid URI so it should be tried before Url::parse
     if let Ok(did) = Did::from_str(search_query) {
         return SearchQuery::Did(did);
     };
+    if let Ok(tmdb_object) = parse_tmdb_url(search_query) {
+        return SearchQuery::TmdbUrl(tmdb_object);
+    };
     if Url::parse(search_query).is_ok() {
         return SearchQuery::Url(search_query.to_string());
     };
@@ -82,8 +162,18 @@ fn parse_search_query(search_query: &str) -> SearchQuery {
         return SearchQuery::TagQuery(tag);
     };
     if let Ok((username, maybe_hostname)) = parse_profile_query(search_query) {
+        // Single word can be a username or a movie title
+        let is_prefixed = search_query.starts_with(|c| c == '@' || c == '!');
+        if maybe_hostname.is_none() && !is_prefixed {
+            if let Ok(movie_query) = parse_movie_query(search_query) {
+                return SearchQuery::ProfileOrMovieQuery(username, movie_query);
+            };
+        };
         return SearchQuery::ProfileQuery(username, maybe_hostname);
     };
+    if let Ok(movie_query) = parse_movie_query(search_query) {
+        return SearchQuery::MovieQuery(movie_query);
+    };
     SearchQuery::Unknown
 }
 
@@ -94,7 +184,7 @@ async fn search_profiles_or_import(
     mut maybe_hostname: Option<String>,
     resolve: bool,
     limit: u16,
-) -> Result<Vec<DbActorProfile>, DatabaseError> {
+) -> Result<Vec<DbActorProfile>, MastodonError> {
     let mut instance = config.instance();
     if let Some(ref hostname) = maybe_hostname {
         if hostname == &instance.hostname() {
@@ -105,6 +195,52 @@ async fn search_profiles_or_import(
     let mut profiles =
         search_profiles(db_client, &username, maybe_hostname.as_ref(), limit).await?;
     if profiles.is_empty() && resolve {
+        if maybe_hostname.is_none() {
+            // Local movie account may not exist yet
+            let movie_provider = get_movie_metadata_provider(config);
+            match get_or_create_media_user(
+                &instance,
+                db_client,
+                movie_provider.as_deref(),
+                &MediaStorage::from(config).media_dir,
+                &username,
+            )
+            .await
+            {
+                Ok(user) => {
+                    profiles.push(user.profile);
+                }
+                Err(MovieError::Ambiguous(candidates)) => {
+                    // Short form matches several movies, return all of them
+                    for candidate in candidates.iter().take(limit.into()) {
+                        match get_or_create_media_user(
+                            &instance,
+                            db_client,
+                            movie_provider.as_deref(),
+                            &MediaStorage::from(config).media_dir,
+                            candidate,
+                        )
+                        .await
+                        {
+                            Ok(user) => profiles.push(user.profile),
+                            Err(MovieError::DatabaseError(db_error)) => {
+                                return Err(db_error.into());
+                            }
+                            Err(other_error) => {
+                                log::warn!("failed to find movie {}: {}", candidate, other_error);
+                            }
+                        };
+                    }
+                }
+                Err(MovieError::DatabaseError(db_error)) => {
+                    return Err(db_error.into());
+                }
+                Err(MovieError::NotMovie) => (),
+                Err(other_error) => {
+                    log::warn!("failed to find movie {}: {}", username, other_error);
+                }
+            };
+        };
         if let Some(hostname) = maybe_hostname {
             let actor_address = ActorAddress { username, hostname };
             instance.fetcher_timeout = SEARCH_FETCHER_TIMEOUT;
@@ -121,7 +257,7 @@ async fn search_profiles_or_import(
                 }
                 Err(HandlerError::DatabaseError(db_error)) => {
                     // Propagate database errors
-                    return Err(db_error);
+                    return Err(db_error.into());
                 }
                 Err(other_error) => {
                     log::warn!(
@@ -136,6 +272,118 @@ async fn search_profiles_or_import(
     Ok(profiles)
 }
 
+/// Finds local movie accounts by title.
+/// If none found and `resolve` is true, searches TMDB and creates accounts.
+async fn search_movies_or_import(
+    config: &Config,
+    db_client: &mut impl DatabaseClient,
+    title: &str,
+    maybe_year: Option<u32>,
+    resolve: bool,
+    limit: u16,
+) -> Result<Vec<DbActorProfile>, MastodonError> {
+    let maybe_db_year = maybe_year.and_then(|year| i32::try_from(year).ok());
+    let mut profiles = search_media_profiles(db_client, title, maybe_db_year, limit).await?;
+    if !profiles.is_empty() || !resolve {
+        return Ok(profiles);
+    };
+    let movie_provider = match get_movie_metadata_provider(config) {
+        Some(movie_provider) => movie_provider,
+        None => return Ok(profiles),
+    };
+    let movies = match movie_provider.search_movies(title, maybe_year).await {
+        Ok(movies) => movies,
+        Err(error) => {
+            log::warn!("failed to search movies ({title}): {error}");
+            return Ok(profiles);
+        }
+    };
+    let instance = config.instance();
+    let media_dir = MediaStorage::from(config).media_dir;
+    let import_limit = usize::from(limit).min(MOVIE_IMPORT_LIMIT);
+    for movie_info in movies
+        .into_iter()
+        .filter(|movie_info| !movie_info.adult && !movie_info.release_date.is_empty())
+        .take(import_limit)
+    {
+        match get_or_create_movie_user_by_id(
+            &instance,
+            db_client,
+            movie_provider.as_ref(),
+            &media_dir,
+            movie_info.id,
+        )
+        .await
+        {
+            Ok(user) => profiles.push(user.profile),
+            Err(MovieError::DatabaseError(db_error)) => return Err(db_error.into()),
+            Err(other_error) => {
+                log::warn!("failed to import movie {}: {}", movie_info.id, other_error);
+            }
+        };
+    }
+    Ok(profiles)
+}
+
+/// Finds movie or TV series account by TMDB ID, creating it if necessary
+async fn find_movie_by_tmdb_url(
+    config: &Config,
+    db_client: &mut impl DatabaseClient,
+    tmdb_object: TmdbObject,
+    resolve: bool,
+) -> Result<Option<DbActorProfile>, MastodonError> {
+    let (account_kind, tmdb_id) = match tmdb_object {
+        TmdbObject::Movie(tmdb_id) => (AccountKind::Movie, tmdb_id),
+        TmdbObject::TvSeries(tmdb_id) => (AccountKind::TvSeries, tmdb_id),
+    };
+    let db_tmdb_id =
+        i32::try_from(tmdb_id).map_err(|_| ValidationError("invalid TMDB ID".to_string()))?;
+    match get_user_by_tmdb_id(db_client, &account_kind, db_tmdb_id).await {
+        Ok(user) => return Ok(Some(user.profile)),
+        Err(DatabaseError::NotFound(_)) => (),
+        Err(other_error) => return Err(other_error.into()),
+    };
+    if !resolve {
+        return Ok(None);
+    };
+    let movie_provider = match get_movie_metadata_provider(config) {
+        Some(movie_provider) => movie_provider,
+        None => return Ok(None),
+    };
+    let instance = config.instance();
+    let media_dir = MediaStorage::from(config).media_dir;
+    let result = match account_kind {
+        AccountKind::Movie => {
+            get_or_create_movie_user_by_id(
+                &instance,
+                db_client,
+                movie_provider.as_ref(),
+                &media_dir,
+                tmdb_id,
+            )
+            .await
+        }
+        _ => {
+            get_or_create_tv_series_user_by_id(
+                &instance,
+                db_client,
+                movie_provider.as_ref(),
+                &media_dir,
+                tmdb_id,
+            )
+            .await
+        }
+    };
+    match result {
+        Ok(user) => Ok(Some(user.profile)),
+        Err(MovieError::DatabaseError(db_error)) => Err(db_error.into()),
+        Err(other_error) => {
+            log::warn!("failed to import TMDB object {}: {}", tmdb_id, other_error);
+            Ok(None)
+        }
+    }
+}
+
 /// Finds post by its object ID
 async fn find_post_by_url(
     config: &Config,
@@ -155,14 +403,12 @@ async fn find_post_by_url(
         }
         Err(_) => {
             instance.fetcher_timeout = SEARCH_FETCHER_TIMEOUT;
-            let tmdb_api_key = config.tmdb_api_key.clone();
-            let default_movie_user_password = config.movie_user_password.clone();
+            let movie_provider = get_movie_metadata_provider(config);
             match import_post(
                 db_client,
                 &instance,
                 &storage,
-                tmdb_api_key,
-                default_movie_user_password,
+                movie_provider.as_deref(),
                 url.to_string(),
                 None,
             )
@@ -217,20 +463,76 @@ pub async fn search(
     current_user: &User,
     db_client: &mut impl DatabaseClient,
     search_query: &str,
+    resolve: bool,
     limit: u16,
-) -> Result<SearchResults, DatabaseError> {
+) -> Result<SearchResults, MastodonError> {
     let mut profiles = vec![];
     let mut posts = vec![];
     let mut tags = vec![];
     match parse_search_query(search_query) {
         SearchQuery::ProfileQuery(username, maybe_hostname) => {
-            profiles =
-                search_profiles_or_import(config, db_client, username, maybe_hostname, true, limit)
-                    .await?;
+            profiles = search_profiles_or_import(
+                config,
+                db_client,
+                username,
+                maybe_hostname,
+                resolve,
+                limit,
+            )
+            .await?;
+        }
+        SearchQuery::ProfileOrMovieQuery(username, movie_query) => {
+            profiles = search_profiles_or_import(config, db_client, username, None, resolve, limit)
+                .await?;
+            let movie_profiles = search_movies_or_import(
+                config,
+                db_client,
+                &movie_query.title,
+                movie_query.year,
+                resolve,
+                limit,
+            )
+            .await?;
+            for profile in movie_profiles {
+                if !profiles.iter().any(|item| item.id == profile.id) {
+                    profiles.push(profile);
+                };
+            }
+            profiles.truncate(limit.into());
         }
         SearchQuery::TagQuery(tag) => {
             tags = search_tags(db_client, &tag, limit).await?;
         }
+        SearchQuery::MovieQuery(movie_query) => {
+            profiles = search_movies_or_import(
+                config,
+                db_client,
+                &movie_query.title,
+                movie_query.year,
+                resolve,
+                limit,
+            )
+            .await?;
+            if profiles.is_empty() && movie_query.year.is_some() {
+                // Number at the end can be a part of the title
+                profiles = search_movies_or_import(
+                    config,
+                    db_client,
+                    &movie_query.text,
+                    None,
+                    resolve,
+                    limit,
+                )
+                .await?;
+            };
+        }
+        SearchQuery::TmdbUrl(tmdb_object) => {
+            let maybe_profile =
+                find_movie_by_tmdb_url(config, db_client, tmdb_object, resolve).await?;
+            if let Some(profile) = maybe_profile {
+                profiles = vec![profile];
+            };
+        }
         SearchQuery::Url(url) => {
             let maybe_post = find_post_by_url(config, db_client, &url).await?;
             if let Some(post) = maybe_post {
@@ -258,7 +560,7 @@ pub async fn search_profiles_only(
     search_query: &str,
     resolve: bool,
     limit: u16,
-) -> Result<Vec<DbActorProfile>, DatabaseError> {
+) -> Result<Vec<DbActorProfile>, MastodonError> {
     let (username, maybe_hostname) = match parse_profile_query(search_query) {
         Ok(result) => result,
         Err(_) => return Ok(vec![]),
@@ -269,6 +571,54 @@ pub async fn search_profiles_only(
     Ok(profiles)
 }
 
+/// Returns local movie accounts matching the partial title,
+/// followed by TMDB results that don't have local accounts yet
+/// (only if `lookup` is true)
+pub async fn autocomplete_movies(
+    config: &Config,
+    db_client: &impl DatabaseClient,
+    search_query: &str,
+    lookup: bool,
+    limit: u16,
+) -> Result<(Vec<DbActorProfile>, Vec<MovieInfo>), MastodonError> {
+    let movie_query = match parse_movie_query(search_query.trim()) {
+        Ok(movie_query) => movie_query,
+        Err(_) => return Ok((vec![], vec![])),
+    };
+    let maybe_db_year = movie_query.year.and_then(|year| i32::try_from(year).ok());
+    let profiles =
+        search_media_profiles(db_client, &movie_query.title, maybe_db_year, limit).await?;
+    let remaining = usize::from(limit).saturating_sub(profiles.len());
+    if !lookup || remaining == 0 {
+        return Ok((profiles, vec![]));
+    };
+    let movie_provider = match get_movie_metadata_provider(config) {
+        Some(movie_provider) => movie_provider,
+        None => return Ok((profiles, vec![])),
+    };
+    let mut movies = match movie_provider
+        .search_movies(&movie_query.title, movie_query.year)
+        .await
+    {
+        Ok(movies) => movies,
+        Err(error) => {
+            log::warn!("failed to search movies ({}): {}", movie_query.title, error);
+            vec![]
+        }
+    };
+    movies.retain(|movie_info| {
+        !movie_info.adult
+            && !movie_info.release_date.is_empty()
+            && !profiles.iter().any(|profile| {
+                profile.account_kind == AccountKind::Movie
+                    && profile.tmdb_id == i32::try_from(movie_info.id).ok()
+            })
+    });
+    movies.sort_by(|a, b| b.popularity.total_cmp(&a.popularity));
+    movies.truncate(remaining);
+    Ok((profiles, movies))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,6 +639,56 @@ mod tests {
         assert_eq!(maybe_hostname.as_deref(), Some("example.com"));
     }
 
+    #[test]
+    fn test_parse_tmdb_url() {
+        let tmdb_object = parse_tmdb_url("https://www.themoviedb.org/movie/438631-dune").unwrap();
+        assert_eq!(tmdb_object, TmdbObject::Movie(438631));
+        let tmdb_object = parse_tmdb_url("https://themoviedb.org/tv/1396").unwrap();
+        assert_eq!(tmdb_object, TmdbObject::TvSeries(1396));
+        assert!(parse_tmdb_url("https://www.themoviedb.org/person/505710").is_err());
+        assert!(parse_tmdb_url("https://example.com/movie/438631").is_err());
+    }
+
+    #[test]
+    fn test_parse_movie_query() {
+        let movie_query = parse_movie_query("Dune  2021").unwrap();
+        assert_eq!(movie_query.title, "Dune");
+        assert_eq!(movie_query.year, Some(2021));
+        assert_eq!(movie_query.text, "Dune 2021");
+
+        let movie_query = parse_movie_query("Blade Runner (1982)").unwrap();
+        assert_eq!(movie_query.title, "Blade Runner");
+        assert_eq!(movie_query.year, Some(1982));
+
+        let movie_query = parse_movie_query("The Lord of the Rings").unwrap();
+        assert_eq!(movie_query.title, "The Lord of the Rings");
+        assert_eq!(movie_query.year, None);
+
+        let movie_query = parse_movie_query("Apollo 1000").unwrap();
+        assert_eq!(movie_query.title, "Apollo 1000");
+        assert_eq!(movie_query.year, None);
+    }
+
+    #[test]
+    fn test_parse_search_query_movie() {
+        let search_query = parse_search_query("Dune 2021");
+        assert!(matches!(search_query, SearchQuery::MovieQuery(_)));
+        let search_query = parse_search_query("https://www.themoviedb.org/movie/438631");
+        assert!(matches!(
+            search_query,
+            SearchQuery::TmdbUrl(TmdbObject::Movie(438631)),
+        ));
+        // Single word can be a username or a title
+        let search_query = parse_search_query("Dune");
+        assert!(matches!(
+            search_query,
+            SearchQuery::ProfileOrMovieQuery(username, movie_query)
+                if username == "Dune" && movie_query.title == "Dune"
+        ));
+        let search_query = parse_search_query("@dune");
+        assert!(matches!(search_query, SearchQuery::ProfileQuery(_, None)));
+    }
+
     #[test]
     fn test_parse_tag_query() {
         let query = "#Activity";
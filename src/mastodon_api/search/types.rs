@@ -11,10 +11,17 @@ fn default_page_size() -> PageSize {
     PageSize::new(20)
 }
 
+fn default_resolve() -> bool {
+    true
+}
+
 #[derive(Deserialize)]
 pub struct SearchQueryParams {
     pub q: String,
 
+    #[serde(default = "default_resolve")]
+    pub resolve: bool,
+
     #[serde(default = "default_page_size")]
     pub limit: PageSize,
 }
@@ -28,6 +28,7 @@ async fn search_view(
         &current_user,
         db_client,
         query_params.q.trim(),
+        query_params.resolve,
         query_params.limit.inner(),
     )
     .await?;
@@ -1,12 +1,20 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use serde::Deserialize;
 use uuid::Uuid;
 
 use fedimovies_config::Config;
 use fedimovies_models::{
     database::{get_database_client, DatabaseClient, DatabaseError, DbPool},
+    notifications::queries::delete_mention_notifications,
+    posts::queries::{create_post, has_post_with_mention},
+    posts::types::{PostCreateData, Visibility},
     profiles::types::DbActorProfile,
     relationships::queries::{follow, get_followers, get_following, unfollow},
     users::types::User,
+    watch_log::queries::{create_watch_log_entry, get_watch_log_by_owner, has_watch_log_entry},
+    watch_log::types::{WatchLogEntry, WatchLogEntryCreateData},
 };
+use fedimovies_utils::html::escape_html;
 
 use crate::activitypub::{
     builders::{
@@ -14,10 +22,15 @@ use crate::activitypub::{
         undo_follow::prepare_undo_follow,
     },
     fetcher::helpers::get_or_import_profile_by_actor_address,
+    identifiers::local_actor_id,
     HandlerError,
 };
 use crate::errors::ValidationError;
 use crate::media::MediaStorage;
+use crate::tmdb::{
+    get_movie_metadata_provider, get_or_create_media_user, MovieError, MovieUsername,
+};
+use crate::validators::posts::{clean_content, validate_rating};
 use crate::webfinger::types::ActorAddress;
 
 fn export_profiles_to_csv(local_hostname: &str, profiles: Vec<DbActorProfile>) -> String {
@@ -49,6 +62,45 @@ pub async fn export_follows(
     Ok(csv)
 }
 
+fn export_watch_log_to_csv(local_hostname: &str, entries: Vec<WatchLogEntry>) -> String {
+    // Columns are similar to Letterboxd diary export
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer
+        .write_record(["Watched Date", "Name", "Account", "Rating", "Rewatch"])
+        .expect("header should be written");
+    for entry in entries {
+        let movie_address = ActorAddress::from_profile(local_hostname, &entry.movie);
+        let record = [
+            entry.watched_on.to_string(),
+            entry
+                .movie
+                .display_name
+                .unwrap_or_else(|| entry.movie.username.clone()),
+            movie_address.to_string(),
+            entry
+                .rating
+                .map(|rating| rating.to_string())
+                .unwrap_or_default(),
+            if entry.is_rewatch { "Yes" } else { "" }.to_string(),
+        ];
+        writer
+            .write_record(&record)
+            .expect("record should be written");
+    }
+    let csv = writer.into_inner().expect("buffer should be flushed");
+    String::from_utf8(csv).expect("CSV should be valid UTF-8")
+}
+
+pub async fn export_watch_log(
+    db_client: &impl DatabaseClient,
+    local_hostname: &str,
+    user_id: &Uuid,
+) -> Result<String, DatabaseError> {
+    let entries = get_watch_log_by_owner(db_client, user_id, Some(user_id), None, None).await?;
+    let csv = export_watch_log_to_csv(local_hostname, entries);
+    Ok(csv)
+}
+
 pub fn parse_address_list(csv: &str) -> Result<Vec<ActorAddress>, ValidationError> {
     let mut addresses: Vec<_> = csv
         .lines()
@@ -98,6 +150,207 @@ pub async fn import_follows_task(
     Ok(())
 }
 
+/// Row of Letterboxd export file (diary.csv, reviews.csv or ratings.csv)
+#[derive(Deserialize)]
+struct LetterboxdRecord {
+    #[serde(rename = "Date")]
+    date: NaiveDate,
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Year")]
+    year: Option<u32>,
+    #[serde(rename = "Rating", default)]
+    rating: Option<f32>,
+    #[serde(rename = "Review", default)]
+    review: Option<String>,
+    #[serde(rename = "Watched Date", default)]
+    watched_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LetterboxdEntry {
+    pub title: String,
+    pub year: u32,
+    pub watched_date: NaiveDate,
+    pub rating: Option<f32>,
+    pub review: Option<String>,
+}
+
+fn parse_letterboxd_csv(csv: &str) -> Result<Vec<LetterboxdRecord>, ValidationError> {
+    csv::Reader::from_reader(csv.as_bytes())
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .map_err(|error| ValidationError(format!("invalid CSV file: {}", error)))
+}
+
+/// Merges Letterboxd exports into a list of viewings.
+/// Reviews take precedence over diary entries of the same viewing,
+/// ratings are only used for films that have no diary entries.
+pub fn parse_letterboxd_export(
+    maybe_diary_csv: Option<&str>,
+    maybe_reviews_csv: Option<&str>,
+    maybe_ratings_csv: Option<&str>,
+) -> Result<Vec<LetterboxdEntry>, ValidationError> {
+    let mut entries: Vec<LetterboxdEntry> = vec![];
+    for (maybe_csv, is_ratings) in [
+        (maybe_reviews_csv, false),
+        (maybe_diary_csv, false),
+        (maybe_ratings_csv, true),
+    ] {
+        let csv = match maybe_csv {
+            Some(csv) => csv,
+            None => continue,
+        };
+        for record in parse_letterboxd_csv(csv)? {
+            let year = match record.year {
+                Some(year) => year,
+                // Films without release year can't be resolved
+                None => continue,
+            };
+            if let Some(rating) = record.rating {
+                validate_rating(rating)?;
+            };
+            let watched_date = record.watched_date.unwrap_or(record.date);
+            let is_duplicate = entries.iter().any(|entry| {
+                entry.title == record.name
+                    && entry.year == year
+                    && (is_ratings || entry.watched_date == watched_date)
+            });
+            if is_duplicate {
+                continue;
+            };
+            entries.push(LetterboxdEntry {
+                title: record.name,
+                year,
+                watched_date,
+                rating: record.rating,
+                review: record.review.filter(|review| !review.trim().is_empty()),
+            });
+        }
+    }
+    if entries.len() > 1000 {
+        return Err(ValidationError(
+            "can't process more than 1000 items at once".to_string(),
+        ));
+    };
+    entries.sort_by_key(|entry| entry.watched_date);
+    Ok(entries)
+}
+
+fn letterboxd_review_content(
+    instance_url: &str,
+    movie_user: &User,
+    review: &str,
+) -> Result<String, ValidationError> {
+    let mut content = format!(
+        r#"<p><span class="h-card"><a class="u-url mention" href="{}">@{}</a></span></p>"#,
+        local_actor_id(instance_url, &movie_user.profile.username),
+        movie_user.profile.username,
+    );
+    // Letterboxd reviews are plain text
+    for paragraph in review.split("\n\n").map(|text| text.trim()) {
+        if !paragraph.is_empty() {
+            content += &format!("<p>{}</p>", escape_html(paragraph).replace('\n', "<br>"));
+        };
+    }
+    clean_content(&content)
+}
+
+#[derive(Default)]
+pub struct LetterboxdImportResult {
+    pub imported_count: usize,
+    /// Titles that match more than one movie, with candidate usernames
+    pub ambiguous: Vec<(String, Vec<String>)>,
+}
+
+pub async fn import_letterboxd_task(
+    config: &Config,
+    current_user: User,
+    db_pool: &DbPool,
+    entries: Vec<LetterboxdEntry>,
+) -> Result<LetterboxdImportResult, anyhow::Error> {
+    let db_client = &mut **get_database_client(db_pool).await?;
+    let instance = config.instance();
+    let storage = MediaStorage::from(config);
+    let movie_provider = get_movie_metadata_provider(config);
+    let mut result = LetterboxdImportResult::default();
+    for entry in entries {
+        let movie_username = MovieUsername::new(&entry.title, entry.year).short_form();
+        let movie_user = match get_or_create_media_user(
+            &instance,
+            db_client,
+            movie_provider.as_deref(),
+            &storage.media_dir,
+            &movie_username,
+        )
+        .await
+        {
+            Ok(user) => user,
+            Err(MovieError::DatabaseError(db_error)) => return Err(db_error.into()),
+            Err(MovieError::Ambiguous(candidates)) => {
+                let title = format!("{} ({})", entry.title, entry.year);
+                result.ambiguous.push((title, candidates));
+                continue;
+            }
+            Err(other_error) => {
+                log::warn!("failed to import movie {}: {}", movie_username, other_error);
+                continue;
+            }
+        };
+        let review = match entry.review {
+            Some(review) => review,
+            None => {
+                // Viewings without review are added to watch log
+                if has_watch_log_entry(
+                    db_client,
+                    &current_user.id,
+                    &movie_user.id,
+                    &entry.watched_date,
+                )
+                .await?
+                {
+                    continue;
+                };
+                let entry_data = WatchLogEntryCreateData {
+                    movie_id: movie_user.id,
+                    watched_on: entry.watched_date,
+                    is_rewatch: false,
+                    rating: entry.rating,
+                    is_federated: false,
+                };
+                create_watch_log_entry(db_client, &current_user.id, entry_data).await?;
+                result.imported_count += 1;
+                continue;
+            }
+        };
+        let created_at = Utc.from_utc_datetime(
+            &entry
+                .watched_date
+                .and_hms_opt(0, 0, 0)
+                .expect("midnight should be valid"),
+        );
+        // Viewings imported earlier are skipped
+        if has_post_with_mention(db_client, &current_user.id, &movie_user.id, &created_at).await? {
+            continue;
+        };
+        let content = letterboxd_review_content(&instance.url(), &movie_user, &review)?;
+        let post_data = PostCreateData {
+            content,
+            visibility: Visibility::Public,
+            mentions: vec![movie_user.id],
+            rating: entry.rating,
+            created_at,
+            ..Default::default()
+        };
+        let post = create_post(db_client, &current_user.id, post_data).await?;
+        // Imported reviews are not federated and not boosted
+        // by movie accounts, so old posts don't flood timelines
+        delete_mention_notifications(db_client, &post.id).await?;
+        result.imported_count += 1;
+    }
+    Ok(result)
+}
+
 pub async fn move_followers_task(
     config: &Config,
     db_pool: &DbPool,
@@ -199,6 +452,32 @@ mod tests {
         assert_eq!(csv, "user1@example.org\nuser2@test.net\n");
     }
 
+    #[test]
+    fn test_export_watch_log_to_csv() {
+        let entry = WatchLogEntry {
+            id: Uuid::new_v4(),
+            owner: DbActorProfile::default(),
+            movie: DbActorProfile {
+                username: "Dune_2021_438631".to_string(),
+                display_name: Some("Dune, Part One".to_string()),
+                ..Default::default()
+            },
+            watched_on: NaiveDate::from_ymd_opt(2021, 10, 22).unwrap(),
+            is_rewatch: true,
+            rating: Some(4.5),
+            is_federated: false,
+            created_at: Utc::now(),
+        };
+        let csv = export_watch_log_to_csv("example.org", vec![entry]);
+        assert_eq!(
+            csv,
+            concat!(
+                "Watched Date,Name,Account,Rating,Rewatch\n",
+                "2021-10-22,\"Dune, Part One\",Dune_2021_438631@example.org,4.5,Yes\n",
+            ),
+        );
+    }
+
     #[test]
     fn test_parse_address_list() {
         let csv = concat!(
@@ -214,4 +493,79 @@ mod tests {
             .collect();
         assert_eq!(addresses, vec!["user1@example.net", "user2@example.com",]);
     }
+
+    #[test]
+    fn test_parse_letterboxd_export() {
+        let diary_csv = concat!(
+            "Date,Name,Year,Letterboxd URI,Rating,Rewatch,Tags,Watched Date\n",
+            "2021-10-23,Dune,2021,https://boxd.it/2AbC,4.5,,,2021-10-22\n",
+            "2022-03-05,Dune,2021,https://boxd.it/2AbD,5,Yes,,2022-03-04\n",
+            "2022-04-01,Untitled,,https://boxd.it/2AbE,,,,2022-04-01\n",
+        );
+        let reviews_csv = concat!(
+            "Date,Name,Year,Letterboxd URI,Rating,Rewatch,Review,Tags,Watched Date\n",
+            "2021-10-23,Dune,2021,https://boxd.it/2AbF,4.5,,\"Part one.\n\nWorth it, \"\"really\"\".\",,2021-10-22\n",
+        );
+        let ratings_csv = concat!(
+            "Date,Name,Year,Letterboxd URI,Rating\n",
+            "2022-03-05,Dune,2021,https://boxd.it/2AbG,5\n",
+            "2020-01-10,Arrival,2016,https://boxd.it/2AbH,4\n",
+        );
+        let entries =
+            parse_letterboxd_export(Some(diary_csv), Some(reviews_csv), Some(ratings_csv)).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].title, "Arrival");
+        assert_eq!(entries[0].year, 2016);
+        assert_eq!(entries[0].rating, Some(4.0));
+        assert_eq!(
+            entries[0].watched_date,
+            NaiveDate::from_ymd_opt(2020, 1, 10).unwrap(),
+        );
+        assert_eq!(entries[1].title, "Dune");
+        assert_eq!(
+            entries[1].watched_date,
+            NaiveDate::from_ymd_opt(2021, 10, 22).unwrap(),
+        );
+        assert_eq!(
+            entries[1].review.as_deref(),
+            Some("Part one.\n\nWorth it, \"really\"."),
+        );
+        assert_eq!(entries[2].title, "Dune");
+        assert_eq!(entries[2].rating, Some(5.0));
+        assert_eq!(entries[2].review, None);
+    }
+
+    #[test]
+    fn test_parse_letterboxd_export_invalid_rating() {
+        let ratings_csv = concat!(
+            "Date,Name,Year,Letterboxd URI,Rating\n",
+            "2020-01-10,Arrival,2016,https://boxd.it/2AbH,4.2\n",
+        );
+        let result = parse_letterboxd_export(None, None, Some(ratings_csv));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_letterboxd_review_content() {
+        let movie_user = User {
+            profile: DbActorProfile {
+                username: "Dune_2021".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let content = letterboxd_review_content(
+            "https://example.com",
+            &movie_user,
+            "Part <i>one</i> & two.\nSpice.\n\nWorth it.",
+        )
+        .unwrap();
+        assert_eq!(
+            content,
+            concat!(
+                r#"<p><span class="h-card"><a class="u-url mention" href="https://example.com/users/Dune_2021" rel="noopener">@Dune_2021</a></span></p>"#,
+                "<p>Part &lt;i&gt;one&lt;/i&gt; &amp; two.<br>Spice.</p><p>Worth it.</p>",
+            ),
+        );
+    }
 }
@@ -10,6 +10,13 @@ pub struct ImportFollowsRequest {
     pub follows_csv: String,
 }
 
+#[derive(Deserialize)]
+pub struct ImportLetterboxdRequest {
+    pub diary_csv: Option<String>,
+    pub reviews_csv: Option<String>,
+    pub ratings_csv: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct AddAliasRequest {
     pub acct: String,
@@ -4,6 +4,7 @@ use actix_web_httpauth::extractors::bearer::BearerAuth;
 use fedimovies_config::Config;
 use fedimovies_models::{
     database::{get_database_client, DatabaseError, DbPool},
+    posts::helpers::can_create_post,
     profiles::helpers::find_verified_aliases,
     profiles::queries::{get_profile_by_acct, get_profile_by_remote_actor_id, update_profile},
     profiles::types::ProfileUpdateData,
@@ -13,10 +14,12 @@ use fedimovies_models::{
 use fedimovies_utils::passwords::hash_password;
 
 use super::helpers::{
-    export_followers, export_follows, import_follows_task, move_followers_task, parse_address_list,
+    export_followers, export_follows, export_watch_log, import_follows_task,
+    import_letterboxd_task, move_followers_task, parse_address_list, parse_letterboxd_export,
 };
 use super::types::{
-    AddAliasRequest, ImportFollowsRequest, MoveFollowersRequest, PasswordChangeRequest,
+    AddAliasRequest, ImportFollowsRequest, ImportLetterboxdRequest, MoveFollowersRequest,
+    PasswordChangeRequest,
 };
 use crate::activitypub::{
     builders::update_person::prepare_update_person, identifiers::profile_actor_id,
@@ -141,6 +144,19 @@ async fn export_follows_view(
     Ok(response)
 }
 
+#[get("/export_watch_log")]
+async fn export_watch_log_view(
+    auth: BearerAuth,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let csv = export_watch_log(db_client, &config.instance().hostname(), &current_user.id).await?;
+    let response = HttpResponse::Ok().content_type("text/csv").body(csv);
+    Ok(response)
+}
+
 #[post("/import_follows")]
 async fn import_follows_view(
     auth: BearerAuth,
@@ -161,6 +177,47 @@ async fn import_follows_view(
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[post("/import_letterboxd")]
+async fn import_letterboxd_view(
+    auth: BearerAuth,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    request_data: web::Json<ImportLetterboxdRequest>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    if !can_create_post(&current_user) {
+        return Err(MastodonError::PermissionError);
+    };
+    let entries = parse_letterboxd_export(
+        request_data.diary_csv.as_deref(),
+        request_data.reviews_csv.as_deref(),
+        request_data.ratings_csv.as_deref(),
+    )?;
+    let username = current_user.profile.username.clone();
+    tokio::spawn(async move {
+        match import_letterboxd_task(&config, current_user, &db_pool, entries).await {
+            Ok(result) => {
+                log::info!(
+                    "imported {} Letterboxd entries for {}",
+                    result.imported_count,
+                    username,
+                );
+                for (title, candidates) in result.ambiguous {
+                    log::warn!(
+                        "skipped Letterboxd entry {} for {}: ambiguous title ({})",
+                        title,
+                        username,
+                        candidates.join(", "),
+                    );
+                }
+            }
+            Err(error) => log::error!("import letterboxd: {}", error),
+        };
+    });
+    Ok(HttpResponse::NoContent().finish())
+}
+
 #[post("/move_followers")]
 async fn move_followers(
     auth: BearerAuth,
@@ -228,6 +285,8 @@ pub fn settings_api_scope() -> Scope {
         .service(add_alias_view)
         .service(export_followers_view)
         .service(export_follows_view)
+        .service(export_watch_log_view)
         .service(import_follows_view)
+        .service(import_letterboxd_view)
         .service(move_followers)
 }
@@ -1,10 +1,10 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
 use fedimovies_models::{
     emojis::types::DbEmoji,
-    posts::types::{Post, Visibility},
+    posts::types::{DbPostEdit, Post, Visibility},
     profiles::types::DbActorProfile,
 };
 
@@ -65,6 +65,8 @@ pub struct Status {
     pub visibility: String,
     pub sensitive: bool,
     pub spoiler_text: Option<String>,
+    pub rating: Option<f32>,
+    pub language: Option<String>,
     pub replies_count: i32,
     pub favourites_count: i32,
     pub reblogs_count: i32,
@@ -76,6 +78,7 @@ pub struct Status {
     // Authorized user attributes
     pub favourited: bool,
     pub reblogged: bool,
+    pub bookmarked: bool,
 
     // Extra fields
     pub ipfs_cid: Option<String>,
@@ -136,7 +139,9 @@ impl Status {
             reblog: reblog,
             visibility: visibility.to_string(),
             sensitive: post.is_sensitive,
-            spoiler_text: None,
+            spoiler_text: post.summary,
+            rating: post.rating,
+            language: post.language,
             replies_count: post.reply_count,
             favourites_count: post.reaction_count,
             reblogs_count: post.repost_count,
@@ -152,6 +157,10 @@ impl Status {
                 .actions
                 .as_ref()
                 .map_or(false, |actions| actions.reposted),
+            bookmarked: post
+                .actions
+                .as_ref()
+                .map_or(false, |actions| actions.bookmarked),
             ipfs_cid: post.ipfs_cid,
             token_id: post.token_id,
             token_tx_id: post.token_tx_id,
@@ -178,13 +187,125 @@ pub struct StatusData {
     #[serde(default)]
     pub sensitive: bool,
 
+    pub spoiler_text: Option<String>,
+    pub language: Option<String>,
+
     // Not supported by Mastodon
     pub mentions: Option<Vec<Uuid>>,
+    pub rating: Option<f32>,
+
+    #[serde(default = "default_post_content_type")]
+    pub content_type: String,
+}
+
+/// https://docs.joinmastodon.org/methods/statuses/#edit
+#[derive(Deserialize)]
+pub struct StatusUpdateData {
+    pub status: String,
+
+    #[serde(rename = "media_ids[]")]
+    pub media_ids: Option<Vec<Uuid>>,
+
+    #[serde(default)]
+    pub sensitive: bool,
+
+    pub spoiler_text: Option<String>,
+    pub language: Option<String>,
+
+    // Not supported by Mastodon
+    // Explicit null removes the rating
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub rating: Option<Option<f32>>,
 
     #[serde(default = "default_post_content_type")]
     pub content_type: String,
 }
 
+fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// https://docs.joinmastodon.org/entities/StatusEdit/
+#[derive(Serialize)]
+pub struct StatusEdit {
+    content: String,
+    spoiler_text: String,
+    sensitive: bool,
+    rating: Option<f32>,
+    created_at: DateTime<Utc>,
+    account: Account,
+    media_attachments: Vec<Attachment>,
+    emojis: Vec<CustomEmoji>,
+}
+
+impl StatusEdit {
+    /// Creates a previous revision of the status.
+    /// Attachments and emojis are not stored in edit history.
+    pub fn from_db(
+        base_url: &str,
+        instance_url: &str,
+        edit: DbPostEdit,
+        author: DbActorProfile,
+    ) -> Self {
+        Self {
+            content: edit.content,
+            spoiler_text: edit.summary.unwrap_or_default(),
+            sensitive: edit.is_sensitive,
+            rating: edit.rating,
+            created_at: edit.created_at,
+            account: Account::from_profile(base_url, instance_url, author),
+            media_attachments: vec![],
+            emojis: vec![],
+        }
+    }
+
+    /// Creates the current revision of the status
+    pub fn from_post(base_url: &str, instance_url: &str, post: Post) -> Self {
+        let attachments = post
+            .attachments
+            .into_iter()
+            .map(|item| Attachment::from_db(base_url, item))
+            .collect();
+        let emojis = post
+            .emojis
+            .into_iter()
+            .map(|emoji| CustomEmoji::from_db(base_url, emoji))
+            .collect();
+        Self {
+            content: post.content,
+            spoiler_text: post.summary.unwrap_or_default(),
+            sensitive: post.is_sensitive,
+            rating: post.rating,
+            created_at: post.updated_at.unwrap_or(post.created_at),
+            account: Account::from_profile(base_url, instance_url, post.author),
+            media_attachments: attachments,
+            emojis: emojis,
+        }
+    }
+}
+
+/// https://docs.joinmastodon.org/entities/StatusSource/
+#[derive(Serialize)]
+pub struct StatusSource {
+    id: Uuid,
+    text: String,
+    spoiler_text: String,
+}
+
+impl StatusSource {
+    pub fn from_post(post: Post) -> Self {
+        Self {
+            id: post.id,
+            text: post.content_source.unwrap_or(post.content),
+            spoiler_text: post.summary.unwrap_or_default(),
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub struct StatusPreviewData {
     pub status: String,
@@ -219,3 +340,24 @@ pub struct Context {
 pub struct TransactionData {
     pub transaction_id: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_status_update_data_rating() {
+        let value = json!({"status": "test"});
+        let data: StatusUpdateData = serde_json::from_value(value).unwrap();
+        assert_eq!(data.rating, None);
+
+        let value = json!({"status": "test", "rating": null});
+        let data: StatusUpdateData = serde_json::from_value(value).unwrap();
+        assert_eq!(data.rating, Some(None));
+
+        let value = json!({"status": "test", "rating": 3.5});
+        let data: StatusUpdateData = serde_json::from_value(value).unwrap();
+        assert_eq!(data.rating, Some(Some(3.5)));
+    }
+}
@@ -1,30 +1,34 @@
 /// https://docs.joinmastodon.org/methods/statuses/
-use actix_web::{delete, dev::ConnectionInfo, get, post, web, HttpResponse, Scope};
+use actix_web::{delete, dev::ConnectionInfo, get, post, put, web, HttpResponse, Scope};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
 use chrono::Utc;
 use uuid::Uuid;
 
 use fedimovies_config::Config;
 use fedimovies_models::{
+    bookmarks::queries::{create_bookmark, delete_bookmark},
     database::{get_database_client, DatabaseError, DbPool},
-    posts::helpers::{can_create_post, can_view_post},
+    posts::helpers::{add_related_posts, add_user_actions, can_create_post, can_view_post},
     posts::queries::{
-        create_post, delete_post, find_reposts_by_user, get_post_by_id, get_thread,
-        set_post_ipfs_cid,
+        create_post, delete_post, find_reposts_by_user, get_post_by_id, get_post_edits, get_thread,
+        set_post_ipfs_cid, update_post,
     },
-    posts::types::{PostCreateData, Visibility},
+    posts::types::{PostCreateData, PostUpdateData, Visibility},
     reactions::queries::{create_reaction, delete_reaction},
     relationships::queries::get_subscribers,
 };
 use fedimovies_utils::markdown::markdown_lite_to_html;
 
 use super::helpers::{build_status, build_status_list, parse_microsyntaxes, PostContent};
-use super::types::{Context, Status, StatusData, StatusPreview, StatusPreviewData};
+use super::types::{
+    Context, Status, StatusData, StatusEdit, StatusPreview, StatusPreviewData, StatusSource,
+    StatusUpdateData,
+};
 use crate::activitypub::{
     builders::{
         announce::prepare_announce, create_note::prepare_create_note,
         delete_note::prepare_delete_note, like::prepare_like, undo_announce::prepare_undo_announce,
-        undo_like::prepare_undo_like,
+        undo_like::prepare_undo_like, update_note::prepare_update_note,
     },
     identifiers::local_object_id,
 };
@@ -33,8 +37,10 @@ use crate::http::{get_request_base_url, FormOrJson};
 use crate::ipfs::{posts::PostMetadata, store as ipfs_store};
 use crate::mastodon_api::{errors::MastodonError, oauth::auth::get_current_user};
 use crate::media::remove_media;
+use crate::tmdb::{prepare_undo_movie_reposts, undo_unmentioned_movie_reposts};
 use crate::validators::posts::{
-    clean_content, ATTACHMENT_LIMIT, EMOJI_LIMIT, LINK_LIMIT, MENTION_LIMIT,
+    clean_content, clean_summary, validate_language, validate_rating, ATTACHMENT_LIMIT,
+    EMOJI_LIMIT, LINK_LIMIT, MENTION_LIMIT,
 };
 
 #[post("")]
@@ -60,6 +66,7 @@ async fn create_status(
         Some(_) => return Err(ValidationError("invalid visibility parameter".to_string()).into()),
         None => Visibility::Public,
     };
+    let content_source = status_data.status.clone();
     let content = match status_data.content_type.as_str() {
         "text/html" => status_data.status,
         "text/markdown" => markdown_lite_to_html(&status_data.status)
@@ -147,6 +154,21 @@ async fn create_status(
     if attachments.len() > ATTACHMENT_LIMIT {
         return Err(ValidationError("too many attachments".to_string()).into());
     };
+    // Validate rating
+    if let Some(rating) = status_data.rating {
+        validate_rating(rating)?;
+    };
+    // Validate language
+    if let Some(ref language) = status_data.language {
+        validate_language(language)?;
+    };
+    // Content warning
+    let summary = match status_data.spoiler_text {
+        Some(ref spoiler_text) => clean_summary(spoiler_text)?,
+        None => None,
+    };
+    // Posts with content warning are always sensitive
+    let is_sensitive = status_data.sensitive || summary.is_some();
 
     // Create post
     let post_data = PostCreateData {
@@ -154,13 +176,18 @@ async fn create_status(
         in_reply_to_id: status_data.in_reply_to_id,
         repost_of_id: None,
         visibility: visibility,
-        is_sensitive: status_data.sensitive,
+        is_sensitive: is_sensitive,
         attachments: attachments,
         mentions: mentions,
         tags: hashtags,
         links: links,
         emojis: emojis,
         object_id: None,
+        // Keep existing values if fields are not present
+        rating: status_data.rating,
+        language: status_data.language,
+        summary: summary,
+        content_source: Some(content_source),
         created_at: Utc::now(),
     };
     let mut post = create_post(db_client, &current_user.id, post_data).await?;
@@ -253,17 +280,202 @@ async fn delete_status(
     if post.author.id != current_user.id {
         return Err(MastodonError::PermissionError);
     };
-    let delete_note =
-        prepare_delete_note(db_client, &config.instance(), &current_user, &post).await?;
+    let instance = config.instance();
+    let delete_note = prepare_delete_note(db_client, &instance, &current_user, &post).await?;
+    let undo_reposts = prepare_undo_movie_reposts(db_client, &instance, &post).await?;
     let deletion_queue = delete_post(db_client, &status_id).await?;
     tokio::spawn(async move {
         remove_media(&config, deletion_queue).await;
     });
     delete_note.enqueue(db_client).await?;
+    for undo_repost in undo_reposts {
+        undo_repost.enqueue(db_client).await?;
+    }
 
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[put("/{status_id}")]
+async fn edit_status(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    status_id: web::Path<Uuid>,
+    status_data: FormOrJson<StatusUpdateData>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &mut **get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let post = get_post_by_id(db_client, &status_id).await?;
+    if post.author.id != current_user.id {
+        return Err(MastodonError::PermissionError);
+    };
+    if post.repost_of_id.is_some() {
+        return Err(ValidationError("can't edit repost".to_string()).into());
+    };
+    if post.ipfs_cid.is_some() {
+        return Err(ValidationError("post is immutable".to_string()).into());
+    };
+    let instance = config.instance();
+    let status_data = status_data.into_inner();
+    let content_source = status_data.status.clone();
+    let content = match status_data.content_type.as_str() {
+        "text/html" => status_data.status,
+        "text/markdown" => markdown_lite_to_html(&status_data.status)
+            .map_err(|_| ValidationError("invalid markdown".to_string()))?,
+        _ => return Err(ValidationError("unsupported content type".to_string()).into()),
+    };
+    // Parse content
+    let PostContent {
+        mut content,
+        mut mentions,
+        hashtags,
+        links,
+        emojis,
+        ..
+    } = parse_microsyntaxes(db_client, &instance, content).await?;
+    // Clean content
+    content = clean_content(&content)?;
+
+    // Audience of non-public posts can't be changed
+    let audience: Vec<_> = post.mentions.iter().map(|profile| profile.id).collect();
+    if post.visibility != Visibility::Public && !mentions.iter().all(|id| audience.contains(id)) {
+        return Err(ValidationError("audience can't be expanded".to_string()).into());
+    };
+    if post.visibility != Visibility::Public {
+        // Previously mentioned actors remain in audience
+        mentions.extend(audience);
+    };
+    mentions.sort();
+    mentions.dedup();
+    if mentions.len() > MENTION_LIMIT {
+        return Err(ValidationError("too many mentions".to_string()).into());
+    };
+
+    // Links validation
+    if links.len() > 0 && post.visibility != Visibility::Public {
+        return Err(ValidationError("can't add links to non-public posts".to_string()).into());
+    };
+    if links.len() > LINK_LIMIT {
+        return Err(ValidationError("too many links".to_string()).into());
+    };
+
+    // Emoji validation
+    let emojis: Vec<_> = emojis.iter().map(|emoji| emoji.id).collect();
+    if emojis.len() > EMOJI_LIMIT {
+        return Err(ValidationError("too many emojis".to_string()).into());
+    };
+
+    // Validate attachments
+    let attachments = status_data.media_ids.unwrap_or(vec![]);
+    if attachments.len() > ATTACHMENT_LIMIT {
+        return Err(ValidationError("too many attachments".to_string()).into());
+    };
+    // Validate rating. Explicit null removes the rating,
+    // existing value is kept if the field is not present
+    let rating = match status_data.rating {
+        Some(Some(rating)) => {
+            validate_rating(rating)?;
+            Some(rating)
+        }
+        Some(None) => None,
+        None => post.rating,
+    };
+    // Validate language
+    if let Some(ref language) = status_data.language {
+        validate_language(language)?;
+    };
+    let language = status_data.language.or(post.language.clone());
+    // Content warning
+    let summary = match status_data.spoiler_text {
+        Some(ref spoiler_text) => clean_summary(spoiler_text)?,
+        None => None,
+    };
+    let is_sensitive = status_data.sensitive || summary.is_some();
+
+    // Update post
+    let post_data = PostUpdateData {
+        content: content,
+        content_source: Some(content_source),
+        is_sensitive: is_sensitive,
+        rating: rating,
+        language: language,
+        summary: summary,
+        attachments: attachments,
+        mentions: mentions,
+        tags: hashtags,
+        links: links,
+        emojis: emojis,
+        updated_at: Utc::now(),
+    };
+    let deletion_queue = update_post(db_client, &post.id, post_data).await?;
+    tokio::spawn(async move {
+        remove_media(&config, deletion_queue).await;
+    });
+    let mut post = get_post_by_id(db_client, &post.id).await?;
+    add_related_posts(db_client, vec![&mut post]).await?;
+    // Movies that are no longer mentioned should undo their reposts
+    undo_unmentioned_movie_reposts(db_client, &instance, &post).await?;
+    // Federate
+    prepare_update_note(db_client, &instance, &current_user, &post)
+        .await?
+        .enqueue(db_client)
+        .await?;
+
+    add_user_actions(db_client, &current_user.id, vec![&mut post]).await?;
+    let status = Status::from_post(
+        &get_request_base_url(connection_info),
+        &instance.url(),
+        post,
+    );
+    Ok(HttpResponse::Ok().json(status))
+}
+
+#[get("/{status_id}/history")]
+async fn get_status_history(
+    auth: Option<BearerAuth>,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    status_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let maybe_current_user = match auth {
+        Some(auth) => Some(get_current_user(db_client, auth.token()).await?),
+        None => None,
+    };
+    let post = get_post_by_id(db_client, &status_id).await?;
+    if !can_view_post(db_client, maybe_current_user.as_ref(), &post).await? {
+        return Err(MastodonError::NotFoundError("post"));
+    };
+    let base_url = get_request_base_url(connection_info);
+    let instance_url = config.instance_url();
+    // Oldest revision first, current revision last
+    let mut history: Vec<StatusEdit> = get_post_edits(db_client, &post.id)
+        .await?
+        .into_iter()
+        .map(|edit| StatusEdit::from_db(&base_url, &instance_url, edit, post.author.clone()))
+        .collect();
+    history.push(StatusEdit::from_post(&base_url, &instance_url, post));
+    Ok(HttpResponse::Ok().json(history))
+}
+
+#[get("/{status_id}/source")]
+async fn get_status_source(
+    auth: BearerAuth,
+    db_pool: web::Data<DbPool>,
+    status_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let post = get_post_by_id(db_client, &status_id).await?;
+    if post.author.id != current_user.id {
+        return Err(MastodonError::PermissionError);
+    };
+    let source = StatusSource::from_post(post);
+    Ok(HttpResponse::Ok().json(source))
+}
+
 #[get("/{status_id}/context")]
 async fn get_context(
     auth: Option<BearerAuth>,
@@ -437,6 +649,65 @@ async fn unfavourite(
     Ok(HttpResponse::Ok().json(status))
 }
 
+#[post("/{status_id}/bookmark")]
+async fn bookmark(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    status_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let post = get_post_by_id(db_client, &status_id).await?;
+    if post.repost_of_id.is_some() || !can_view_post(db_client, Some(&current_user), &post).await? {
+        return Err(MastodonError::NotFoundError("post"));
+    };
+    match create_bookmark(db_client, &current_user.id, &post.id).await {
+        Ok(_) => (),
+        Err(DatabaseError::AlreadyExists(_)) => (), // post already bookmarked
+        Err(other_error) => return Err(other_error.into()),
+    };
+
+    let status = build_status(
+        db_client,
+        &get_request_base_url(connection_info),
+        &config.instance_url(),
+        Some(&current_user),
+        post,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(status))
+}
+
+#[post("/{status_id}/unbookmark")]
+async fn unbookmark(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    status_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let post = get_post_by_id(db_client, &status_id).await?;
+    match delete_bookmark(db_client, &current_user.id, &post.id).await {
+        Ok(_) => (),
+        Err(DatabaseError::NotFound(_)) => (), // post not bookmarked
+        Err(other_error) => return Err(other_error.into()),
+    };
+
+    let status = build_status(
+        db_client,
+        &get_request_base_url(connection_info),
+        &config.instance_url(),
+        Some(&current_user),
+        post,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(status))
+}
+
 #[post("/{status_id}/reblog")]
 async fn reblog(
     auth: BearerAuth,
@@ -594,10 +865,15 @@ pub fn status_api_scope() -> Scope {
         // Routes with status ID
         .service(get_status)
         .service(delete_status)
+        .service(edit_status)
+        .service(get_status_history)
+        .service(get_status_source)
         .service(get_context)
         .service(get_thread_view)
         .service(favourite)
         .service(unfavourite)
+        .service(bookmark)
+        .service(unbookmark)
         .service(reblog)
         .service(unreblog)
         .service(make_permanent)
@@ -1,6 +1,8 @@
 use serde::Deserialize;
 use uuid::Uuid;
 
+use fedimovies_models::posts::types::ReviewFilter;
+
 use crate::mastodon_api::pagination::PageSize;
 
 fn default_page_size() -> PageSize {
@@ -14,3 +16,34 @@ pub struct TimelineQueryParams {
     #[serde(default = "default_page_size")]
     pub limit: PageSize,
 }
+
+/// Query parameters of movie reviews timeline (not a part of Mastodon API)
+#[derive(Deserialize)]
+pub struct MovieTimelineQueryParams {
+    pub max_id: Option<Uuid>,
+
+    #[serde(default = "default_page_size")]
+    pub limit: PageSize,
+
+    #[serde(default)]
+    pub only_rated: bool,
+
+    #[serde(default)]
+    pub local: bool,
+
+    #[serde(default)]
+    pub remote: bool,
+
+    pub language: Option<String>,
+}
+
+impl MovieTimelineQueryParams {
+    pub fn to_filter(&self) -> ReviewFilter {
+        ReviewFilter {
+            only_rated: self.only_rated,
+            only_local: self.local,
+            only_remote: self.remote,
+            language: self.language.clone(),
+        }
+    }
+}
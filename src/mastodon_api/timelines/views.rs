@@ -1,18 +1,26 @@
 /// https://docs.joinmastodon.org/methods/timelines/
 use actix_web::{dev::ConnectionInfo, get, web, HttpResponse, Scope};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
+use uuid::Uuid;
 
 use fedimovies_config::Config;
 use fedimovies_models::{
     database::{get_database_client, DbPool},
-    posts::queries::{get_home_timeline, get_local_timeline, get_posts_by_tag},
+    lists::queries::get_list,
+    posts::queries::{
+        get_home_timeline, get_list_timeline, get_local_timeline, get_posts_by_mentioned_profile,
+        get_posts_by_tag,
+    },
+    profiles::queries::get_profile_by_id,
 };
 
-use super::types::TimelineQueryParams;
+use super::types::{MovieTimelineQueryParams, TimelineQueryParams};
+use crate::errors::ValidationError;
 use crate::http::get_request_base_url;
 use crate::mastodon_api::{
     errors::MastodonError, oauth::auth::get_current_user, statuses::helpers::build_status_list,
 };
+use crate::validators::posts::validate_language;
 
 #[get("/home")]
 async fn home_timeline(
@@ -104,9 +112,89 @@ async fn hashtag_timeline(
     Ok(HttpResponse::Ok().json(statuses))
 }
 
+/// Posts mentioning the movie or TV show, including remote reviews
+#[get("/movie/{account_id}")]
+async fn movie_timeline(
+    auth: Option<BearerAuth>,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+    query_params: web::Query<MovieTimelineQueryParams>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let maybe_current_user = match auth {
+        Some(auth) => Some(get_current_user(db_client, auth.token()).await?),
+        None => None,
+    };
+    if query_params.local && query_params.remote {
+        return Err(
+            ValidationError("local and remote filters can't be combined".to_string()).into(),
+        );
+    };
+    if let Some(ref language) = query_params.language {
+        validate_language(language)?;
+    };
+    let profile = get_profile_by_id(db_client, &account_id).await?;
+    if !profile.is_media() {
+        return Err(MastodonError::NotFoundError("movie"));
+    };
+    let posts = get_posts_by_mentioned_profile(
+        db_client,
+        &profile.id,
+        maybe_current_user.as_ref().map(|user| &user.id),
+        &query_params.to_filter(),
+        query_params.max_id,
+        query_params.limit.inner(),
+    )
+    .await?;
+    let statuses = build_status_list(
+        db_client,
+        &get_request_base_url(connection_info),
+        &config.instance_url(),
+        maybe_current_user.as_ref(),
+        posts,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(statuses))
+}
+
+#[get("/list/{list_id}")]
+async fn list_timeline(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    list_id: web::Path<Uuid>,
+    query_params: web::Query<TimelineQueryParams>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let list = get_list(db_client, &list_id, &current_user.id).await?;
+    let posts = get_list_timeline(
+        db_client,
+        &list.id,
+        &current_user.id,
+        query_params.max_id,
+        query_params.limit.inner(),
+    )
+    .await?;
+    let statuses = build_status_list(
+        db_client,
+        &get_request_base_url(connection_info),
+        &config.instance_url(),
+        Some(&current_user),
+        posts,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(statuses))
+}
+
 pub fn timeline_api_scope() -> Scope {
     web::scope("/api/v1/timelines")
         .service(home_timeline)
         .service(public_timeline)
         .service(hashtag_timeline)
+        .service(movie_timeline)
+        .service(list_timeline)
 }
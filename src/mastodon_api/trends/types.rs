@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use fedimovies_models::{profiles::types::DbActorProfile, trends::types::TrendingTag};
+
+use crate::activitypub::identifiers::local_tag_collection;
+use crate::mastodon_api::{accounts::types::Account, pagination::PageSize};
+
+fn default_trending_statuses_page_size() -> PageSize {
+    PageSize::new(20)
+}
+
+fn default_trends_page_size() -> PageSize {
+    PageSize::new(10)
+}
+
+/// https://docs.joinmastodon.org/methods/trends/#statuses
+#[derive(Deserialize)]
+pub struct TrendingStatusesQueryParams {
+    #[serde(default)]
+    pub offset: u16,
+
+    #[serde(default = "default_trending_statuses_page_size")]
+    pub limit: PageSize,
+}
+
+/// https://docs.joinmastodon.org/methods/trends/#tags
+#[derive(Deserialize)]
+pub struct TrendsQueryParams {
+    #[serde(default)]
+    pub offset: u16,
+
+    #[serde(default = "default_trends_page_size")]
+    pub limit: PageSize,
+}
+
+/// https://docs.joinmastodon.org/entities/Tag/#history
+#[derive(Serialize)]
+struct TagHistory {
+    day: String,
+    uses: String,
+    accounts: String,
+}
+
+/// https://docs.joinmastodon.org/entities/Tag/
+#[derive(Serialize)]
+pub struct ApiTrendingTag {
+    name: String,
+    url: String,
+    history: Vec<TagHistory>,
+}
+
+impl ApiTrendingTag {
+    /// History contains a single entry (current day)
+    /// with totals for the whole trend period
+    pub fn from_db(instance_url: &str, tag: TrendingTag, day: DateTime<Utc>) -> Self {
+        let history = TagHistory {
+            day: day.timestamp().to_string(),
+            uses: tag.uses.to_string(),
+            accounts: tag.accounts.to_string(),
+        };
+        Self {
+            url: local_tag_collection(instance_url, &tag.tag_name),
+            name: tag.tag_name,
+            history: vec![history],
+        }
+    }
+}
+
+/// Trending movie or TV show (not a part of Mastodon API)
+#[derive(Serialize)]
+pub struct TrendingMovie {
+    account: Account,
+    reviews_count: i64,
+}
+
+impl TrendingMovie {
+    pub fn from_profile(
+        base_url: &str,
+        instance_url: &str,
+        profile: DbActorProfile,
+        reviews_count: i64,
+    ) -> Self {
+        Self {
+            account: Account::from_profile(base_url, instance_url, profile),
+            reviews_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const INSTANCE_URL: &str = "https://example.com";
+
+    #[test]
+    fn test_trending_tag_from_db() {
+        let tag = TrendingTag {
+            tag_name: "scifi".to_string(),
+            uses: 5,
+            accounts: 3,
+        };
+        let day = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+        let tag = ApiTrendingTag::from_db(INSTANCE_URL, tag, day);
+        assert_eq!(tag.name, "scifi");
+        assert_eq!(tag.url, "https://example.com/collections/tags/scifi");
+        assert_eq!(tag.history.len(), 1);
+        assert_eq!(tag.history[0].day, "1672531200");
+        assert_eq!(tag.history[0].uses, "5");
+        assert_eq!(tag.history[0].accounts, "3");
+    }
+}
@@ -0,0 +1,130 @@
+/// https://docs.joinmastodon.org/methods/trends/
+use actix_web::{dev::ConnectionInfo, get, web, HttpResponse, Scope};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use chrono::{TimeZone, Utc};
+
+use fedimovies_config::Config;
+use fedimovies_models::{
+    database::{get_database_client, DbPool},
+    posts::queries::get_posts_by_ids,
+    profiles::queries::get_profiles_by_ids,
+    trends::queries::get_trends,
+};
+
+use super::types::{ApiTrendingTag, TrendingMovie, TrendingStatusesQueryParams, TrendsQueryParams};
+use crate::http::get_request_base_url;
+use crate::mastodon_api::{
+    errors::MastodonError, oauth::auth::get_current_user, statuses::helpers::build_status_list,
+};
+
+fn get_page<T>(items: Vec<T>, offset: u16, limit: u16) -> Vec<T> {
+    items
+        .into_iter()
+        .skip(offset.into())
+        .take(limit.into())
+        .collect()
+}
+
+#[get("/statuses")]
+async fn trending_statuses_view(
+    auth: Option<BearerAuth>,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    query_params: web::Query<TrendingStatusesQueryParams>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let maybe_current_user = match auth {
+        Some(auth) => Some(get_current_user(db_client, auth.token()).await?),
+        None => None,
+    };
+    let trends = get_trends(db_client).await?;
+    let posts_ids = get_page(
+        trends.posts,
+        query_params.offset,
+        query_params.limit.inner(),
+    );
+    let mut posts = get_posts_by_ids(db_client, &posts_ids).await?;
+    // Restore the order of cached trends, deleted posts are skipped
+    posts.sort_by_key(|post| posts_ids.iter().position(|post_id| *post_id == post.id));
+    let statuses = build_status_list(
+        db_client,
+        &get_request_base_url(connection_info),
+        &config.instance_url(),
+        maybe_current_user.as_ref(),
+        posts,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(statuses))
+}
+
+#[get("/tags")]
+async fn trending_tags_view(
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    query_params: web::Query<TrendsQueryParams>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let trends = get_trends(db_client).await?;
+    let instance_url = config.instance_url();
+    let today = Utc.from_utc_datetime(
+        &Utc::now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight should be valid"),
+    );
+    let tags: Vec<ApiTrendingTag> =
+        get_page(trends.tags, query_params.offset, query_params.limit.inner())
+            .into_iter()
+            .map(|tag| ApiTrendingTag::from_db(&instance_url, tag, today))
+            .collect();
+    Ok(HttpResponse::Ok().json(tags))
+}
+
+#[get("/movies")]
+async fn trending_movies_view(
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    query_params: web::Query<TrendsQueryParams>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let trends = get_trends(db_client).await?;
+    let trending_movies = get_page(
+        trends.movies,
+        query_params.offset,
+        query_params.limit.inner(),
+    );
+    let profiles_ids: Vec<_> = trending_movies
+        .iter()
+        .map(|movie| movie.profile_id)
+        .collect();
+    let mut profiles = get_profiles_by_ids(db_client, &profiles_ids).await?;
+    let base_url = get_request_base_url(connection_info);
+    let instance_url = config.instance_url();
+    let mut movies = vec![];
+    for trending_movie in trending_movies {
+        // Profile could be deleted after trends were updated
+        let maybe_index = profiles
+            .iter()
+            .position(|profile| profile.id == trending_movie.profile_id);
+        if let Some(index) = maybe_index {
+            let profile = profiles.swap_remove(index);
+            let movie = TrendingMovie::from_profile(
+                &base_url,
+                &instance_url,
+                profile,
+                trending_movie.review_count,
+            );
+            movies.push(movie);
+        };
+    }
+    Ok(HttpResponse::Ok().json(movies))
+}
+
+pub fn trends_api_scope() -> Scope {
+    web::scope("/api/v1/trends")
+        .service(trending_statuses_view)
+        .service(trending_tags_view)
+        .service(trending_movies_view)
+}
@@ -0,0 +1,56 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use fedimovies_models::watch_log::types::WatchLogEntry;
+
+use crate::mastodon_api::{accounts::types::Account, pagination::PageSize};
+
+#[derive(Deserialize)]
+pub struct WatchLogEntryData {
+    pub movie_id: Uuid,
+    pub watched_on: Option<NaiveDate>,
+    #[serde(default)]
+    pub rewatch: bool,
+    pub rating: Option<f32>,
+    #[serde(default)]
+    pub federate: bool,
+}
+
+fn default_watch_log_page_size() -> PageSize {
+    PageSize::new(20)
+}
+
+#[derive(Deserialize)]
+pub struct WatchLogQueryParams {
+    pub max_id: Option<Uuid>,
+
+    #[serde(default = "default_watch_log_page_size")]
+    pub limit: PageSize,
+}
+
+/// Watch log entry (not a part of Mastodon API)
+#[derive(Serialize)]
+pub struct ApiWatchLogEntry {
+    pub id: Uuid,
+    pub account: Account,
+    pub movie: Account,
+    pub watched_on: NaiveDate,
+    pub rewatch: bool,
+    pub rating: Option<f32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiWatchLogEntry {
+    pub fn from_db(base_url: &str, instance_url: &str, entry: WatchLogEntry) -> Self {
+        Self {
+            id: entry.id,
+            account: Account::from_profile(base_url, instance_url, entry.owner),
+            movie: Account::from_profile(base_url, instance_url, entry.movie),
+            watched_on: entry.watched_on,
+            rewatch: entry.is_rewatch,
+            rating: entry.rating,
+            created_at: entry.created_at,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use actix_web::{delete, dev::ConnectionInfo, get, post, web, HttpResponse, Scope};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use chrono::Utc;
+use uuid::Uuid;
+
+use fedimovies_config::Config;
+use fedimovies_models::{
+    database::{get_database_client, DbPool},
+    relationships::queries::has_block,
+    watch_log::queries::{create_watch_log_entry, delete_watch_log_entry, get_watch_log_entry},
+    watch_log::types::WatchLogEntryCreateData,
+};
+
+use super::types::{ApiWatchLogEntry, WatchLogEntryData};
+use crate::activitypub::builders::{
+    undo_view_movie::prepare_undo_view_movie, view_movie::prepare_view_movie,
+};
+use crate::errors::ValidationError;
+use crate::http::{get_request_base_url, FormOrJson};
+use crate::mastodon_api::{errors::MastodonError, oauth::auth::get_current_user};
+use crate::validators::posts::validate_rating;
+
+#[post("")]
+async fn create_entry_view(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    entry_data: FormOrJson<WatchLogEntryData>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let entry_data = entry_data.into_inner();
+    let today = Utc::now().date_naive();
+    let watched_on = entry_data.watched_on.unwrap_or(today);
+    if watched_on > today {
+        return Err(ValidationError("watch date is in the future".to_string()).into());
+    };
+    if let Some(rating) = entry_data.rating {
+        validate_rating(rating)?;
+    };
+    let db_entry_data = WatchLogEntryCreateData {
+        movie_id: entry_data.movie_id,
+        watched_on,
+        is_rewatch: entry_data.rewatch,
+        rating: entry_data.rating,
+        is_federated: entry_data.federate,
+    };
+    let db_entry = create_watch_log_entry(db_client, &current_user.id, db_entry_data).await?;
+    let entry = get_watch_log_entry(db_client, &db_entry.id).await?;
+    let instance = config.instance();
+    if entry_data.federate {
+        prepare_view_movie(db_client, &instance, &current_user, &entry)
+            .await?
+            .enqueue(db_client)
+            .await?;
+    };
+    let entry = ApiWatchLogEntry::from_db(
+        &get_request_base_url(connection_info),
+        &instance.url(),
+        entry,
+    );
+    Ok(HttpResponse::Ok().json(entry))
+}
+
+#[get("/{entry_id}")]
+async fn get_entry_view(
+    auth: Option<BearerAuth>,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    entry_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let maybe_current_user = match auth {
+        Some(auth) => Some(get_current_user(db_client, auth.token()).await?),
+        None => None,
+    };
+    let entry = get_watch_log_entry(db_client, &entry_id).await?;
+    let is_owner = maybe_current_user
+        .as_ref()
+        .map_or(false, |user| user.id == entry.owner.id);
+    if !is_owner {
+        // Entries that were not shared are visible only to the owner
+        if !entry.is_federated {
+            return Err(MastodonError::NotFoundError("watch log entry"));
+        };
+        if let Some(ref current_user) = maybe_current_user {
+            if has_block(db_client, &current_user.id, &entry.owner.id).await? {
+                return Err(MastodonError::NotFoundError("watch log entry"));
+            };
+        };
+    };
+    let entry = ApiWatchLogEntry::from_db(
+        &get_request_base_url(connection_info),
+        &config.instance_url(),
+        entry,
+    );
+    Ok(HttpResponse::Ok().json(entry))
+}
+
+#[delete("/{entry_id}")]
+async fn delete_entry_view(
+    auth: BearerAuth,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    entry_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let entry = get_watch_log_entry(db_client, &entry_id).await?;
+    if entry.owner.id != current_user.id {
+        return Err(MastodonError::NotFoundError("watch log entry"));
+    };
+    delete_watch_log_entry(db_client, &entry.id, &current_user.id).await?;
+    if entry.is_federated {
+        prepare_undo_view_movie(db_client, &config.instance(), &current_user, &entry)
+            .await?
+            .enqueue(db_client)
+            .await?;
+    };
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn watch_log_api_scope() -> Scope {
+    web::scope("/api/v1/watch_log")
+        .service(create_entry_view)
+        .service(get_entry_view)
+        .service(delete_entry_view)
+}
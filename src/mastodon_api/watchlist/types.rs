@@ -0,0 +1,16 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::mastodon_api::pagination::PageSize;
+
+fn default_watchlist_page_size() -> PageSize {
+    PageSize::new(40)
+}
+
+#[derive(Deserialize)]
+pub struct WatchlistQueryParams {
+    pub max_id: Option<Uuid>,
+
+    #[serde(default = "default_watchlist_page_size")]
+    pub limit: PageSize,
+}
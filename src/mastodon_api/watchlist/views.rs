@@ -0,0 +1,87 @@
+use actix_web::{delete, dev::ConnectionInfo, get, post, web, HttpRequest, HttpResponse, Scope};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use uuid::Uuid;
+
+use fedimovies_config::Config;
+use fedimovies_models::{
+    database::{get_database_client, DbPool},
+    profiles::queries::get_profile_by_id,
+    watchlist::queries::{add_to_watchlist, get_watchlist, remove_from_watchlist},
+};
+
+use super::types::WatchlistQueryParams;
+use crate::http::get_request_base_url;
+use crate::mastodon_api::{
+    accounts::types::Account, errors::MastodonError, oauth::auth::get_current_user,
+    pagination::get_paginated_response,
+};
+
+#[get("")]
+async fn get_watchlist_view(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    query_params: web::Query<WatchlistQueryParams>,
+    request: HttpRequest,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let entries = get_watchlist(
+        db_client,
+        &current_user.id,
+        query_params.max_id,
+        query_params.limit.inner(),
+    )
+    .await?;
+    let max_index = usize::from(query_params.limit.inner().saturating_sub(1));
+    let maybe_last_id = entries.get(max_index).map(|entry| entry.id);
+    let base_url = get_request_base_url(connection_info);
+    let instance_url = config.instance_url();
+    let accounts: Vec<Account> = entries
+        .into_iter()
+        .map(|entry| Account::from_profile(&base_url, &instance_url, entry.movie))
+        .collect();
+    let response =
+        get_paginated_response(&instance_url, request.uri().path(), accounts, maybe_last_id);
+    Ok(response)
+}
+
+#[post("/{account_id}")]
+async fn add_to_watchlist_view(
+    auth: BearerAuth,
+    connection_info: ConnectionInfo,
+    config: web::Data<Config>,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    let profile = get_profile_by_id(db_client, &account_id).await?;
+    add_to_watchlist(db_client, &current_user.id, &profile.id).await?;
+    let account = Account::from_profile(
+        &get_request_base_url(connection_info),
+        &config.instance_url(),
+        profile,
+    );
+    Ok(HttpResponse::Ok().json(account))
+}
+
+#[delete("/{account_id}")]
+async fn remove_from_watchlist_view(
+    auth: BearerAuth,
+    db_pool: web::Data<DbPool>,
+    account_id: web::Path<Uuid>,
+) -> Result<HttpResponse, MastodonError> {
+    let db_client = &**get_database_client(&db_pool).await?;
+    let current_user = get_current_user(db_client, auth.token()).await?;
+    remove_from_watchlist(db_client, &current_user.id, &account_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn watchlist_api_scope() -> Scope {
+    web::scope("/api/v1/watchlist")
+        .service(get_watchlist_view)
+        .service(add_to_watchlist_view)
+        .service(remove_from_watchlist_view)
+}
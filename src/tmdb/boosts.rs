@@ -0,0 +1,223 @@
+use fedimovies_config::{BoostPolicyConfig, Config, Instance};
+use fedimovies_models::{
+    database::{DatabaseClient, DatabaseError},
+    posts::queries::{delete_post, find_media_reposts, has_reposted_author},
+    posts::types::Post,
+    profiles::types::DbActorProfile,
+    users::queries::get_user_by_id,
+};
+
+use crate::activitypub::{
+    builders::undo_announce::prepare_undo_announce,
+    deliverer::OutgoingActivity,
+    vocabulary::{APPLICATION, SERVICE},
+};
+
+fn is_bot(profile: &DbActorProfile) -> bool {
+    if profile.is_media() {
+        return true;
+    };
+    profile.actor_json.as_ref().map_or(false, |actor| {
+        actor.object_type == SERVICE || actor.object_type == APPLICATION
+    })
+}
+
+/// Returns the reason why post can't be reposted by a movie account,
+/// or None if repost is allowed
+pub fn get_boost_rejection_reason(
+    policy: &BoostPolicyConfig,
+    blocked_instances: &[String],
+    post: &Post,
+) -> Option<&'static str> {
+    if !post.is_public() {
+        return Some("post is not public");
+    };
+    if post.repost_of_id.is_some() {
+        return Some("post is a repost");
+    };
+    if policy.only_top_level && post.in_reply_to_id.is_some() {
+        return Some("post is a reply");
+    };
+    if policy.require_rating && post.rating.is_none() {
+        return Some("post has no rating");
+    };
+    if !policy.allow_bots && is_bot(&post.author) {
+        return Some("author is a bot");
+    };
+    if let Some(ref hostname) = post.author.hostname {
+        if blocked_instances
+            .iter()
+            .chain(policy.muted_instances.iter())
+            .any(|instance| instance == hostname)
+        {
+            return Some("author's instance is muted");
+        };
+    };
+    None
+}
+
+/// Checks whether post mentioning a movie account can be reposted by it
+pub async fn check_boost_policy(
+    db_client: &impl DatabaseClient,
+    config: &Config,
+    movie: &DbActorProfile,
+    post: &Post,
+) -> Result<Option<&'static str>, DatabaseError> {
+    let policy = &config.boost_policy;
+    if let Some(reason) = get_boost_rejection_reason(policy, &config.blocked_instances, post) {
+        return Ok(Some(reason));
+    };
+    if policy.one_per_author && has_reposted_author(db_client, &movie.id, &post.author.id).await? {
+        return Ok(Some("author's post has already been reposted"));
+    };
+    Ok(None)
+}
+
+/// Prepares Undo(Announce) activities for reposts made by movie accounts.
+/// Should be called before the post is deleted.
+pub async fn prepare_undo_movie_reposts(
+    db_client: &impl DatabaseClient,
+    instance: &Instance,
+    post: &Post,
+) -> Result<Vec<OutgoingActivity>, DatabaseError> {
+    let reposts = find_media_reposts(db_client, &post.id).await?;
+    let mut activities = vec![];
+    for repost in reposts {
+        let movie_user = get_user_by_id(db_client, &repost.author_id).await?;
+        let activity =
+            prepare_undo_announce(db_client, instance, &movie_user, post, &repost.id).await?;
+        activities.push(activity);
+    }
+    Ok(activities)
+}
+
+/// Deletes reposts made by movie accounts that are no longer mentioned
+/// in the (updated) post
+pub async fn undo_unmentioned_movie_reposts(
+    db_client: &mut impl DatabaseClient,
+    instance: &Instance,
+    post: &Post,
+) -> Result<(), DatabaseError> {
+    let reposts = find_media_reposts(db_client, &post.id).await?;
+    for repost in reposts {
+        if post
+            .mentions
+            .iter()
+            .any(|profile| profile.id == repost.author_id)
+        {
+            continue;
+        };
+        let movie_user = get_user_by_id(db_client, &repost.author_id).await?;
+        // Ignore returned data because reposts don't have attached files
+        delete_post(db_client, &repost.id).await?;
+        prepare_undo_announce(db_client, instance, &movie_user, post, &repost.id)
+            .await?
+            .enqueue(db_client)
+            .await?;
+        log::info!(
+            "{} undid repost of {} after mention was removed",
+            movie_user.profile.username,
+            post.id,
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fedimovies_models::posts::types::Visibility;
+    use fedimovies_models::profiles::types::{AccountKind, DbActor};
+    use uuid::Uuid;
+
+    fn create_remote_author(hostname: &str, object_type: &str) -> DbActorProfile {
+        DbActorProfile {
+            hostname: Some(hostname.to_string()),
+            actor_json: Some(DbActor {
+                object_type: object_type.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_boost_policy_default() {
+        let policy = BoostPolicyConfig::default();
+        let post = Post {
+            author: create_remote_author("example.org", "Person"),
+            ..Default::default()
+        };
+        assert_eq!(get_boost_rejection_reason(&policy, &[], &post), None);
+
+        let reply = Post {
+            in_reply_to_id: Some(Uuid::new_v4()),
+            ..post.clone()
+        };
+        assert_eq!(
+            get_boost_rejection_reason(&policy, &[], &reply),
+            Some("post is a reply"),
+        );
+        let private_post = Post {
+            visibility: Visibility::Followers,
+            ..post.clone()
+        };
+        assert_eq!(
+            get_boost_rejection_reason(&policy, &[], &private_post),
+            Some("post is not public"),
+        );
+        let bot_post = Post {
+            author: create_remote_author("example.org", SERVICE),
+            ..post.clone()
+        };
+        assert_eq!(
+            get_boost_rejection_reason(&policy, &[], &bot_post),
+            Some("author is a bot"),
+        );
+        let movie_post = Post {
+            author: DbActorProfile {
+                account_kind: AccountKind::Movie,
+                ..Default::default()
+            },
+            ..post.clone()
+        };
+        assert_eq!(
+            get_boost_rejection_reason(&policy, &[], &movie_post),
+            Some("author is a bot"),
+        );
+        let blocked_instances = vec!["example.org".to_string()];
+        assert_eq!(
+            get_boost_rejection_reason(&policy, &blocked_instances, &post),
+            Some("author's instance is muted"),
+        );
+    }
+
+    #[test]
+    fn test_boost_policy_require_rating() {
+        let policy = BoostPolicyConfig {
+            require_rating: true,
+            muted_instances: vec!["muted.example".to_string()],
+            ..Default::default()
+        };
+        let post = Post::default();
+        assert_eq!(
+            get_boost_rejection_reason(&policy, &[], &post),
+            Some("post has no rating"),
+        );
+        let post = Post {
+            rating: Some(4.0),
+            ..Default::default()
+        };
+        assert_eq!(get_boost_rejection_reason(&policy, &[], &post), None);
+        let post = Post {
+            author: create_remote_author("muted.example", "Person"),
+            object_id: Some("https://muted.example/objects/1".to_string()),
+            rating: Some(4.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_boost_rejection_reason(&policy, &[], &post),
+            Some("author's instance is muted"),
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use fedimovies_models::database::DatabaseClient;
+use fedimovies_models::movie_catalog::queries::{
+    get_catalog_entries_by_username, upsert_catalog_entries,
+};
+use fedimovies_models::movie_catalog::types::MovieCatalogData;
+
+use super::service::{MovieError, MovieInfo};
+
+const CATALOG_BATCH_SIZE: usize = 1000;
+
+/// Line of TMDB daily ID export
+/// https://developers.themoviedb.org/3/getting-started/daily-file-exports
+#[derive(Deserialize)]
+struct ExportedMovieId {
+    #[serde(default)]
+    adult: bool,
+    id: u32,
+    original_title: String,
+    #[serde(default)]
+    popularity: f32,
+}
+
+/// Opens export file, decompressing it if its name ends with `.gz`
+pub fn open_export_file(path: &Path) -> Result<Box<dyn BufRead>, std::io::Error> {
+    let file = File::open(path)?;
+    let reader: Box<dyn BufRead> = if path.extension().map_or(false, |ext| ext == "gz") {
+        Box::new(BufReader::new(flate2::read::GzDecoder::new(file)))
+    } else {
+        Box::new(BufReader::new(file))
+    };
+    Ok(reader)
+}
+
+async fn import_lines<T, F>(
+    db_client: &impl DatabaseClient,
+    reader: impl BufRead,
+    to_catalog_data: F,
+) -> Result<usize, anyhow::Error>
+where
+    T: DeserializeOwned,
+    F: Fn(T) -> Result<MovieCatalogData, anyhow::Error>,
+{
+    let mut imported_count = 0;
+    let mut batch = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        };
+        let entry = match serde_json::from_str(&line)
+            .map_err(anyhow::Error::from)
+            .and_then(&to_catalog_data)
+        {
+            Ok(entry) => entry,
+            Err(error) => {
+                log::warn!("skipping invalid catalog entry ({error}): {line}");
+                continue;
+            }
+        };
+        batch.push(entry);
+        if batch.len() >= CATALOG_BATCH_SIZE {
+            imported_count += batch.len();
+            upsert_catalog_entries(db_client, std::mem::take(&mut batch)).await?;
+        };
+    }
+    imported_count += batch.len();
+    if !batch.is_empty() {
+        upsert_catalog_entries(db_client, batch).await?;
+    };
+    Ok(imported_count)
+}
+
+/// Loads TMDB daily ID export (JSON lines) into the movie catalog
+pub async fn import_movie_id_export(
+    db_client: &impl DatabaseClient,
+    reader: impl BufRead,
+) -> Result<usize, anyhow::Error> {
+    import_lines(db_client, reader, |exported: ExportedMovieId| {
+        let entry = MovieCatalogData {
+            tmdb_id: i32::try_from(exported.id)?,
+            original_title: exported.original_title,
+            adult: exported.adult,
+            popularity: exported.popularity,
+            movie_username: None,
+            metadata: None,
+        };
+        Ok(entry)
+    })
+    .await
+}
+
+/// Loads cached movie details (JSON lines, one TMDB API response per line)
+/// into the movie catalog
+pub async fn import_movie_metadata_dump(
+    db_client: &impl DatabaseClient,
+    reader: impl BufRead,
+) -> Result<usize, anyhow::Error> {
+    import_lines(db_client, reader, |movie_info: MovieInfo| {
+        if movie_info.release_date.is_empty() {
+            return Err(anyhow::anyhow!("release date is not known"));
+        };
+        let entry = MovieCatalogData {
+            tmdb_id: i32::try_from(movie_info.id)?,
+            original_title: movie_info.original_title.clone(),
+            adult: movie_info.adult,
+            popularity: movie_info.popularity,
+            movie_username: Some(movie_info.movie_username()),
+            metadata: Some(serde_json::to_value(&movie_info)?),
+        };
+        Ok(entry)
+    })
+    .await
+}
+
+/// Returns catalog movies matching the username (short form)
+pub async fn find_catalog_movies(
+    db_client: &impl DatabaseClient,
+    movie_username: &str,
+) -> Result<Vec<MovieInfo>, MovieError> {
+    let entries = get_catalog_entries_by_username(db_client, movie_username).await?;
+    let movies = entries
+        .into_iter()
+        .filter_map(|entry| entry.metadata)
+        .filter_map(|metadata| match serde_json::from_value(metadata) {
+            Ok(movie_info) => Some(movie_info),
+            Err(error) => {
+                log::warn!("invalid movie metadata in catalog: {error}");
+                None
+            }
+        })
+        .collect();
+    Ok(movies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fedimovies_models::database::test_utils::create_test_database;
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_import_movie_catalog() {
+        let db_client = &create_test_database().await;
+        let export = concat!(
+            r#"{"adult":false,"id":19995,"original_title":"Avatar","popularity":102.3,"video":false}"#,
+            "\n",
+            "not json\n",
+            r#"{"adult":false,"id":76600,"original_title":"Avatar: The Way of Water","popularity":1210.4,"video":false}"#,
+            "\n",
+        );
+        let imported_count = import_movie_id_export(db_client, export.as_bytes())
+            .await
+            .unwrap();
+        assert_eq!(imported_count, 2);
+        // Metadata is not known yet
+        let movies = find_catalog_movies(db_client, "Avatar_2009").await.unwrap();
+        assert!(movies.is_empty());
+
+        let dump = concat!(
+            r#"{"adult":false,"backdrop_path":null,"id":19995,"original_language":"en","original_title":"Avatar","overview":"","popularity":102.3,"poster_path":null,"release_date":"2009-12-15","title":"Avatar","video":false,"vote_average":7.6,"vote_count":29362}"#,
+            "\n",
+        );
+        let imported_count = import_movie_metadata_dump(db_client, dump.as_bytes())
+            .await
+            .unwrap();
+        assert_eq!(imported_count, 1);
+        let movies = find_catalog_movies(db_client, "Avatar_2009").await.unwrap();
+        assert_eq!(movies.len(), 1);
+        assert_eq!(movies[0].id, 19995);
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+use super::people::{MovieCredits, PersonDetails};
+use super::provider::MovieMetadataProvider;
+use super::service::{MovieError, MovieInfo, MovieSearchResponse};
+use super::tv::{TvSearchResponse, TvSeriesDetails, TvSeriesInfo};
+
+fn normalize_title(title: &str) -> String {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Reads movie metadata from a directory with TMDB API responses.
+/// Allows to test movie lookups without network access.
+pub struct FileMetadataProvider {
+    data_dir: PathBuf,
+}
+
+impl FileMetadataProvider {
+    pub fn new(data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+        }
+    }
+
+    pub fn for_test() -> Self {
+        let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tmdb/test_data");
+        Self::new(data_dir)
+    }
+
+    fn read<T: DeserializeOwned>(&self, file_name: &str) -> Result<T, MovieError> {
+        let data = std::fs::read_to_string(self.data_dir.join(file_name))
+            .map_err(|_| MovieError::NotFoundError("failed to read movie data"))?;
+        let value = serde_json::from_str(&data)
+            .map_err(|_| MovieError::NotFoundError("failed to parse movie data"))?;
+        Ok(value)
+    }
+}
+
+#[async_trait]
+impl MovieMetadataProvider for FileMetadataProvider {
+    async fn search_movies(
+        &self,
+        title: &str,
+        maybe_year: Option<u32>,
+    ) -> Result<Vec<MovieInfo>, MovieError> {
+        let response: MovieSearchResponse = self.read("search_movie.json")?;
+        let title = normalize_title(title);
+        let maybe_year = maybe_year.map(|year| year.to_string());
+        let movies = response
+            .results
+            .into_iter()
+            .filter(|movie| {
+                normalize_title(&movie.title).contains(&title)
+                    && maybe_year
+                        .as_ref()
+                        .map_or(true, |year| movie.release_date.starts_with(year))
+            })
+            .collect();
+        Ok(movies)
+    }
+
+    async fn get_movie(&self, movie_id: u32) -> Result<MovieInfo, MovieError> {
+        self.read(&format!("movie_{}.json", movie_id))
+    }
+
+    async fn get_movie_credits(&self, movie_id: u32) -> Result<MovieCredits, MovieError> {
+        self.read(&format!("movie_{}_credits.json", movie_id))
+    }
+
+    async fn search_tv_series(
+        &self,
+        name: &str,
+        year: u32,
+    ) -> Result<Vec<TvSeriesInfo>, MovieError> {
+        let response: TvSearchResponse = self.read("search_tv.json")?;
+        let name = normalize_title(name);
+        let year = year.to_string();
+        let series = response
+            .results
+            .into_iter()
+            .filter(|series_info| {
+                normalize_title(&series_info.name).contains(&name)
+                    && series_info.first_air_date.starts_with(&year)
+            })
+            .collect();
+        Ok(series)
+    }
+
+    async fn get_tv_series(&self, series_id: u32) -> Result<TvSeriesDetails, MovieError> {
+        self.read(&format!("tv_{}.json", series_id))
+    }
+
+    async fn get_person(&self, person_id: u32) -> Result<PersonDetails, MovieError> {
+        self.read(&format!("person_{}.json", person_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_search_movies() {
+        let provider = FileMetadataProvider::for_test();
+        let movies = provider
+            .search_movies("Avatar The Way Of Water", Some(2022))
+            .await
+            .unwrap();
+        assert_eq!(movies.len(), 1);
+        assert_eq!(movies[0].id, 76600);
+
+        let movies = provider
+            .search_movies("Avatar The Way Of Water", Some(2009))
+            .await
+            .unwrap();
+        assert!(movies.is_empty());
+
+        let movies = provider.search_movies("Avatar", None).await.unwrap();
+        assert_eq!(movies.len(), 2);
+
+        let movie_info = provider.get_movie(19995).await.unwrap();
+        assert_eq!(movie_info.title, "Avatar");
+    }
+
+    #[tokio::test]
+    async fn test_search_tv_series() {
+        let provider = FileMetadataProvider::for_test();
+        let series = provider
+            .search_tv_series("Breaking Bad", 2008)
+            .await
+            .unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].id, 1396);
+
+        let series_details = provider.get_tv_series(1396).await.unwrap();
+        assert_eq!(series_details.info.name, "Breaking Bad");
+        assert_eq!(series_details.seasons[2].season_number, 2);
+    }
+}
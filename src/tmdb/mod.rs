@@ -1,2 +1,17 @@
+mod boosts;
+mod catalog;
+#[cfg(test)]
+mod file_provider;
+mod people;
+mod provider;
 mod service;
+mod tv;
+
+pub use boosts::{check_boost_policy, prepare_undo_movie_reposts, undo_unmentioned_movie_reposts};
+pub use catalog::{import_movie_id_export, import_movie_metadata_dump, open_export_file};
+#[cfg(test)]
+pub use file_provider::FileMetadataProvider;
+pub use people::process_queued_movie_credits;
+pub use provider::{get_movie_metadata_provider, MovieMetadataProvider, TmdbClient};
 pub use service::*;
+pub use tv::{get_or_create_tv_series_user_by_id, TvUsername};
@@ -0,0 +1,581 @@
+use std::path::Path;
+
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use fedimovies_config::{Config, Instance};
+use fedimovies_models::background_jobs::queries::{
+    delete_job_from_queue, enqueue_job, get_job_batch,
+};
+use fedimovies_models::background_jobs::types::JobType;
+use fedimovies_models::database::{DatabaseClient, DatabaseError, DatabaseTypeError};
+use fedimovies_models::profiles::queries::update_profile;
+use fedimovies_models::profiles::types::{AccountKind, ExtraField, ProfileUpdateData};
+use fedimovies_models::users::queries::get_user_by_id;
+use fedimovies_models::users::types::User;
+use fedimovies_utils::html::escape_html;
+
+use crate::activitypub::builders::update_person::prepare_update_person;
+use crate::activitypub::identifiers::local_actor_id;
+
+use super::provider::{get_movie_metadata_provider, MovieMetadataProvider};
+use super::service::{
+    camel_case_title, create_media_user, find_user_by_short_form, find_user_by_tmdb_id,
+    tmdb_image_url, MediaProfile, MovieError,
+};
+
+const PERSON_MARKER: &str = "Person";
+const CAST_LIMIT: usize = 5;
+const WRITER_LIMIT: usize = 3;
+const CREDIT_FIELD_NAMES: [&str; 3] = ["Director", "Writer", "Cast"];
+
+/// Username of a person from movie credits
+/// (e.g. `DenisVilleneuve` or `DenisVilleneuve_Person_137427`)
+#[derive(Debug, PartialEq)]
+pub struct PersonUsername {
+    pub name: String,
+    pub tmdb_id: Option<u32>,
+}
+
+impl PersonUsername {
+    pub fn parse(username: &str) -> Result<Self, MovieError> {
+        let (name, tmdb_id) = match username.split('_').collect::<Vec<_>>().as_slice() {
+            [name] => (name.to_string(), None),
+            [name, PERSON_MARKER, tmdb_id] => {
+                let tmdb_id = tmdb_id.parse::<u32>().map_err(|_| MovieError::NotMovie)?;
+                (name.to_string(), Some(tmdb_id))
+            }
+            _ => return Err(MovieError::NotMovie),
+        };
+        // Names are camel-cased, so the short form can't be
+        // confused with usernames of most regular accounts
+        let starts_with_capital = name
+            .chars()
+            .next()
+            .map_or(false, |first_char| first_char.is_ascii_uppercase());
+        if !starts_with_capital || !name.chars().all(|c| c.is_alphanumeric()) {
+            return Err(MovieError::NotMovie);
+        };
+        Ok(Self { name, tmdb_id })
+    }
+
+    /// Returns the part of username that precedes TMDB ID
+    fn prefix(&self) -> String {
+        format!("{}_{}", self.name, PERSON_MARKER)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PersonInfo {
+    #[serde(default)]
+    pub adult: bool,
+    pub id: u32,
+    pub name: String,
+    pub known_for_department: Option<String>,
+    pub profile_path: Option<String>,
+}
+
+impl PersonInfo {
+    pub fn person_username(&self) -> String {
+        camel_case_title(&self.name)
+    }
+
+    /// Returns username with TMDB ID suffix, which is unique
+    pub fn person_username_with_id(&self) -> String {
+        format!("{}_{}_{}", self.person_username(), PERSON_MARKER, self.id)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PersonDetails {
+    #[serde(flatten)]
+    pub info: PersonInfo,
+    #[serde(default)]
+    pub biography: String,
+}
+
+impl From<&PersonDetails> for MediaProfile {
+    fn from(person_details: &PersonDetails) -> Self {
+        Self {
+            username: person_details.info.person_username_with_id(),
+            account_kind: AccountKind::MoviePerson,
+            tmdb_id: person_details.info.id,
+            display_name: person_details.info.name.clone(),
+            overview: person_details.biography.clone(),
+            tmdb_url: format!(
+                "https://www.themoviedb.org/person/{}",
+                person_details.info.id,
+            ),
+            poster_url: person_details
+                .info
+                .profile_path
+                .as_ref()
+                .map(|path| tmdb_image_url(path, "w185")),
+            background_url: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MovieCredits {
+    pub id: u32,
+    pub cast: Vec<CastCredit>,
+    pub crew: Vec<CrewCredit>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CastCredit {
+    #[serde(default)]
+    pub adult: bool,
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub order: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CrewCredit {
+    #[serde(default)]
+    pub adult: bool,
+    pub id: u32,
+    pub name: String,
+    pub department: String,
+    pub job: String,
+}
+
+/// Returns local account of a person with the given TMDB ID,
+/// creating it if necessary
+async fn get_or_create_person_user_by_id(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: &dyn MovieMetadataProvider,
+    media_dir: &Path,
+    person_id: u32,
+) -> Result<User, MovieError> {
+    if let Some(user) =
+        find_user_by_tmdb_id(db_client, &AccountKind::MoviePerson, person_id).await?
+    {
+        return Ok(user);
+    };
+    let person_details = movie_provider.get_person(person_id).await?;
+    let user = create_media_user(
+        instance,
+        db_client,
+        MediaProfile::from(&person_details),
+        media_dir,
+    )
+    .await?;
+    Ok(user)
+}
+
+/// Returns local account of a director, actor or writer.
+/// Account is created only if username contains TMDB ID,
+/// the short form resolves to existing accounts.
+pub async fn get_or_create_person_user(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
+    media_dir: &Path,
+    person_username: &PersonUsername,
+) -> Result<User, MovieError> {
+    let person_id = match person_username.tmdb_id {
+        Some(person_id) => person_id,
+        None => {
+            // Short form matches any capitalized word,
+            // so it is not looked up in TMDB
+            return find_user_by_short_form(db_client, &person_username.prefix())
+                .await?
+                .ok_or(MovieError::NotFoundError("person not found"));
+        }
+    };
+    let movie_provider =
+        movie_provider.ok_or(MovieError::NotFoundError("person lookup is not available"))?;
+    get_or_create_person_user_by_id(instance, db_client, movie_provider, media_dir, person_id).await
+}
+
+fn credit_field(instance: &Instance, name: &str, users: &[User]) -> ExtraField {
+    let instance_url = instance.url();
+    let links: Vec<String> = users
+        .iter()
+        .map(|user| {
+            format!(
+                r#"<span class="h-card"><a class="u-url mention" href="{}">{}</a></span>"#,
+                local_actor_id(&instance_url, &user.profile.username),
+                escape_html(
+                    user.profile
+                        .display_name
+                        .as_deref()
+                        .unwrap_or(&user.profile.username)
+                ),
+            )
+        })
+        .collect();
+    let mentions: Vec<String> = users
+        .iter()
+        .map(|user| format!("@{}", user.profile.username))
+        .collect();
+    ExtraField {
+        name: name.to_string(),
+        value: links.join(", "),
+        value_source: Some(mentions.join(", ")),
+    }
+}
+
+/// Creates accounts for directors, writers and leading actors of the movie
+/// and returns profile fields that link to them
+pub async fn get_movie_credit_fields(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: &dyn MovieMetadataProvider,
+    media_dir: &Path,
+    movie_id: u32,
+) -> Result<Vec<ExtraField>, MovieError> {
+    let credits = movie_provider.get_movie_credits(movie_id).await?;
+    let mut directors = vec![];
+    let mut writers = vec![];
+    for credit in credits.crew.iter().filter(|credit| !credit.adult) {
+        if credit.job == "Director" && !directors.contains(&credit.id) {
+            directors.push(credit.id);
+        } else if credit.department == "Writing"
+            && !writers.contains(&credit.id)
+            && writers.len() < WRITER_LIMIT
+        {
+            writers.push(credit.id);
+        };
+    }
+    let mut cast: Vec<&CastCredit> = credits.cast.iter().filter(|credit| !credit.adult).collect();
+    cast.sort_by_key(|credit| credit.order);
+    let actors: Vec<u32> = cast
+        .into_iter()
+        .take(CAST_LIMIT)
+        .map(|credit| credit.id)
+        .collect();
+
+    let mut fields = vec![];
+    for (field_name, person_ids) in CREDIT_FIELD_NAMES
+        .into_iter()
+        .zip([directors, writers, actors])
+    {
+        let mut users = vec![];
+        for person_id in person_ids {
+            match get_or_create_person_user_by_id(
+                instance,
+                db_client,
+                movie_provider,
+                media_dir,
+                person_id,
+            )
+            .await
+            {
+                Ok(user) => users.push(user),
+                Err(MovieError::DatabaseError(db_error)) => return Err(db_error.into()),
+                Err(error @ MovieError::ApiError(_)) => return Err(error),
+                Err(other_error) => {
+                    // Names that can't be turned into usernames are skipped too
+                    log::warn!("failed to create account for person {person_id}: {other_error}");
+                }
+            };
+        }
+        if !users.is_empty() {
+            fields.push(credit_field(instance, field_name, &users));
+        };
+    }
+    Ok(fields)
+}
+
+/// Adds links to directors, writers and actors to the profile of movie account
+pub async fn add_movie_credits(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: &dyn MovieMetadataProvider,
+    media_dir: &Path,
+    user: &User,
+) -> Result<User, MovieError> {
+    if user.profile.account_kind != AccountKind::Movie {
+        return Err(MovieError::NotMovie);
+    };
+    let movie_id = user
+        .profile
+        .tmdb_id
+        .and_then(|tmdb_id| u32::try_from(tmdb_id).ok())
+        .ok_or(MovieError::NotMovie)?;
+    let credit_fields =
+        get_movie_credit_fields(instance, db_client, movie_provider, media_dir, movie_id).await?;
+    let mut user = user.clone();
+    if !credit_fields.is_empty() {
+        let mut profile_data = ProfileUpdateData::from(&user.profile);
+        // Credits could have been added before
+        profile_data
+            .extra_fields
+            .retain(|field| !CREDIT_FIELD_NAMES.contains(&field.name.as_str()));
+        profile_data.extra_fields.extend(credit_fields);
+        user.profile = update_profile(db_client, &user.id, profile_data).await?;
+    };
+    Ok(user)
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct MovieCreditsJobData {
+    user_id: Uuid,
+    #[serde(default)]
+    failure_count: u32,
+}
+
+impl MovieCreditsJobData {
+    pub fn new(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            failure_count: 0,
+        }
+    }
+
+    pub async fn into_job(self, db_client: &impl DatabaseClient) -> Result<(), DatabaseError> {
+        self.into_delayed_job(db_client, 0).await
+    }
+
+    async fn into_delayed_job(
+        self,
+        db_client: &impl DatabaseClient,
+        delay: u32,
+    ) -> Result<(), DatabaseError> {
+        let job_data = serde_json::to_value(self).expect("job data should be serializable");
+        let scheduled_for = Utc::now() + Duration::seconds(delay.into());
+        enqueue_job(db_client, &JobType::MovieCredits, &job_data, &scheduled_for).await
+    }
+}
+
+const CREDITS_QUEUE_BATCH_SIZE: u32 = 5;
+const CREDITS_QUEUE_RETRIES_MAX: u32 = 3;
+const CREDITS_QUEUE_BACKOFF: u32 = 60 * 30; // 30 minutes
+const CREDITS_JOB_TIMEOUT: u32 = 600; // 10 minutes
+
+/// Creates accounts for people from the credits of new movie accounts.
+/// This involves many TMDB API calls, so it is not done when
+/// movie account is created.
+pub async fn process_queued_movie_credits(
+    config: &Config,
+    db_client: &mut impl DatabaseClient,
+) -> Result<(), DatabaseError> {
+    let movie_provider = match get_movie_metadata_provider(config) {
+        Some(movie_provider) => movie_provider,
+        None => return Ok(()), // not configured
+    };
+    let instance = config.instance();
+    let batch = get_job_batch(
+        db_client,
+        &JobType::MovieCredits,
+        CREDITS_QUEUE_BATCH_SIZE,
+        CREDITS_JOB_TIMEOUT,
+    )
+    .await?;
+    for job in batch {
+        let mut job_data: MovieCreditsJobData =
+            serde_json::from_value(job.job_data).map_err(|_| DatabaseTypeError)?;
+        let user = match get_user_by_id(db_client, &job_data.user_id).await {
+            Ok(user) => Some(user),
+            // Account has been deleted
+            Err(DatabaseError::NotFound(_)) => None,
+            Err(other_error) => return Err(other_error),
+        };
+        if let Some(user) = user {
+            match add_movie_credits(
+                &instance,
+                db_client,
+                movie_provider.as_ref(),
+                &config.media_dir(),
+                &user,
+            )
+            .await
+            {
+                Ok(user) => {
+                    prepare_update_person(db_client, &instance, &user, None)
+                        .await?
+                        .enqueue(db_client)
+                        .await?;
+                    log::info!("added credits to {}", user.profile.username);
+                }
+                Err(MovieError::DatabaseError(db_error)) => return Err(db_error),
+                Err(error @ MovieError::ApiError(_)) => {
+                    // TMDB API is temporarily unavailable
+                    job_data.failure_count += 1;
+                    log::warn!(
+                        "failed to get credits of {} (attempt #{}): {}",
+                        user.profile.username,
+                        job_data.failure_count,
+                        error,
+                    );
+                    if job_data.failure_count <= CREDITS_QUEUE_RETRIES_MAX {
+                        // Re-queue
+                        job_data
+                            .into_delayed_job(db_client, CREDITS_QUEUE_BACKOFF)
+                            .await?;
+                    };
+                }
+                Err(other_error) => {
+                    log::warn!(
+                        "failed to get credits of {}: {}",
+                        user.profile.username,
+                        other_error,
+                    );
+                }
+            };
+        };
+        delete_job_from_queue(db_client, &job.id).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmdb::FileMetadataProvider;
+    use fedimovies_models::database::test_utils::create_test_database;
+    use fedimovies_models::profiles::types::DbActorProfile;
+    use fedimovies_models::users::{queries::create_user, types::UserCreateData};
+    use serial_test::serial;
+
+    #[test]
+    fn test_parse_person_username() {
+        let person_username = PersonUsername::parse("DenisVilleneuve").unwrap();
+        assert_eq!(person_username.name, "DenisVilleneuve");
+        assert_eq!(person_username.tmdb_id, None);
+        assert_eq!(person_username.prefix(), "DenisVilleneuve_Person");
+
+        let person_username = PersonUsername::parse("DenisVilleneuve_Person_137427").unwrap();
+        assert_eq!(person_username.name, "DenisVilleneuve");
+        assert_eq!(person_username.tmdb_id, Some(137427));
+
+        assert!(PersonUsername::parse("denis").is_err());
+        assert!(PersonUsername::parse("Dune_2021").is_err());
+        assert!(PersonUsername::parse("DenisVilleneuve_Person").is_err());
+        assert!(PersonUsername::parse("BreakingBad_TV_2008").is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_or_create_person_user() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+
+        // Short form is not looked up in TMDB
+        let person_username = PersonUsername::parse("DenisVilleneuve").unwrap();
+        let result = get_or_create_person_user(
+            &instance,
+            db_client,
+            Some(&movie_provider),
+            &media_dir,
+            &person_username,
+        )
+        .await;
+        assert!(matches!(result, Err(MovieError::NotFoundError(_))));
+
+        let user = get_or_create_person_user(
+            &instance,
+            db_client,
+            Some(&movie_provider),
+            &media_dir,
+            &PersonUsername::parse("DenisVilleneuve_Person_137427").unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(user.profile.username, "DenisVilleneuve_Person_137427");
+        assert_eq!(user.profile.account_kind, AccountKind::MoviePerson);
+        assert_eq!(user.profile.tmdb_id, Some(137427));
+        assert_eq!(
+            user.profile.display_name.as_deref(),
+            Some("Denis Villeneuve"),
+        );
+
+        // Short form is an alias
+        let user_found =
+            get_or_create_person_user(&instance, db_client, None, &media_dir, &person_username)
+                .await
+                .unwrap();
+        assert_eq!(user_found.id, user.id);
+    }
+
+    #[test]
+    fn test_credit_field_escapes_name() {
+        let instance = Instance::for_test("https://example.com");
+        let user = User {
+            profile: DbActorProfile {
+                username: "Tom_Person_1".to_string(),
+                display_name: Some("Tom & <b>Jerry</b>".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let field = credit_field(&instance, "Cast", &[user]);
+        assert_eq!(
+            field.value,
+            r#"<span class="h-card"><a class="u-url mention" href="https://example.com/users/Tom_Person_1">Tom&#32;&amp;&#32;&lt;b&gt;Jerry&lt;&#47;b&gt;</a></span>"#,
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_movie_credit_fields() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+
+        let fields =
+            get_movie_credit_fields(&instance, db_client, &movie_provider, &media_dir, 438631)
+                .await
+                .unwrap();
+        // Only people with available details get accounts
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0].name, "Director");
+        assert_eq!(
+            fields[0].value,
+            r#"<span class="h-card"><a class="u-url mention" href="https://example.com/users/DenisVilleneuve_Person_137427">Denis&#32;Villeneuve</a></span>"#,
+        );
+        assert_eq!(
+            fields[0].value_source.as_deref(),
+            Some("@DenisVilleneuve_Person_137427"),
+        );
+        assert_eq!(fields[1].name, "Writer");
+        assert_eq!(fields[2].name, "Cast");
+        assert_eq!(
+            fields[2].value_source.as_deref(),
+            Some("@Zendaya_Person_505710"),
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_add_movie_credits_twice() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+        let user_data = UserCreateData {
+            username: "Dune_2021_438631".to_string(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: Some(438631),
+            ..Default::default()
+        };
+        let user = create_user(db_client, user_data).await.unwrap();
+
+        let user = add_movie_credits(&instance, db_client, &movie_provider, &media_dir, &user)
+            .await
+            .unwrap();
+        assert_eq!(user.profile.extra_fields.clone().into_inner().len(), 3);
+        // Job can be retried after the profile has been updated
+        let user = add_movie_credits(&instance, db_client, &movie_provider, &media_dir, &user)
+            .await
+            .unwrap();
+        let field_names: Vec<String> = user
+            .profile
+            .extra_fields
+            .into_inner()
+            .into_iter()
+            .map(|field| field.name)
+            .collect();
+        assert_eq!(field_names, vec!["Director", "Writer", "Cast"]);
+    }
+}
@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use reqwest::{header, Client, StatusCode};
+use serde::de::DeserializeOwned;
+
+use fedimovies_config::{Config, Instance};
+
+use crate::activitypub::http_client::{build_federation_client, get_network_type};
+
+use super::people::{MovieCredits, PersonDetails};
+use super::service::{MovieError, MovieInfo, MovieSearchResponse};
+use super::tv::{TvSearchResponse, TvSeriesDetails, TvSeriesInfo};
+
+const TMDB_API_TIMEOUT: u64 = 30;
+const TMDB_USER_AGENT: &str = "FediMovies.rocks/1.0";
+
+/// Source of movie metadata
+#[async_trait]
+pub trait MovieMetadataProvider: Send + Sync {
+    /// Returns movies matching the given title and release year (if known)
+    async fn search_movies(
+        &self,
+        title: &str,
+        maybe_year: Option<u32>,
+    ) -> Result<Vec<MovieInfo>, MovieError>;
+
+    /// Returns movie with the given TMDB ID
+    async fn get_movie(&self, movie_id: u32) -> Result<MovieInfo, MovieError>;
+
+    /// Returns cast and crew of the movie
+    async fn get_movie_credits(&self, movie_id: u32) -> Result<MovieCredits, MovieError>;
+
+    /// Returns TV series matching the given name and year of the first air date
+    async fn search_tv_series(
+        &self,
+        name: &str,
+        year: u32,
+    ) -> Result<Vec<TvSeriesInfo>, MovieError>;
+
+    /// Returns TV series with the given TMDB ID, including its seasons
+    async fn get_tv_series(&self, series_id: u32) -> Result<TvSeriesDetails, MovieError>;
+
+    /// Returns person with the given TMDB ID
+    async fn get_person(&self, person_id: u32) -> Result<PersonDetails, MovieError>;
+}
+
+/// TMDB API client
+pub struct TmdbClient {
+    client: Client,
+    api_url: String,
+    api_key: String,
+}
+
+impl TmdbClient {
+    pub fn new(instance: &Instance, api_url: &str, api_key: &str) -> Result<Self, MovieError> {
+        let network =
+            get_network_type(api_url).map_err(|_| MovieError::ApiError("invalid TMDB API URL"))?;
+        // Proxy settings of the instance are applied to TMDB API requests
+        let client = build_federation_client(instance, network, TMDB_API_TIMEOUT)
+            .map_err(|_| MovieError::ApiError("failed to build HTTP client"))?;
+        let tmdb_client = Self {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        };
+        Ok(tmdb_client)
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, MovieError> {
+        let url = format!("{}{}", self.api_url, path);
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("api_key", self.api_key.as_str()), ("language", "en-US")])
+            .query(query)
+            .header(header::USER_AGENT, TMDB_USER_AGENT)
+            .send()
+            .await
+            .map_err(|err| {
+                // Error message is not logged because request URL contains API key
+                log::warn!(
+                    "error sending request to TMDB API ({url}): {:?}",
+                    std::error::Error::source(&err),
+                );
+                MovieError::ApiError("error sending request")
+            })?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(MovieError::NotFoundError("not found"));
+        };
+        // 401 Unauthorized, 429 Too Many Requests, 5xx
+        let response = response.error_for_status().map_err(|err| {
+            log::warn!("error response from TMDB API ({url}): {:?}", err.status());
+            MovieError::ApiError("error response")
+        })?;
+        let data = response.json::<T>().await.map_err(|err| {
+            log::error!("error parsing result from TMDB API ({url}): {err:?}");
+            MovieError::ApiError("error parsing result from TMDB API")
+        })?;
+        Ok(data)
+    }
+}
+
+#[async_trait]
+impl MovieMetadataProvider for TmdbClient {
+    async fn search_movies(
+        &self,
+        title: &str,
+        maybe_year: Option<u32>,
+    ) -> Result<Vec<MovieInfo>, MovieError> {
+        let maybe_year = maybe_year.map(|year| year.to_string());
+        let mut query = vec![("query", title), ("include_adult", "false")];
+        if let Some(ref year) = maybe_year {
+            query.push(("year", year.as_str()));
+        };
+        let response: MovieSearchResponse = self.get("/search/movie", &query).await?;
+        Ok(response.results)
+    }
+
+    async fn get_movie(&self, movie_id: u32) -> Result<MovieInfo, MovieError> {
+        let path = format!("/movie/{}", movie_id);
+        self.get(&path, &[]).await
+    }
+
+    async fn get_movie_credits(&self, movie_id: u32) -> Result<MovieCredits, MovieError> {
+        let path = format!("/movie/{}/credits", movie_id);
+        self.get(&path, &[]).await
+    }
+
+    async fn search_tv_series(
+        &self,
+        name: &str,
+        year: u32,
+    ) -> Result<Vec<TvSeriesInfo>, MovieError> {
+        let year = year.to_string();
+        let response: TvSearchResponse = self
+            .get(
+                "/search/tv",
+                &[
+                    ("query", name),
+                    ("first_air_date_year", year.as_str()),
+                    ("include_adult", "false"),
+                ],
+            )
+            .await?;
+        Ok(response.results)
+    }
+
+    async fn get_tv_series(&self, series_id: u32) -> Result<TvSeriesDetails, MovieError> {
+        let path = format!("/tv/{}", series_id);
+        self.get(&path, &[]).await
+    }
+
+    async fn get_person(&self, person_id: u32) -> Result<PersonDetails, MovieError> {
+        let path = format!("/person/{}", person_id);
+        self.get(&path, &[]).await
+    }
+}
+
+/// Returns TMDB client if TMDB API key is configured
+pub fn get_movie_metadata_provider(config: &Config) -> Option<Box<dyn MovieMetadataProvider>> {
+    let api_key = config.tmdb_api_key.as_ref()?;
+    match TmdbClient::new(&config.instance(), &config.tmdb_api_url, api_key) {
+        Ok(client) => Some(Box::new(client)),
+        Err(error) => {
+            log::error!("failed to create TMDB client: {}", error);
+            None
+        }
+    }
+}
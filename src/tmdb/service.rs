@@ -1,43 +1,250 @@
 use crate::activitypub::actors::helpers::ACTOR_IMAGE_MAX_SIZE;
 use crate::activitypub::fetcher::fetchers::fetch_file;
-use crate::mastodon_api::oauth::utils::generate_access_token;
 use crate::validators::users::validate_local_username;
 use fedimovies_config::Instance;
-use fedimovies_models::database::DatabaseClient;
+use fedimovies_models::cleanup::{find_orphaned_files, DeletionQueue};
+use fedimovies_models::database::{DatabaseClient, DatabaseError};
 use fedimovies_models::profiles::queries::update_profile;
-use fedimovies_models::profiles::types::{ExtraField, ProfileImage, ProfileUpdateData};
-use fedimovies_models::users::queries::create_user;
+use fedimovies_models::profiles::types::{
+    AccountKind, DbActorProfile, ExtraField, ProfileImage, ProfileUpdateData,
+};
+use fedimovies_models::users::queries::{
+    create_user, get_user_by_name, get_user_by_tmdb_id, get_users_by_name_prefix,
+};
 use fedimovies_models::users::types::{Role, User, UserCreateData};
 use fedimovies_utils::crypto_rsa::{generate_rsa_key, serialize_private_key};
 use fedimovies_utils::markdown::markdown_basic_to_html;
-use fedimovies_utils::passwords::hash_password;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use std::time::Duration;
+
+use super::catalog::find_catalog_movies;
+use super::people::{get_or_create_person_user, MovieCreditsJobData, PersonUsername};
+use super::provider::MovieMetadataProvider;
+use super::tv::{get_or_create_tv_user, get_tv_season_profile, TvUsername};
+
+const TMDB_PROFILE_FIELD: &str = "TMDB Profile";
+
+/// Removes non-alphanumeric characters from the title
+/// and replaces spaces with camel case letters
+pub(super) fn camel_case_title(title: &str) -> String {
+    let title = title.replace(|c: char| !c.is_alphanumeric() && !c.is_whitespace(), "");
+    title
+        .split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first_char) => first_char.to_uppercase().chain(chars).collect(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+/// Reverses the camel case to get a search query
+pub(super) fn expand_camel_case(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| {
+            if c.is_uppercase() {
+                format!(" {}", c)
+            } else {
+                c.to_string()
+            }
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+pub(super) fn tmdb_image_url(path: &str, size: &str) -> String {
+    format!("https://image.tmdb.org/t/p/{}{}", size, path)
+}
+
+/// Movie username with optional TMDB ID suffix
+/// (e.g. `Title_2022` or `Title_2022_12345`)
+#[derive(Debug, PartialEq)]
+pub struct MovieUsername {
+    pub title: String,
+    pub year: u32,
+    pub tmdb_id: Option<u32>,
+}
+
+impl MovieUsername {
+    pub fn new(title: &str, year: u32) -> Self {
+        Self {
+            title: camel_case_title(title),
+            year,
+            tmdb_id: None,
+        }
+    }
+
+    pub fn parse(username: &str) -> Result<Self, MovieError> {
+        let (rest, last) = username.rsplit_once('_').ok_or(MovieError::NotMovie)?;
+        let last = last.parse::<u32>().map_err(|_| MovieError::NotMovie)?;
+        // Short form can't be confused with the long one
+        // because movie titles don't contain underscores
+        let movie_username = match rest.rsplit_once('_') {
+            Some((title, year)) => {
+                let year = year.parse::<u32>().map_err(|_| MovieError::NotMovie)?;
+                Self {
+                    title: title.to_string(),
+                    year,
+                    tmdb_id: Some(last),
+                }
+            }
+            None => Self {
+                title: rest.to_string(),
+                year: last,
+                tmdb_id: None,
+            },
+        };
+        if movie_username.title.is_empty() || movie_username.title.contains('_') {
+            return Err(MovieError::NotMovie);
+        };
+        Ok(movie_username)
+    }
+
+    /// Returns username without TMDB ID
+    pub fn short_form(&self) -> String {
+        format!("{}_{}", self.title, self.year)
+    }
+}
+
+/// Finds account by the short form of its username (without TMDB ID).
+/// Returns error if the short form matches more than one account.
+pub(super) async fn find_user_by_short_form(
+    db_client: &impl DatabaseClient,
+    short_form: &str,
+) -> Result<Option<User>, MovieError> {
+    let mut users = get_users_by_name_prefix(db_client, short_form).await?;
+    match users.len() {
+        0 => Ok(None),
+        1 => Ok(users.pop()),
+        _ => {
+            let candidates = users
+                .into_iter()
+                .map(|user| user.profile.username)
+                .collect();
+            Err(MovieError::Ambiguous(candidates))
+        }
+    }
+}
+
+pub(super) async fn find_user_by_tmdb_id(
+    db_client: &impl DatabaseClient,
+    account_kind: &AccountKind,
+    tmdb_id: u32,
+) -> Result<Option<User>, MovieError> {
+    let tmdb_id = i32::try_from(tmdb_id).map_err(|_| MovieError::NotMovie)?;
+    match get_user_by_tmdb_id(db_client, account_kind, tmdb_id).await {
+        Ok(user) => Ok(Some(user)),
+        Err(DatabaseError::NotFound(_)) => Ok(None),
+        Err(other_error) => Err(other_error.into()),
+    }
+}
+
+/// Returns local movie or TV show account,
+/// creating it if movie or TV show exists in TMDB
+pub async fn get_or_create_media_user(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
+    media_dir: &Path,
+    username: &str,
+) -> Result<User, MovieError> {
+    match get_user_by_name(db_client, username).await {
+        Ok(user) => return Ok(user),
+        Err(DatabaseError::NotFound(_)) => (),
+        Err(other_error) => return Err(other_error.into()),
+    };
+    if let Ok(tv_username) = TvUsername::parse(username) {
+        return get_or_create_tv_user(instance, db_client, movie_provider, media_dir, &tv_username)
+            .await;
+    };
+    if let Ok(person_username) = PersonUsername::parse(username) {
+        return get_or_create_person_user(
+            instance,
+            db_client,
+            movie_provider,
+            media_dir,
+            &person_username,
+        )
+        .await;
+    };
+    lookup_and_create_movie_user(instance, db_client, movie_provider, media_dir, username).await
+}
 
 pub async fn lookup_and_create_movie_user(
     instance: &Instance,
     db_client: &mut impl DatabaseClient,
-    api_key: &str,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
     media_dir: &Path,
     username: &str,
-    default_movie_user_password: Option<String>,
 ) -> Result<User, MovieError> {
-    let (movie_title, year) = username.rsplit_once('_').ok_or(MovieError::NotMovie)?;
-    let year = year.parse::<u32>().map_err(|_| MovieError::NotMovie)?;
+    let movie_username = MovieUsername::parse(username)?;
+    // Short form is resolved against the catalog and TMDB API, not only
+    // against local accounts, because other movies may have the same
+    // title and release year
+    let movie_info = match get_movie_info(db_client, movie_provider, &movie_username).await {
+        Ok(movie_info) => movie_info,
+        Err(MovieError::NotFoundError(message)) if movie_username.tmdb_id.is_none() => {
+            // Movie lookup is not available, fall back to local accounts
+            return find_user_by_short_form(db_client, &movie_username.short_form())
+                .await?
+                .ok_or(MovieError::NotFoundError(message));
+        }
+        Err(other_error) => return Err(other_error),
+    };
+    // Movie account may exist under a different username
+    if let Some(user) = find_user_by_tmdb_id(db_client, &AccountKind::Movie, movie_info.id).await? {
+        return Ok(user);
+    };
+    create_movie_user_with_credits(instance, db_client, movie_provider, media_dir, &movie_info)
+        .await
+}
 
-    let movie_info = get_movie_info(api_key, movie_title, year).await?;
-    let user = create_movie_user(
+/// Returns local movie account with the given TMDB ID,
+/// creating it if necessary
+pub async fn get_or_create_movie_user_by_id(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: &dyn MovieMetadataProvider,
+    media_dir: &Path,
+    tmdb_id: u32,
+) -> Result<User, MovieError> {
+    if let Some(user) = find_user_by_tmdb_id(db_client, &AccountKind::Movie, tmdb_id).await? {
+        return Ok(user);
+    };
+    let movie_info = movie_provider.get_movie(tmdb_id).await?;
+    if movie_info.adult {
+        return Err(MovieError::NotFoundError("adult movies are not supported"));
+    };
+    create_movie_user_with_credits(
         instance,
         db_client,
-        &movie_info,
-        &default_movie_user_password
-            .clone()
-            .unwrap_or_else(generate_access_token),
+        Some(movie_provider),
         media_dir,
+        &movie_info,
     )
-    .await?;
+    .await
+}
 
+async fn create_movie_user_with_credits(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
+    media_dir: &Path,
+    movie_info: &MovieInfo,
+) -> Result<User, MovieError> {
+    let user = create_movie_user(instance, db_client, movie_info, media_dir).await?;
+    // Links to directors, writers and actors are added in background.
+    // Credits can't be fetched if TMDB API key is not set.
+    if movie_provider.is_some() {
+        MovieCreditsJobData::new(user.id)
+            .into_job(db_client)
+            .await?;
+    };
     Ok(user)
 }
 
@@ -46,66 +253,101 @@ pub enum MovieError {
     #[error("error calling TMDB API: {0}")]
     NotFoundError(&'static str),
 
-    #[error("not a movie")]
+    /// Transport, authentication or rate limit error
+    #[error("TMDB API is not available: {0}")]
+    ApiError(&'static str),
+
+    #[error("not a movie, TV show or person")]
     NotMovie,
 
+    #[error("ambiguous title, candidates: {}", .0.join(", "))]
+    Ambiguous(Vec<String>),
+
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+
     #[error("error creating movie user: {0}")]
     UserCreationError(#[from] anyhow::Error),
 }
 
+fn select_movie_candidates(
+    movies: Vec<MovieInfo>,
+    movie_username: &MovieUsername,
+) -> Vec<MovieInfo> {
+    let short_form = movie_username.short_form();
+    let mut candidates: Vec<MovieInfo> = movies
+        .into_iter()
+        .filter(|movie_info| movie_info.movie_username() == short_form && !movie_info.adult)
+        .collect();
+    if let Some(tmdb_id) = movie_username.tmdb_id {
+        candidates.retain(|movie_info| movie_info.id == tmdb_id);
+    };
+    candidates
+}
+
+async fn search_movie_candidates(
+    movie_provider: Option<&dyn MovieMetadataProvider>,
+    movie_username: &MovieUsername,
+) -> Result<Vec<MovieInfo>, MovieError> {
+    let movie_provider =
+        movie_provider.ok_or(MovieError::NotFoundError("movie lookup is not available"))?;
+    let movie_title = expand_camel_case(&movie_username.title);
+    let movies = movie_provider
+        .search_movies(&movie_title, Some(movie_username.year))
+        .await?;
+    Ok(select_movie_candidates(movies, movie_username))
+}
+
+/// Finds movie matching the username.
+/// Local movie catalog is searched first, TMDB API is used as a fallback.
+/// Single catalog match without TMDB ID is confirmed with TMDB API
+/// because the catalog may be incomplete.
 pub async fn get_movie_info(
-    api_key: &str,
-    movie_username: &str,
-    year: u32,
+    db_client: &impl DatabaseClient,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
+    movie_username: &MovieUsername,
 ) -> Result<MovieInfo, MovieError> {
-    // Expand movie title to reverse the camel case on spaces
-    let movie_title = movie_username
-        .chars()
-        .map(|c| {
-            if c.is_uppercase() {
-                format!(" {}", c)
-            } else {
-                c.to_string()
+    let movie_title = expand_camel_case(&movie_username.title);
+    let year = movie_username.year;
+
+    let catalog_movies = find_catalog_movies(db_client, &movie_username.short_form()).await?;
+    let mut candidates = select_movie_candidates(catalog_movies, movie_username);
+    let is_exact_match = candidates.len() > 1 || movie_username.tmdb_id.is_some();
+    if candidates.is_empty() || !is_exact_match {
+        match search_movie_candidates(movie_provider, movie_username).await {
+            Ok(movies) => {
+                for movie_info in movies {
+                    if !candidates
+                        .iter()
+                        .any(|candidate| candidate.id == movie_info.id)
+                    {
+                        candidates.push(movie_info);
+                    };
+                }
             }
-        })
-        .collect::<String>()
-        .trim()
-        .to_string();
-
-    let client = reqwest::Client::new();
-    let url = format!(
-        "https://api.themoviedb.org/3/search/movie?api_key={api_key}&query={movie_title}&year={year}&include_adult=false&language=en-US",
-    );
-    let response = client
-        .get(&url)
-        .header("User-Agent", "FediMovies.rocks/1.0")
-        .timeout(Duration::from_secs(30))
-        .send()
-        .await
-        .map_err(|_| MovieError::NotFoundError("sending request"))?;
-    let response = response
-        .json::<MovieSearchResponse>()
-        .await
-        .map_err(|err| {
-            log::error!("error parsing result from TMDB API ({url}): {err:?}");
-            MovieError::NotFoundError("error parsing result from TMDB API")
-        })?;
-    let movie_info = response.results.first().ok_or_else(|| {
-        log::error!("movie not found in TMDB API ({url})");
-        MovieError::NotFoundError("movie not found in TMDB API")
-    })?;
-
-    if movie_info.movie_username().starts_with(movie_username)
-        && movie_info.release_date.starts_with(&year.to_string())
-        && !movie_info.adult
-    {
-        Ok(movie_info.clone())
-    } else {
-        let movie_username_gen = movie_info.movie_username();
-        log::error!("does not movie match any movie found in TMDB API ({url}) - {movie_username_gen} != {movie_username}");
-        Err(MovieError::NotFoundError(
-            "does not movie match any movie found in TMDB API",
-        ))
+            // Catalog match is used if TMDB API is not available
+            Err(MovieError::NotFoundError(_)) if !candidates.is_empty() => (),
+            Err(error) if !candidates.is_empty() => {
+                log::warn!("failed to confirm catalog match ({movie_title}, {year}): {error}");
+            }
+            Err(error) => return Err(error),
+        };
+    };
+    match candidates.len() {
+        0 => {
+            log::error!("movie not found in TMDB API ({movie_title}, {year})");
+            Err(MovieError::NotFoundError(
+                "does not movie match any movie found in TMDB API",
+            ))
+        }
+        1 => Ok(candidates.remove(0)),
+        _ => {
+            let candidates = candidates
+                .iter()
+                .map(|movie_info| movie_info.movie_username_with_id())
+                .collect();
+            Err(MovieError::Ambiguous(candidates))
+        }
     }
 }
 
@@ -136,35 +378,28 @@ pub struct MovieInfo {
 
 impl MovieInfo {
     pub fn movie_username(&self) -> String {
-        // Sanitize the movie title by removing all non-alphanumeric characters and replacing
-        // space with camel case letter.
-        let title = self
-            .title
-            .replace(|c: char| !c.is_alphanumeric() && !c.is_whitespace(), "");
-        let title = title
-            .split_whitespace()
-            .map(|word| {
-                let mut chars = word.chars();
-                match chars.next() {
-                    None => String::new(),
-                    Some(first_char) => first_char.to_uppercase().chain(chars).collect(),
-                }
-            })
-            .collect::<Vec<String>>()
-            .join("");
-        format!("{}_{}", title, self.release_date.split('-').next().unwrap())
+        format!(
+            "{}_{}",
+            camel_case_title(&self.title),
+            self.release_date.split('-').next().unwrap(),
+        )
+    }
+
+    /// Returns username with TMDB ID suffix, which is unique
+    pub fn movie_username_with_id(&self) -> String {
+        format!("{}_{}", self.movie_username(), self.id)
     }
 
     pub fn poster_url(&self) -> Option<String> {
         self.poster_path
             .as_ref()
-            .map(|path| format!("https://image.tmdb.org/t/p/w185{}", path))
+            .map(|path| tmdb_image_url(path, "w185"))
     }
 
     pub fn background_url(&self) -> Option<String> {
         self.backdrop_path
             .as_ref()
-            .map(|path| format!("https://image.tmdb.org/t/p/w780{}", path))
+            .map(|path| tmdb_image_url(path, "w780"))
     }
 
     pub fn movie_url(&self) -> String {
@@ -172,88 +407,232 @@ impl MovieInfo {
     }
 }
 
-pub async fn create_movie_user(
+/// Profile of movie or TV show account
+pub struct MediaProfile {
+    pub username: String,
+    pub account_kind: AccountKind,
+    pub tmdb_id: u32,
+    pub display_name: String,
+    pub overview: String,
+    pub tmdb_url: String,
+    pub poster_url: Option<String>,
+    pub background_url: Option<String>,
+}
+
+impl From<&MovieInfo> for MediaProfile {
+    fn from(movie_info: &MovieInfo) -> Self {
+        Self {
+            username: movie_info.movie_username_with_id(),
+            account_kind: AccountKind::Movie,
+            tmdb_id: movie_info.id,
+            display_name: movie_info.title.clone(),
+            overview: movie_info.overview.clone(),
+            tmdb_url: movie_info.movie_url(),
+            poster_url: movie_info.poster_url(),
+            background_url: movie_info.background_url(),
+        }
+    }
+}
+
+fn tmdb_profile_field(tmdb_url: String) -> ExtraField {
+    ExtraField {
+        name: TMDB_PROFILE_FIELD.to_string(),
+        value: markdown_basic_to_html(&tmdb_url).unwrap_or_else(|_| tmdb_url.clone()),
+        value_source: Some(tmdb_url),
+    }
+}
+
+async fn fetch_media_image(
+    instance: &Instance,
+    url: &str,
+    media_dir: &Path,
+) -> Option<ProfileImage> {
+    match fetch_file(instance, url, None, ACTOR_IMAGE_MAX_SIZE, media_dir).await {
+        Ok((file_name, file_size, maybe_media_type)) => {
+            let image = ProfileImage::new(file_name, file_size, maybe_media_type);
+            Some(image)
+        }
+        Err(error) => {
+            log::warn!("failed to fetch image {} ({})", url, error);
+            None
+        }
+    }
+}
+
+pub async fn create_media_user(
     instance: &Instance,
     db_client: &mut impl DatabaseClient,
-    movie_info: &MovieInfo,
-    password: &str,
+    media_profile: MediaProfile,
     media_dir: &Path,
 ) -> Result<User, anyhow::Error> {
-    let username = movie_info.movie_username();
+    let username = media_profile.username;
     validate_local_username(&username)?;
-    let password_hash = hash_password(password)?;
     let private_key = generate_rsa_key()?;
     let private_key_pem = serialize_private_key(&private_key)?;
     let user_data = UserCreateData {
         username: username.clone(),
-        password_hash: Some(password_hash),
+        password_hash: None,
         private_key_pem,
         wallet_address: None,
         invite_code: None,
-        role: Role::NormalUser,
+        role: Role::Guest,
+        account_kind: media_profile.account_kind,
+        tmdb_id: Some(i32::try_from(media_profile.tmdb_id)?),
     };
     let mut user = create_user(db_client, user_data).await?;
     log::info!("user {username} created");
     // Update profile
     let mut profile_data = ProfileUpdateData::from(&user.profile);
 
-    let tmdb_profile_url = movie_info.movie_url();
-    profile_data.extra_fields = vec![ExtraField {
-        name: "TMDB Profile".to_string(),
-        value: markdown_basic_to_html(&tmdb_profile_url)
-            .unwrap_or_else(|_| tmdb_profile_url.clone()),
-        value_source: Some(tmdb_profile_url),
-    }];
-    profile_data.bio = Some(movie_info.overview.clone());
-    profile_data.display_name = Some(movie_info.title.clone());
-    match movie_info.poster_url() {
+    profile_data.extra_fields = vec![tmdb_profile_field(media_profile.tmdb_url)];
+    profile_data.bio = Some(media_profile.overview);
+    profile_data.display_name = Some(media_profile.display_name);
+    profile_data.avatar = match media_profile.poster_url {
+        Some(poster_url) => fetch_media_image(instance, &poster_url, media_dir).await,
+        None => None,
+    };
+    profile_data.banner = match media_profile.background_url {
+        Some(background_url) => fetch_media_image(instance, &background_url, media_dir).await,
+        None => None,
+    };
+    user.profile = update_profile(db_client, &user.id, profile_data).await?;
+    log::info!("user {username} profile updated");
+    Ok(user)
+}
+
+pub async fn create_movie_user(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_info: &MovieInfo,
+    media_dir: &Path,
+) -> Result<User, anyhow::Error> {
+    create_media_user(
+        instance,
+        db_client,
+        MediaProfile::from(movie_info),
+        media_dir,
+    )
+    .await
+}
+
+/// Fetches current metadata of movie or TV show account
+async fn get_media_profile(
+    movie_provider: &dyn MovieMetadataProvider,
+    user: &User,
+) -> Result<MediaProfile, MovieError> {
+    let tmdb_id = user
+        .profile
+        .tmdb_id
+        .and_then(|tmdb_id| u32::try_from(tmdb_id).ok())
+        .ok_or(MovieError::NotMovie)?;
+    let media_profile = match user.profile.account_kind {
+        AccountKind::User => return Err(MovieError::NotMovie),
+        AccountKind::Movie => {
+            let movie_info = movie_provider.get_movie(tmdb_id).await?;
+            MediaProfile::from(&movie_info)
+        }
+        AccountKind::TvSeries => {
+            let series_details = movie_provider.get_tv_series(tmdb_id).await?;
+            MediaProfile::from(&series_details.info)
+        }
+        AccountKind::TvSeason => {
+            get_tv_season_profile(movie_provider, &user.profile.username).await?
+        }
+        AccountKind::MoviePerson => {
+            let person_details = movie_provider.get_person(tmdb_id).await?;
+            MediaProfile::from(&person_details)
+        }
+    };
+    Ok(media_profile)
+}
+
+/// Re-fetches metadata of movie or TV show account and updates its profile.
+/// Returns updated user and the files that are no longer used.
+pub async fn refresh_media_user(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: &dyn MovieMetadataProvider,
+    media_dir: &Path,
+    user: &User,
+) -> Result<(User, DeletionQueue, bool), MovieError> {
+    let media_profile = get_media_profile(movie_provider, user).await?;
+    // Username doesn't change even if the title was edited
+    let mut profile_data = ProfileUpdateData::from(&user.profile);
+    profile_data.display_name = Some(media_profile.display_name);
+    profile_data.bio = Some(media_profile.overview);
+    profile_data
+        .extra_fields
+        .retain(|field| field.name != TMDB_PROFILE_FIELD);
+    profile_data
+        .extra_fields
+        .insert(0, tmdb_profile_field(media_profile.tmdb_url));
+    // Current image is kept if the new one can't be fetched
+    match media_profile.poster_url {
         Some(poster_url) => {
-            profile_data.avatar =
-                match fetch_file(instance, &poster_url, None, ACTOR_IMAGE_MAX_SIZE, media_dir).await
-                {
-                    Ok((file_name, file_size, maybe_media_type)) => {
-                        let image = ProfileImage::new(file_name, file_size, maybe_media_type);
-                        Some(image)
-                    }
-                    Err(error) => {
-                        log::warn!("failed to fetch movie poster ({})", error);
-                        None
-                    }
-                }
+            if let Some(avatar) = fetch_media_image(instance, &poster_url, media_dir).await {
+                profile_data.avatar = Some(avatar);
+            };
         }
         None => profile_data.avatar = None,
-    }
-    match movie_info.background_url() {
+    };
+    match media_profile.background_url {
         Some(background_url) => {
-            profile_data.banner = match fetch_file(
-                instance,
-                &background_url,
-                None,
-                ACTOR_IMAGE_MAX_SIZE,
-                media_dir,
-            )
-            .await
-            {
-                Ok((file_name, file_size, maybe_media_type)) => {
-                    let image = ProfileImage::new(file_name, file_size, maybe_media_type);
-                    Some(image)
-                }
-                Err(error) => {
-                    log::warn!("failed to fetch movie background ({})", error);
-                    None
-                }
-            }
+            if let Some(banner) = fetch_media_image(instance, &background_url, media_dir).await {
+                profile_data.banner = Some(banner);
+            };
         }
         None => profile_data.banner = None,
+    };
+    let mut replaced_files = vec![];
+    for (old_image, new_image) in [
+        (&user.profile.avatar, &profile_data.avatar),
+        (&user.profile.banner, &profile_data.banner),
+    ] {
+        if let Some(old_image) = old_image {
+            let is_replaced = new_image
+                .as_ref()
+                .map_or(true, |new_image| new_image.file_name != old_image.file_name);
+            if is_replaced {
+                replaced_files.push(old_image.file_name.clone());
+            };
+        };
     }
+    let is_changed = is_media_profile_changed(&user.profile, &profile_data);
+    let mut user = user.clone();
     user.profile = update_profile(db_client, &user.id, profile_data).await?;
-    log::info!("user {username} profile updated");
-    Ok(user)
+    let orphaned_files = find_orphaned_files(db_client, replaced_files).await?;
+    let deletion_queue = DeletionQueue {
+        files: orphaned_files,
+        ipfs_objects: vec![],
+    };
+    Ok((user, deletion_queue, is_changed))
+}
+
+fn is_media_profile_changed(profile: &DbActorProfile, profile_data: &ProfileUpdateData) -> bool {
+    let image_file_name =
+        |image: &Option<ProfileImage>| image.as_ref().map(|image| image.file_name.clone());
+    let field_values = |fields: &[ExtraField]| {
+        fields
+            .iter()
+            .map(|field| (field.name.clone(), field.value.clone()))
+            .collect::<Vec<_>>()
+    };
+    profile.display_name != profile_data.display_name
+        || profile.bio != profile_data.bio
+        || image_file_name(&profile.avatar) != image_file_name(&profile_data.avatar)
+        || image_file_name(&profile.banner) != image_file_name(&profile_data.banner)
+        || field_values(&profile.extra_fields.0) != field_values(&profile_data.extra_fields)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tmdb::people::add_movie_credits;
+    use crate::tmdb::{import_movie_metadata_dump, FileMetadataProvider};
+    use fedimovies_models::background_jobs::queries::get_job_batch;
+    use fedimovies_models::background_jobs::types::JobType;
+    use fedimovies_models::database::test_utils::create_test_database;
+    use serial_test::serial;
 
     #[test]
     fn test_movie_username() {
@@ -275,4 +654,227 @@ mod tests {
 
         assert_eq!(movie.movie_username(), "AvatarTheWayOfWater_2022");
     }
+
+    #[test]
+    fn test_parse_movie_username() {
+        let movie_username = MovieUsername::parse("Avatar_2009").unwrap();
+        assert_eq!(movie_username.title, "Avatar");
+        assert_eq!(movie_username.year, 2009);
+        assert_eq!(movie_username.tmdb_id, None);
+
+        let movie_username = MovieUsername::parse("Avatar_2009_19995").unwrap();
+        assert_eq!(movie_username.title, "Avatar");
+        assert_eq!(movie_username.year, 2009);
+        assert_eq!(movie_username.tmdb_id, Some(19995));
+        assert_eq!(movie_username.short_form(), "Avatar_2009");
+
+        assert!(MovieUsername::parse("avatar").is_err());
+        assert!(MovieUsername::parse("user_name").is_err());
+        assert!(MovieUsername::parse("_2009").is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_movie_info() {
+        let db_client = &create_test_database().await;
+        let movie_provider = FileMetadataProvider::for_test();
+        let movie_username = MovieUsername::parse("Avatar_2009").unwrap();
+        let movie_info = get_movie_info(db_client, Some(&movie_provider), &movie_username)
+            .await
+            .unwrap();
+        assert_eq!(movie_info.id, 19995);
+        assert_eq!(movie_info.movie_username_with_id(), "Avatar_2009_19995");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_movie_info_from_catalog() {
+        let db_client = &create_test_database().await;
+        let movie_provider = FileMetadataProvider::for_test();
+        let movie_info = movie_provider.get_movie(19995).await.unwrap();
+        let dump = serde_json::to_string(&movie_info).unwrap();
+        import_movie_metadata_dump(db_client, dump.as_bytes())
+            .await
+            .unwrap();
+
+        // TMDB API is not used
+        let movie_username = MovieUsername::parse("Avatar_2009").unwrap();
+        let movie_info = get_movie_info(db_client, None, &movie_username)
+            .await
+            .unwrap();
+        assert_eq!(movie_info.id, 19995);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_movie_info_from_catalog_confirmed() {
+        let db_client = &create_test_database().await;
+        let movie_provider = FileMetadataProvider::for_test();
+        let movie_info = movie_provider
+            .search_movies("Pinocchio", Some(2022))
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|movie_info| movie_info.id == 532639)
+            .unwrap();
+        let dump = serde_json::to_string(&movie_info).unwrap();
+        import_movie_metadata_dump(db_client, dump.as_bytes())
+            .await
+            .unwrap();
+
+        // Another movie with the same title is found in TMDB
+        let movie_username = MovieUsername::parse("Pinocchio_2022").unwrap();
+        let result = get_movie_info(db_client, Some(&movie_provider), &movie_username).await;
+        assert!(matches!(
+            result,
+            Err(MovieError::Ambiguous(candidates)) if candidates.len() == 2
+        ));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_movie_info_ambiguous() {
+        let db_client = &create_test_database().await;
+        let movie_provider = FileMetadataProvider::for_test();
+        let movie_username = MovieUsername::parse("Pinocchio_2022").unwrap();
+        let result = get_movie_info(db_client, Some(&movie_provider), &movie_username).await;
+        assert!(matches!(
+            result,
+            Err(MovieError::Ambiguous(candidates))
+                if candidates == vec!["Pinocchio_2022_532639", "Pinocchio_2022_960676"]
+        ));
+
+        let movie_username = MovieUsername::parse("Pinocchio_2022_960676").unwrap();
+        let movie_info = get_movie_info(db_client, Some(&movie_provider), &movie_username)
+            .await
+            .unwrap();
+        assert_eq!(movie_info.id, 960676);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_or_create_media_user_short_form_ambiguous() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+        let movie_info = movie_provider
+            .search_movies("Pinocchio", Some(2022))
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|movie_info| movie_info.id == 532639)
+            .unwrap();
+        create_movie_user(&instance, db_client, &movie_info, &media_dir)
+            .await
+            .unwrap();
+
+        // Only one movie account exists locally, but TMDB has two movies
+        let result = get_or_create_media_user(
+            &instance,
+            db_client,
+            Some(&movie_provider),
+            &media_dir,
+            "Pinocchio_2022",
+        )
+        .await;
+        assert!(matches!(result, Err(MovieError::Ambiguous(_))));
+
+        // Movie lookup is not available
+        let user =
+            get_or_create_media_user(&instance, db_client, None, &media_dir, "Pinocchio_2022")
+                .await
+                .unwrap();
+        assert_eq!(user.profile.username, "Pinocchio_2022_532639");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_refresh_media_user() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+        let movie_info = movie_provider.get_movie(19995).await.unwrap();
+        let user = create_movie_user(&instance, db_client, &movie_info, &media_dir)
+            .await
+            .unwrap();
+        let mut profile_data = ProfileUpdateData::from(&user.profile);
+        profile_data.display_name = Some("Old title".to_string());
+        profile_data.bio = Some("Old overview".to_string());
+        let mut user = user;
+        user.profile = update_profile(db_client, &user.id, profile_data)
+            .await
+            .unwrap();
+
+        let (user, deletion_queue, is_changed) =
+            refresh_media_user(&instance, db_client, &movie_provider, &media_dir, &user)
+                .await
+                .unwrap();
+        assert!(is_changed);
+        assert_eq!(user.profile.username, "Avatar_2009_19995");
+        assert_eq!(user.profile.display_name.as_deref(), Some("Avatar"));
+        assert_eq!(
+            user.profile.bio.as_deref(),
+            Some(movie_info.overview.as_str())
+        );
+        let extra_fields = user.profile.extra_fields.clone().into_inner();
+        assert_eq!(extra_fields.len(), 1);
+        assert_eq!(extra_fields[0].name, TMDB_PROFILE_FIELD);
+        assert!(deletion_queue.files.is_empty());
+
+        let (_, _, is_changed) =
+            refresh_media_user(&instance, db_client, &movie_provider, &media_dir, &user)
+                .await
+                .unwrap();
+        assert!(!is_changed);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_lookup_and_create_movie_user_with_credits() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+        let user = lookup_and_create_movie_user(
+            &instance,
+            db_client,
+            Some(&movie_provider),
+            &media_dir,
+            "Dune_2021",
+        )
+        .await
+        .unwrap();
+        assert_eq!(user.profile.username, "Dune_2021_438631");
+        assert_eq!(user.profile.extra_fields.clone().into_inner().len(), 1);
+
+        // Credits are added by background job
+        let batch = get_job_batch(db_client, &JobType::MovieCredits, 10, 600)
+            .await
+            .unwrap();
+        assert_eq!(batch.len(), 1);
+        let user = add_movie_credits(&instance, db_client, &movie_provider, &media_dir, &user)
+            .await
+            .unwrap();
+        let field_names: Vec<String> = user
+            .profile
+            .extra_fields
+            .into_inner()
+            .into_iter()
+            .map(|field| field.name)
+            .collect();
+        assert_eq!(
+            field_names,
+            vec!["TMDB Profile", "Director", "Writer", "Cast"]
+        );
+
+        // Mentioning a director resolves to the account created from credits
+        let director =
+            get_or_create_media_user(&instance, db_client, None, &media_dir, "DenisVilleneuve")
+                .await
+                .unwrap();
+        assert_eq!(director.profile.username, "DenisVilleneuve_Person_137427");
+        assert_eq!(director.profile.account_kind, AccountKind::MoviePerson);
+    }
 }
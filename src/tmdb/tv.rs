@@ -0,0 +1,514 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use fedimovies_config::Instance;
+use fedimovies_models::database::{DatabaseClient, DatabaseError};
+use fedimovies_models::profiles::types::AccountKind;
+use fedimovies_models::users::queries::get_user_by_name;
+use fedimovies_models::users::types::User;
+
+use super::provider::MovieMetadataProvider;
+use super::service::{
+    camel_case_title, create_media_user, expand_camel_case, find_user_by_short_form,
+    find_user_by_tmdb_id, tmdb_image_url, MediaProfile, MovieError,
+};
+
+const TV_MARKER: &str = "TV";
+
+/// TV series username with optional TMDB ID and season number
+/// (e.g. `Title_TV_2008`, `Title_TV_2008_1396` or `Title_TV_2008_1396_S2`)
+#[derive(Debug, PartialEq)]
+pub struct TvUsername {
+    pub title: String,
+    pub year: u32,
+    pub tmdb_id: Option<u32>,
+    pub season_number: Option<u32>,
+}
+
+impl TvUsername {
+    pub fn parse(username: &str) -> Result<Self, MovieError> {
+        let mut parts: Vec<&str> = username.split('_').collect();
+        let season_number = match parts.last().and_then(|part| part.strip_prefix('S')) {
+            Some(number) => {
+                let number = number.parse::<u32>().map_err(|_| MovieError::NotMovie)?;
+                parts.pop();
+                Some(number)
+            }
+            None => None,
+        };
+        let (title, year, maybe_tmdb_id) = match parts.as_slice() {
+            [title, TV_MARKER, year] => (title, year, None),
+            [title, TV_MARKER, year, tmdb_id] => (title, year, Some(tmdb_id)),
+            _ => return Err(MovieError::NotMovie),
+        };
+        if title.is_empty() {
+            return Err(MovieError::NotMovie);
+        };
+        let year = year.parse::<u32>().map_err(|_| MovieError::NotMovie)?;
+        let tmdb_id = match maybe_tmdb_id {
+            Some(tmdb_id) => Some(tmdb_id.parse::<u32>().map_err(|_| MovieError::NotMovie)?),
+            None => None,
+        };
+        let tv_username = Self {
+            title: title.to_string(),
+            year,
+            tmdb_id,
+            season_number,
+        };
+        Ok(tv_username)
+    }
+
+    /// Returns username of TV series without TMDB ID
+    pub fn short_form(&self) -> String {
+        format!("{}_{}_{}", self.title, TV_MARKER, self.year)
+    }
+}
+
+fn season_username(series_username: &str, season_number: u32) -> String {
+    format!("{}_S{}", series_username, season_number)
+}
+
+fn season_media_profile(
+    username: String,
+    series_id: u32,
+    series_name: &str,
+    series_overview: &str,
+    season_info: TvSeasonInfo,
+) -> MediaProfile {
+    let overview = if season_info.overview.is_empty() {
+        series_overview.to_string()
+    } else {
+        season_info.overview
+    };
+    MediaProfile {
+        username,
+        account_kind: AccountKind::TvSeason,
+        tmdb_id: season_info.id,
+        display_name: format!("{}: {}", series_name, season_info.name),
+        overview,
+        tmdb_url: format!(
+            "https://www.themoviedb.org/tv/{}/season/{}",
+            series_id, season_info.season_number,
+        ),
+        poster_url: season_info
+            .poster_path
+            .as_ref()
+            .map(|path| tmdb_image_url(path, "w185")),
+        background_url: None,
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TvSearchResponse {
+    pub page: u32,
+    pub results: Vec<TvSeriesInfo>,
+    pub total_results: u32,
+    pub total_pages: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TvSeriesInfo {
+    pub poster_path: Option<String>,
+    #[serde(default)]
+    pub adult: bool,
+    pub overview: String,
+    #[serde(default)]
+    pub first_air_date: String,
+    pub id: u32,
+    pub original_name: String,
+    pub original_language: String,
+    pub name: String,
+    pub backdrop_path: Option<String>,
+    pub popularity: f32,
+    pub vote_count: u32,
+    pub vote_average: f32,
+}
+
+impl TvSeriesInfo {
+    pub fn tv_username(&self) -> String {
+        format!(
+            "{}_{}_{}",
+            camel_case_title(&self.name),
+            TV_MARKER,
+            self.first_air_date.split('-').next().unwrap(),
+        )
+    }
+
+    /// Returns username with TMDB ID suffix, which is unique
+    pub fn tv_username_with_id(&self) -> String {
+        format!("{}_{}", self.tv_username(), self.id)
+    }
+
+    pub fn tv_url(&self) -> String {
+        format!("https://www.themoviedb.org/tv/{}", self.id)
+    }
+}
+
+impl From<&TvSeriesInfo> for MediaProfile {
+    fn from(series_info: &TvSeriesInfo) -> Self {
+        Self {
+            username: series_info.tv_username_with_id(),
+            account_kind: AccountKind::TvSeries,
+            tmdb_id: series_info.id,
+            display_name: series_info.name.clone(),
+            overview: series_info.overview.clone(),
+            tmdb_url: series_info.tv_url(),
+            poster_url: series_info
+                .poster_path
+                .as_ref()
+                .map(|path| tmdb_image_url(path, "w185")),
+            background_url: series_info
+                .backdrop_path
+                .as_ref()
+                .map(|path| tmdb_image_url(path, "w780")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TvSeriesDetails {
+    #[serde(flatten)]
+    pub info: TvSeriesInfo,
+    pub seasons: Vec<TvSeasonInfo>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TvSeasonInfo {
+    pub id: u32,
+    pub name: String,
+    pub overview: String,
+    pub season_number: u32,
+    pub air_date: Option<String>,
+    pub poster_path: Option<String>,
+    #[serde(default)]
+    pub episode_count: u32,
+}
+
+pub async fn get_tv_series_info(
+    movie_provider: &dyn MovieMetadataProvider,
+    tv_username: &TvUsername,
+) -> Result<TvSeriesInfo, MovieError> {
+    let series_name = expand_camel_case(&tv_username.title);
+    let year = tv_username.year;
+    let short_form = tv_username.short_form();
+
+    let series = movie_provider.search_tv_series(&series_name, year).await?;
+    let mut candidates: Vec<TvSeriesInfo> = series
+        .into_iter()
+        .filter(|series_info| series_info.tv_username() == short_form && !series_info.adult)
+        .collect();
+    if let Some(tmdb_id) = tv_username.tmdb_id {
+        candidates.retain(|series_info| series_info.id == tmdb_id);
+    };
+    match candidates.len() {
+        0 => {
+            log::error!("TV series not found in TMDB API ({series_name}, {year})");
+            Err(MovieError::NotFoundError(
+                "does not match any TV series found in TMDB API",
+            ))
+        }
+        1 => Ok(candidates.remove(0)),
+        _ => {
+            let candidates = candidates
+                .iter()
+                .map(|series_info| series_info.tv_username_with_id())
+                .collect();
+            Err(MovieError::Ambiguous(candidates))
+        }
+    }
+}
+
+async fn get_or_create_tv_series_user(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
+    media_dir: &Path,
+    tv_username: &TvUsername,
+) -> Result<User, MovieError> {
+    if let Some(tmdb_id) = tv_username.tmdb_id {
+        let username = format!("{}_{}", tv_username.short_form(), tmdb_id);
+        match get_user_by_name(db_client, &username).await {
+            Ok(user) => return Ok(user),
+            Err(DatabaseError::NotFound(_)) => (),
+            Err(other_error) => return Err(other_error.into()),
+        };
+    };
+    // Short form is resolved against TMDB API, not only against
+    // local accounts, because other TV series may have the same
+    // name and year
+    let result = match movie_provider {
+        Some(movie_provider) => get_tv_series_info(movie_provider, tv_username).await,
+        None => Err(MovieError::NotFoundError(
+            "TV series lookup is not available",
+        )),
+    };
+    let series_info = match result {
+        Ok(series_info) => series_info,
+        Err(MovieError::NotFoundError(message)) if tv_username.tmdb_id.is_none() => {
+            // TV series lookup is not available, fall back to local accounts
+            return find_user_by_short_form(db_client, &tv_username.short_form())
+                .await?
+                .ok_or(MovieError::NotFoundError(message));
+        }
+        Err(other_error) => return Err(other_error),
+    };
+    // TV series account may exist under a different username
+    if let Some(user) =
+        find_user_by_tmdb_id(db_client, &AccountKind::TvSeries, series_info.id).await?
+    {
+        return Ok(user);
+    };
+    let user = create_media_user(
+        instance,
+        db_client,
+        MediaProfile::from(&series_info),
+        media_dir,
+    )
+    .await?;
+    Ok(user)
+}
+
+/// Returns local TV series account with the given TMDB ID,
+/// creating it if necessary
+pub async fn get_or_create_tv_series_user_by_id(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: &dyn MovieMetadataProvider,
+    media_dir: &Path,
+    tmdb_id: u32,
+) -> Result<User, MovieError> {
+    if let Some(user) = find_user_by_tmdb_id(db_client, &AccountKind::TvSeries, tmdb_id).await? {
+        return Ok(user);
+    };
+    let series_info = movie_provider.get_tv_series(tmdb_id).await?.info;
+    if series_info.adult {
+        return Err(MovieError::NotFoundError(
+            "adult TV series are not supported",
+        ));
+    };
+    let user = create_media_user(
+        instance,
+        db_client,
+        MediaProfile::from(&series_info),
+        media_dir,
+    )
+    .await?;
+    Ok(user)
+}
+
+/// Returns local TV series or TV season account,
+/// creating it if TV series exists in TMDB
+pub async fn get_or_create_tv_user(
+    instance: &Instance,
+    db_client: &mut impl DatabaseClient,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
+    media_dir: &Path,
+    tv_username: &TvUsername,
+) -> Result<User, MovieError> {
+    let series_user =
+        get_or_create_tv_series_user(instance, db_client, movie_provider, media_dir, tv_username)
+            .await?;
+    let season_number = match tv_username.season_number {
+        Some(season_number) => season_number,
+        None => return Ok(series_user),
+    };
+    let username = season_username(&series_user.profile.username, season_number);
+    match get_user_by_name(db_client, &username).await {
+        Ok(user) => return Ok(user),
+        Err(DatabaseError::NotFound(_)) => (),
+        Err(other_error) => return Err(other_error.into()),
+    };
+    let movie_provider = movie_provider.ok_or(MovieError::NotFoundError(
+        "TV series lookup is not available",
+    ))?;
+    let series_id = series_user
+        .profile
+        .tmdb_id
+        .and_then(|tmdb_id| u32::try_from(tmdb_id).ok())
+        .ok_or(MovieError::NotMovie)?;
+    let season_info = movie_provider
+        .get_tv_series(series_id)
+        .await?
+        .seasons
+        .into_iter()
+        .find(|season_info| season_info.season_number == season_number)
+        .ok_or(MovieError::NotFoundError("TV season not found in TMDB API"))?;
+    if let Some(user) =
+        find_user_by_tmdb_id(db_client, &AccountKind::TvSeason, season_info.id).await?
+    {
+        return Ok(user);
+    };
+    let series_name = series_user
+        .profile
+        .display_name
+        .unwrap_or(series_user.profile.username);
+    let series_overview = series_user.profile.bio.unwrap_or_default();
+    let media_profile = season_media_profile(
+        username,
+        series_id,
+        &series_name,
+        &series_overview,
+        season_info,
+    );
+    let user = create_media_user(instance, db_client, media_profile, media_dir).await?;
+    Ok(user)
+}
+
+/// Fetches current metadata of TV season account
+pub(super) async fn get_tv_season_profile(
+    movie_provider: &dyn MovieMetadataProvider,
+    username: &str,
+) -> Result<MediaProfile, MovieError> {
+    let tv_username = TvUsername::parse(username)?;
+    let (series_id, season_number) = tv_username
+        .tmdb_id
+        .zip(tv_username.season_number)
+        .ok_or(MovieError::NotMovie)?;
+    let series_details = movie_provider.get_tv_series(series_id).await?;
+    let season_info = series_details
+        .seasons
+        .into_iter()
+        .find(|season_info| season_info.season_number == season_number)
+        .ok_or(MovieError::NotFoundError("TV season not found in TMDB API"))?;
+    let media_profile = season_media_profile(
+        username.to_string(),
+        series_id,
+        &series_details.info.name,
+        &series_details.info.overview,
+        season_info,
+    );
+    Ok(media_profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmdb::FileMetadataProvider;
+    use fedimovies_models::database::test_utils::create_test_database;
+    use serial_test::serial;
+
+    #[test]
+    fn test_parse_tv_username() {
+        let tv_username = TvUsername::parse("BreakingBad_TV_2008").unwrap();
+        assert_eq!(tv_username.title, "BreakingBad");
+        assert_eq!(tv_username.year, 2008);
+        assert_eq!(tv_username.tmdb_id, None);
+        assert_eq!(tv_username.season_number, None);
+        assert_eq!(tv_username.short_form(), "BreakingBad_TV_2008");
+
+        let tv_username = TvUsername::parse("BreakingBad_TV_2008_1396_S2").unwrap();
+        assert_eq!(tv_username.tmdb_id, Some(1396));
+        assert_eq!(tv_username.season_number, Some(2));
+
+        let tv_username = TvUsername::parse("BreakingBad_TV_2008_S2").unwrap();
+        assert_eq!(tv_username.tmdb_id, None);
+        assert_eq!(tv_username.season_number, Some(2));
+
+        assert!(TvUsername::parse("BreakingBad_2008").is_err());
+        assert!(TvUsername::parse("BreakingBad_TV_2008_S").is_err());
+        assert!(TvUsername::parse("_TV_2008").is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_or_create_tv_user() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+
+        let tv_username = TvUsername::parse("BreakingBad_TV_2008_S2").unwrap();
+        let season_user = get_or_create_tv_user(
+            &instance,
+            db_client,
+            Some(&movie_provider),
+            &media_dir,
+            &tv_username,
+        )
+        .await
+        .unwrap();
+        assert_eq!(season_user.profile.username, "BreakingBad_TV_2008_1396_S2");
+        assert_eq!(season_user.profile.account_kind, AccountKind::TvSeason);
+        assert_eq!(season_user.profile.tmdb_id, Some(3573));
+        assert_eq!(
+            season_user.profile.display_name.as_deref(),
+            Some("Breaking Bad: Season 2"),
+        );
+
+        // Series account is created too
+        let series_user = get_user_by_name(db_client, "BreakingBad_TV_2008_1396")
+            .await
+            .unwrap();
+        assert_eq!(series_user.profile.account_kind, AccountKind::TvSeries);
+        assert_eq!(series_user.profile.tmdb_id, Some(1396));
+
+        // Short form is an alias
+        let tv_username = TvUsername::parse("BreakingBad_TV_2008").unwrap();
+        let user = get_or_create_tv_user(
+            &instance,
+            db_client,
+            Some(&movie_provider),
+            &media_dir,
+            &tv_username,
+        )
+        .await
+        .unwrap();
+        assert_eq!(user.id, series_user.id);
+        // Local accounts are used if TV series lookup is not available
+        let user = get_or_create_tv_user(&instance, db_client, None, &media_dir, &tv_username)
+            .await
+            .unwrap();
+        assert_eq!(user.id, series_user.id);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_or_create_tv_series_user_by_id() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+        let user = get_or_create_tv_series_user_by_id(
+            &instance,
+            db_client,
+            &movie_provider,
+            &media_dir,
+            1396,
+        )
+        .await
+        .unwrap();
+        assert_eq!(user.profile.username, "BreakingBad_TV_2008_1396");
+        assert_eq!(user.profile.account_kind, AccountKind::TvSeries);
+
+        let same_user = get_or_create_tv_series_user_by_id(
+            &instance,
+            db_client,
+            &movie_provider,
+            &media_dir,
+            1396,
+        )
+        .await
+        .unwrap();
+        assert_eq!(same_user.id, user.id);
+    }
+
+    #[tokio::test]
+    async fn test_get_tv_season_profile() {
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_profile = get_tv_season_profile(&movie_provider, "BreakingBad_TV_2008_1396_S2")
+            .await
+            .unwrap();
+        assert_eq!(media_profile.account_kind, AccountKind::TvSeason);
+        assert_eq!(media_profile.tmdb_id, 3573);
+        assert_eq!(media_profile.display_name, "Breaking Bad: Season 2");
+        assert_eq!(
+            media_profile.tmdb_url,
+            "https://www.themoviedb.org/tv/1396/season/2",
+        );
+
+        // Season username must include TMDB ID of the series
+        let result = get_tv_season_profile(&movie_provider, "BreakingBad_TV_2008_S2").await;
+        assert!(matches!(result, Err(MovieError::NotMovie)));
+    }
+}
@@ -0,0 +1,28 @@
+use crate::errors::ValidationError;
+
+const TITLE_LENGTH_MAX: usize = 200;
+pub const LIST_MEMBER_LIMIT: usize = 500;
+
+pub fn validate_list_title(title: &str) -> Result<(), ValidationError> {
+    if title.trim().is_empty() {
+        return Err(ValidationError("title can not be empty".to_string()));
+    };
+    if title.chars().count() > TITLE_LENGTH_MAX {
+        return Err(ValidationError("title is too long".to_string()));
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_list_title() {
+        assert!(validate_list_title("Critics").is_ok());
+        let error = validate_list_title(" ").unwrap_err();
+        assert_eq!(error.to_string(), "title can not be empty");
+        let error = validate_list_title(&"a".repeat(201)).unwrap_err();
+        assert_eq!(error.to_string(), "title is too long");
+    }
+}
@@ -1,4 +1,6 @@
 pub mod emojis;
+pub mod lists;
+pub mod movie_lists;
 pub mod posts;
 pub mod profiles;
 pub mod tags;
@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use fedimovies_models::movie_lists::types::MovieListData;
+
+use crate::errors::ValidationError;
+
+const TITLE_LENGTH_MAX: usize = 200;
+const DESCRIPTION_LENGTH_MAX: usize = 5000;
+const NOTE_LENGTH_MAX: usize = 1000;
+pub const MOVIE_LIST_ITEM_LIMIT: usize = 250;
+
+pub fn validate_movie_list_data(list_data: &MovieListData) -> Result<(), ValidationError> {
+    if list_data.title.trim().is_empty() {
+        return Err(ValidationError("title can not be empty".to_string()));
+    };
+    if list_data.title.chars().count() > TITLE_LENGTH_MAX {
+        return Err(ValidationError("title is too long".to_string()));
+    };
+    if let Some(ref description) = list_data.description {
+        if description.chars().count() > DESCRIPTION_LENGTH_MAX {
+            return Err(ValidationError("description is too long".to_string()));
+        };
+    };
+    if list_data.items.len() > MOVIE_LIST_ITEM_LIMIT {
+        return Err(ValidationError("too many items".to_string()));
+    };
+    let mut movie_ids = HashSet::new();
+    for item in list_data.items.iter() {
+        if !movie_ids.insert(item.movie_id) {
+            return Err(ValidationError("duplicate item".to_string()));
+        };
+        if let Some(ref note) = item.note {
+            if note.chars().count() > NOTE_LENGTH_MAX {
+                return Err(ValidationError("note is too long".to_string()));
+            };
+        };
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fedimovies_models::movie_lists::types::MovieListItemData;
+    use fedimovies_utils::id::generate_ulid;
+
+    #[test]
+    fn test_validate_movie_list_data() {
+        let movie_id = generate_ulid();
+        let mut list_data = MovieListData {
+            title: "Best of 2023".to_string(),
+            description: None,
+            items: vec![MovieListItemData {
+                movie_id,
+                note: None,
+            }],
+        };
+        assert!(validate_movie_list_data(&list_data).is_ok());
+
+        list_data.items.push(MovieListItemData {
+            movie_id,
+            note: Some("again".to_string()),
+        });
+        let error = validate_movie_list_data(&list_data).unwrap_err();
+        assert_eq!(error.to_string(), "duplicate item");
+
+        list_data.items.clear();
+        list_data.title = " ".to_string();
+        let error = validate_movie_list_data(&list_data).unwrap_err();
+        assert_eq!(error.to_string(), "title can not be empty");
+    }
+}
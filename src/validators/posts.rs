@@ -1,4 +1,4 @@
-use fedimovies_utils::html::clean_html_strict;
+use fedimovies_utils::html::{clean_html_strict, html_to_text};
 
 use crate::errors::ValidationError;
 
@@ -7,8 +7,12 @@ pub const MENTION_LIMIT: usize = 50;
 pub const LINK_LIMIT: usize = 10;
 pub const EMOJI_LIMIT: usize = 50;
 
+pub const RATING_MIN: f32 = 0.5;
+pub const RATING_MAX: f32 = 5.0;
+
 pub const OBJECT_ID_SIZE_MAX: usize = 2000;
 pub const CONTENT_MAX_SIZE: usize = 100000;
+const SUMMARY_MAX_LENGTH: usize = 500;
 const CONTENT_ALLOWED_TAGS: [&str; 8] = ["a", "br", "pre", "code", "strong", "em", "p", "span"];
 
 pub fn content_allowed_classes() -> Vec<(&'static str, Vec<&'static str>)> {
@@ -33,6 +37,45 @@ pub fn clean_content(content: &str) -> Result<String, ValidationError> {
     Ok(content_trimmed.to_string())
 }
 
+/// Converts content warning to plain text.
+/// Returns None if summary is empty.
+pub fn clean_summary(summary: &str) -> Result<Option<String>, ValidationError> {
+    let summary_text = html_to_text(summary);
+    let summary_trimmed = summary_text.trim();
+    if summary_trimmed.chars().count() > SUMMARY_MAX_LENGTH {
+        return Err(ValidationError("content warning is too long".to_string()));
+    };
+    if summary_trimmed.is_empty() {
+        return Ok(None);
+    };
+    Ok(Some(summary_trimmed.to_string()))
+}
+
+/// Ratings are given in half-star steps (0.5 - 5.0)
+pub fn validate_rating(rating: f32) -> Result<(), ValidationError> {
+    if !(RATING_MIN..=RATING_MAX).contains(&rating) || (rating * 2.0).fract() != 0.0 {
+        return Err(ValidationError("invalid rating".to_string()));
+    };
+    Ok(())
+}
+
+/// Accepts ISO 639 language codes with optional region subtag (`en`, `pt-BR`)
+pub fn validate_language(language: &str) -> Result<(), ValidationError> {
+    let (code, maybe_region) = match language.split_once('-') {
+        Some((code, region)) => (code, Some(region)),
+        None => (language, None),
+    };
+    let is_valid_code =
+        (2..=3).contains(&code.len()) && code.chars().all(|char| char.is_ascii_lowercase());
+    let is_valid_region = maybe_region.map_or(true, |region| {
+        (2..=4).contains(&region.len()) && region.chars().all(|char| char.is_ascii_alphanumeric())
+    });
+    if !is_valid_code || !is_valid_region {
+        return Err(ValidationError("invalid language code".to_string()));
+    };
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,4 +93,37 @@ mod tests {
         let cleaned = clean_content(content).unwrap();
         assert_eq!(cleaned, "test");
     }
+
+    #[test]
+    fn test_clean_summary() {
+        let summary = " <p>Spoilers <b>ahead</b></p> ";
+        let result = clean_summary(summary).unwrap();
+        assert_eq!(result.as_deref(), Some("Spoilers ahead"));
+        let result = clean_summary("Tom &amp; Jerry").unwrap();
+        assert_eq!(result.as_deref(), Some("Tom & Jerry"));
+        assert_eq!(clean_summary(" ").unwrap(), None);
+        assert!(clean_summary(&"a".repeat(501)).is_err());
+    }
+
+    #[test]
+    fn test_validate_rating() {
+        assert!(validate_rating(0.5).is_ok());
+        assert!(validate_rating(4.5).is_ok());
+        assert!(validate_rating(5.0).is_ok());
+        assert!(validate_rating(0.0).is_err());
+        assert!(validate_rating(3.7).is_err());
+        assert!(validate_rating(5.5).is_err());
+        assert!(validate_rating(f32::NAN).is_err());
+    }
+
+    #[test]
+    fn test_validate_language() {
+        assert!(validate_language("en").is_ok());
+        assert!(validate_language("fil").is_ok());
+        assert!(validate_language("pt-BR").is_ok());
+        assert!(validate_language("").is_err());
+        assert!(validate_language("EN").is_err());
+        assert!(validate_language("english").is_err());
+        assert!(validate_language("en-").is_err());
+    }
 }
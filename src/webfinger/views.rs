@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use actix_web::{get, web, HttpResponse};
 
 use fedimovies_config::{Config, Instance};
@@ -12,7 +14,9 @@ use crate::activitypub::{
 };
 use crate::errors::{HttpError, ValidationError};
 use crate::media::MediaStorage;
-use crate::tmdb::lookup_and_create_movie_user;
+use crate::tmdb::{
+    get_movie_metadata_provider, get_or_create_media_user, MovieError, MovieMetadataProvider,
+};
 
 use super::types::{
     ActorAddress, JsonResourceDescriptor, Link, WebfingerQueryParams, JRD_CONTENT_TYPE,
@@ -78,6 +82,53 @@ async fn get_jrd(
     Ok(jrd)
 }
 
+/// Returns JRD of a local actor. If actor doesn't exist,
+/// tries to find or create a movie account with the requested username.
+async fn get_jrd_or_create_movie(
+    db_client: &mut impl DatabaseClient,
+    instance: Instance,
+    movie_provider: Option<&dyn MovieMetadataProvider>,
+    media_dir: &Path,
+    resource: &str,
+) -> Result<JsonResourceDescriptor, HttpError> {
+    let error = match get_jrd(db_client, instance.clone(), resource).await {
+        Ok(jrd) => return Ok(jrd),
+        Err(error) => error,
+    };
+    let actor_address = match parse_acct_uri(resource) {
+        Ok(actor_address) if actor_address.hostname == instance.hostname() => actor_address,
+        _ => return Err(error),
+    };
+    // The requested username may be an alias of existing movie account.
+    // Otherwise lookup the movie in TMDB and create a local user.
+    let user = match get_or_create_media_user(
+        &instance,
+        db_client,
+        movie_provider,
+        media_dir,
+        &actor_address.username,
+    )
+    .await
+    {
+        Ok(user) => user,
+        Err(MovieError::NotMovie | MovieError::NotFoundError(_)) => return Err(error),
+        Err(MovieError::Ambiguous(candidates)) => {
+            return Err(HttpError::ValidationError(format!(
+                "multiple movies match {}: {}",
+                actor_address.username,
+                candidates.join(", "),
+            )));
+        }
+        Err(MovieError::DatabaseError(db_error)) => return Err(db_error.into()),
+        Err(other_error) => {
+            log::error!("Failed to create movie user: {}", other_error);
+            return Err(HttpError::InternalError);
+        }
+    };
+    let canonical_resource = format!("acct:{}@{}", user.profile.username, instance.hostname());
+    get_jrd(db_client, instance, &canonical_resource).await
+}
+
 #[get("/.well-known/webfinger")]
 pub async fn webfinger_view(
     config: web::Data<Config>,
@@ -85,33 +136,15 @@ pub async fn webfinger_view(
     query_params: web::Query<WebfingerQueryParams>,
 ) -> Result<HttpResponse, HttpError> {
     let db_client = &mut **get_database_client(&db_pool).await?;
-    let jrd = match get_jrd(db_client, config.instance(), &query_params.resource).await {
-        Ok(jrd) => jrd,
-        Err(_) => {
-            // Lookup the movie in TMDB and create a local user. By now we know that the local
-            // user for this movie does not exist.
-            let config: &Config = &config;
-            if let Some(api_key) = &config.tmdb_api_key {
-                let movie_account = parse_acct_uri(&query_params.resource)?;
-                let instance = config.instance();
-                let storage = MediaStorage::from(config);
-                lookup_and_create_movie_user(
-                    &instance,
-                    db_client,
-                    api_key,
-                    &storage.media_dir,
-                    &movie_account.username,
-                    config.movie_user_password.clone(),
-                )
-                .await
-                .map_err(|err| {
-                    log::error!("Failed to create movie user: {}", err);
-                    HttpError::InternalError
-                })?;
-            }
-            get_jrd(db_client, config.instance(), &query_params.resource).await?
-        }
-    };
+    let movie_provider = get_movie_metadata_provider(&config);
+    let jrd = get_jrd_or_create_movie(
+        db_client,
+        config.instance(),
+        movie_provider.as_deref(),
+        &MediaStorage::from(config.as_ref()).media_dir,
+        &query_params.resource,
+    )
+    .await?;
     let response = HttpResponse::Ok().content_type(JRD_CONTENT_TYPE).json(jrd);
     Ok(response)
 }
@@ -121,10 +154,15 @@ mod tests {
     use super::*;
     use fedimovies_models::{
         database::test_utils::create_test_database,
-        users::{queries::create_user, types::UserCreateData},
+        users::{
+            queries::{create_user, get_user_by_name},
+            types::UserCreateData,
+        },
     };
     use serial_test::serial;
 
+    use crate::tmdb::FileMetadataProvider;
+
     #[test]
     fn test_parse_acct_uri() {
         let uri = "acct:user_1@example.com";
@@ -152,4 +190,73 @@ mod tests {
             "https://example.com/users/test",
         );
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_jrd_create_movie() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+        let resource = "acct:TheBatman_2022@example.com";
+        let jrd = get_jrd_or_create_movie(
+            db_client,
+            instance.clone(),
+            Some(&movie_provider),
+            &media_dir,
+            resource,
+        )
+        .await
+        .unwrap();
+        assert_eq!(jrd.subject, "acct:TheBatman_2022_414906@example.com");
+        assert_eq!(
+            jrd.links[0].href.as_ref().unwrap(),
+            "https://example.com/users/TheBatman_2022_414906",
+        );
+        let user = get_user_by_name(db_client, "TheBatman_2022_414906")
+            .await
+            .unwrap();
+        assert_eq!(user.profile.display_name.as_deref(), Some("The Batman"));
+
+        // Short form is an alias
+        let jrd = get_jrd_or_create_movie(db_client, instance, None, &media_dir, resource)
+            .await
+            .unwrap();
+        assert_eq!(jrd.subject, "acct:TheBatman_2022_414906@example.com");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_jrd_create_movie_ambiguous() {
+        let db_client = &mut create_test_database().await;
+        let instance = Instance::for_test("https://example.com");
+        let movie_provider = FileMetadataProvider::for_test();
+        let media_dir = std::env::temp_dir();
+        let result = get_jrd_or_create_movie(
+            db_client,
+            instance.clone(),
+            Some(&movie_provider),
+            &media_dir,
+            "acct:Pinocchio_2022@example.com",
+        )
+        .await;
+        let error = result.err().unwrap();
+        assert!(matches!(error, HttpError::ValidationError(_)));
+        assert_eq!(
+            error.to_string(),
+            "multiple movies match Pinocchio_2022: Pinocchio_2022_532639, Pinocchio_2022_960676",
+        );
+
+        let resource = "acct:Pinocchio_2022_960676@example.com";
+        let jrd = get_jrd_or_create_movie(
+            db_client,
+            instance,
+            Some(&movie_provider),
+            &media_dir,
+            resource,
+        )
+        .await
+        .unwrap();
+        assert_eq!(jrd.subject, resource);
+    }
 }